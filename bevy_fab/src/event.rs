@@ -0,0 +1,91 @@
+//! Events sent when bindings change, so that game logic can react to it.
+use bevy::ecs::{prelude::*, system::SystemParam};
+use bevy::utils::{hashbrown::hash_map::Entry, HashMap, HashSet};
+use fab::binding::{BindingKey, Id};
+
+use crate::{BevyModify, LocalBindings, ScopedBindings, Scopes, WorldBindings};
+
+/// A binding changed value, sent by the systems updating [`BevyModify::Items`]
+/// before they reset binding change tracking.
+///
+/// Setting a binding to the value it already has doesn't send an event.
+///
+/// Compare `binding` with the key returned by `WorldBindings::bindings.key("name")`
+/// to check which binding changed.
+///
+/// [`BevyModify::Items`]: fab::modify::Modify::Items
+#[derive(Debug)]
+pub struct BindingChanged<M> {
+    pub binding: BindingKey<M>,
+    /// The entity with the [`LocalBindings`] or [`ScopedBindings`] where the
+    /// binding changed, `None` if it is a [`WorldBindings`] binding.
+    pub entity: Option<Entity>,
+}
+
+/// The value of each binding when its last [`BindingChanged`] was sent.
+///
+/// Values are removed with the binding, or with the [`LocalBindings`] or
+/// [`ScopedBindings`] they are in.
+pub(crate) struct SentValues<M>(HashMap<(Option<Entity>, Id), M>);
+impl<M> Default for SentValues<M> {
+    fn default() -> Self {
+        SentValues(HashMap::default())
+    }
+}
+
+/// Send [`BindingChanged`] events for bindings which value changed since
+/// the last time this ran.
+#[derive(SystemParam)]
+pub struct BindingEvents<'w, 's, M: BevyModify> {
+    events: EventWriter<'w, BindingChanged<M>>,
+    sent: Local<'s, SentValues<M>>,
+    removed_locals: RemovedComponents<'w, 's, LocalBindings<M>>,
+    removed_scopes: RemovedComponents<'w, 's, ScopedBindings<M>>,
+}
+impl<'w, 's, M: BevyModify + PartialEq> BindingEvents<'w, 's, M> {
+    /// Send an event if `value` differs from the last value sent for `binding`.
+    fn send_if_new(&mut self, entity: Option<Entity>, binding: BindingKey<M>, value: &M) {
+        match self.sent.0.entry((entity, binding.id())) {
+            Entry::Occupied(sent) if sent.get() == value => return,
+            Entry::Occupied(mut sent) => {
+                sent.insert(value.clone());
+            }
+            Entry::Vacant(sent) => {
+                sent.insert(value.clone());
+            }
+        }
+        self.events.send(BindingChanged { binding, entity });
+    }
+    /// Send a [`BindingChanged`] for each binding of `world` and the
+    /// [`ScopedBindings`] which value changed since the last call.
+    pub(crate) fn send(&mut self, world: &WorldBindings<M>, scopes: &Scopes<M>) {
+        let removed = self.removed_locals.iter().chain(self.removed_scopes.iter());
+        let removed: HashSet<_> = removed.map(Some).collect();
+        if !removed.is_empty() {
+            self.sent
+                .0
+                .retain(|(entity, _), _| !removed.contains(entity));
+        }
+        for (entity, binding) in scopes.removed() {
+            self.sent.0.remove(&(Some(entity), binding.id()));
+        }
+        for (binding, value) in world.bindings.changed() {
+            self.send_if_new(None, binding, value);
+        }
+        for (entity, binding, value) in scopes.changed() {
+            self.send_if_new(Some(entity), binding, value);
+        }
+    }
+    /// Send a [`BindingChanged`] for each binding of the `local` of `entity`
+    /// which value changed since the last call.
+    pub(crate) fn send_local(
+        &mut self,
+        entity: Entity,
+        local: &LocalBindings<M>,
+        world: &WorldBindings<M>,
+    ) {
+        for (binding, value) in local.bindings.changed(&world.bindings) {
+            self.send_if_new(Some(entity), binding, value);
+        }
+    }
+}
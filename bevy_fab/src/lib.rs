@@ -1,9 +1,13 @@
 #![allow(clippy::new_without_default)]
 //! Integrate the [`fab`] crate with bevy.
 
+mod event;
 pub mod fmt_system;
 mod local;
 mod make;
+mod scope;
+#[cfg(feature = "style_sheet")]
+pub mod style_sheet;
 mod track;
 pub mod trait_extensions;
 mod world;
@@ -15,53 +19,164 @@ use bevy::app::{App, CoreSet, Plugin};
 use bevy::ecs::prelude::*;
 use bevy::ecs::query::WorldQuery;
 use bevy::ecs::system::{EntityCommands, StaticSystemParam, SystemParam, SystemParamItem};
-use fab::modify::{FieldsOf, Indexed};
+use fab::modify::{Changed, FieldsOf, Indexed};
 use fab_parse::Parsable;
 use reflect_query::predefined::QueryablePlugin;
 
+pub use event::{BindingChanged, BindingEvents};
 pub use fmt_system::{FmtSystem, IntoFmtSystem};
 pub use local::LocalBindings;
 pub use make::{parse_into_resolver_system, ParseFormatString};
 pub use reflect_query::ReflectQueryable;
-pub use track::UserFmt;
-pub use world::{update_hooked, Hook, StyleFn, Styles, WorldBindings};
+pub use scope::{Ancestors, ScopedBindings, Scopes};
+#[cfg(feature = "style_sheet")]
+pub use style_sheet::StyleSheet;
+pub use track::{Read, UserFmt, Write, WriteBack, WriteBackError};
+pub use world::{update_hooked, Hook, MarkupTags, StyleFn, Styles, WorldBindings};
 
+/// Access the [`fab::Modify::Item`] of a [`WorldQuery`] item without
+/// triggering bevy's change detection.
 pub trait MakeMut<'a, I: 'a> {
-    fn make_mut(self) -> I;
+    /// The item, bypassing change detection.
+    fn make_mut(&'a mut self) -> I;
+    /// Mark as changed the `i`th component of the item for each bit `i` set
+    /// in `components`.
+    fn set_changed(&mut self, components: u32);
 }
-impl<'a, T1, T2> MakeMut<'a, (&'a mut T1, &'a mut T2)> for (Mut<'a, T1>, Mut<'a, T2>) {
-    fn make_mut(self) -> (&'a mut T1, &'a mut T2) {
-        (self.0.into_inner(), self.1.into_inner())
-    }
+/// Implement [`MakeMut`] for tuples of [`Mut`], `(Mut<T0>, Mut<T1>, …)`
+/// becomes a `(&mut T0, &mut T1, …)`.
+macro_rules! impl_make_mut_tuple {
+    ($( $ty:ident $index:tt ),*) => {
+        impl<'a, 'w, $( $ty ),*> MakeMut<'a, ( $( &'a mut $ty, )* )> for ( $( Mut<'w, $ty>, )* ) {
+            fn make_mut(&'a mut self) -> ( $( &'a mut $ty, )* ) {
+                ( $( self.$index.bypass_change_detection(), )* )
+            }
+            fn set_changed(&mut self, components: u32) {
+                $( if components & (1 << $index) != 0 {
+                    self.$index.set_changed();
+                } )*
+            }
+        }
+    };
 }
+impl_make_mut_tuple!(T0 0);
+impl_make_mut_tuple!(T0 0, T1 1);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_make_mut_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
 
 // omg please don't look at this, I swear this is temporary
 /// A [`fab::Modify`] that works on a bevy component and can be inserted in the ECS.
 pub trait BevyModify: Parsable + Send + Sync + 'static {
     type Param: SystemParam;
-    type ItemsCtorData: Send + Sync;
+    type ItemsCtorData: Clone + Send + Sync;
 
     fn set_content(&mut self, s: Arguments);
     fn init_content(s: Arguments) -> Self;
 
     fn context<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Context<'a>;
+    /// The [`Modify::context_fields`] that changed since the last update.
+    ///
+    /// Modifiers depending on them are applied again by the update systems.
+    /// By default, the context never changes.
+    ///
+    /// [`Modify::context_fields`]: fab::Modify::context_fields
+    fn context_changes(_param: &SystemParamItem<Self::Param>) -> FieldsOf<Self> {
+        FieldsOf::<Self>::empty()
+    }
+    /// The components of [`Items`] holding `fields`, as a bit set: bit `i`
+    /// stands for the `i`th component of the [`Modify::Item`] tuple.
+    ///
+    /// Only those components are marked as changed when a modifier changing
+    /// `fields` changes an item. By default, all components are.
+    ///
+    /// [`Modify::Item`]: fab::Modify::Item
+    fn changed_components(_fields: FieldsOf<Self>) -> u32 {
+        u32::MAX
+    }
 
     fn spawn_items(
         extra: &Self::ItemsCtorData,
         items: Vec<Self::MakeItem>,
         cmds: &mut EntityCommands,
     );
+    /// Despawn the entities spawned by [`BevyModify::spawn_items`], before
+    /// the format string is parsed and its items spawned again.
+    ///
+    /// Other children of the entity must be kept. By default, nothing is
+    /// despawned.
+    fn despawn_items(_cmds: &mut EntityCommands) {}
     fn add_update_system(app: &mut App);
 }
 
+/// A component listing the entities holding the items of a [`LocalBindings`].
+///
+/// [`update_children_system`] updates [`LocalBindings`] in parallel, giving
+/// each of them mutable access to the entities listed in their `UniqueEntities`.
+///
+/// # Safety
+///
+/// An entity must appear at most once in the list, and must not appear in
+/// any other component of the same type.
+pub unsafe trait UniqueEntities: Deref<Target = [Entity]> {}
+
+/// The [`fab::Modify::Items`] of a [`BevyModify`] that lives in child entities
+/// of the entity holding the [`LocalBindings`].
+///
+/// `children` is the list of entities (in order) where the items are located,
+/// `query` is used to access them.
 pub struct Items<'a, 'w, 's, C, It: WorldQuery> {
     children: Option<&'a C>,
-    query: Query<'w, 's, It>,
+    // NOTE: `Query<'w, 's, _>` is covariant, `'w` and `'s` are shortened to `'a`
+    // so that we don't need `'w: 'a` bounds on `Modify::Items`.
+    query: &'a Query<'a, 'a, It>,
+    /// The last item returned by `get_mut` and its index, kept to mark its
+    /// components as changed in `set_changed`.
+    current: Option<(usize, It::Item<'a>)>,
+    _lifetimes: PhantomData<(&'w (), &'s ())>,
 }
 
 impl<'a, 'w, 's, C: Component + Deref<Target = [Entity]>, It: WorldQuery> Items<'a, 'w, 's, C, It> {
-    pub fn new(children: Option<&'a C>, query: Query<'w, 's, It>) -> Self {
-        Items { children, query }
+    pub fn new(children: Option<&'a C>, query: &'a mut Query<'w, 's, It>) -> Self {
+        // Since we hold an exclusive reference to `query`, no other `Items`
+        // can access the same entities.
+        Items {
+            children,
+            query,
+            current: None,
+            _lifetimes: PhantomData,
+        }
+    }
+    /// Create an `Items` from a shared reference to the `query`.
+    ///
+    /// # Safety
+    ///
+    /// While this `Items` is alive, no other `Items` built from the same `query`
+    /// may have an entity in common with `children`.
+    unsafe fn shared(children: Option<&'a C>, query: &'a Query<'w, 's, It>) -> Self {
+        Items {
+            children,
+            query,
+            current: None,
+            _lifetimes: PhantomData,
+        }
+    }
+    fn fetch(&mut self, index: usize) -> Option<&mut It::Item<'a>> {
+        let &entity = self.children?.get(index)?;
+        let query: &'a Query<'a, 'a, It> = self.query;
+        // SAFETY: `Items` is either constructed with an exclusive reference to
+        // the query, or by `Items::shared` which requires to not alias
+        // entities. The item is stored in `self.current`, replacing the
+        // previous one, so a single item per `Items` can exist at a time.
+        let item = unsafe { query.get_unchecked(entity) }.ok()?;
+        Some(&mut self.current.insert((index, item)).1)
     }
 }
 impl<'a, 'w, 's, C, Wq, M> Indexed<M> for Items<'a, 'w, 's, C, Wq>
@@ -69,48 +184,125 @@ where
     C: Component + Deref<Target = [Entity]>,
     Wq: WorldQuery,
     M: BevyModify,
-    for<'b> Wq::Item<'b>: MakeMut<'b, M::Item<'b>>,
+    for<'b, 'c> Wq::Item<'b>: MakeMut<'c, M::Item<'c>>,
 {
     #[inline]
     fn get_mut(&mut self, index: usize) -> Option<M::Item<'_>> {
-        let &entity = self.children?.get(index)?;
-        Some(self.query.get_mut(entity).ok()?.make_mut())
+        Some(self.fetch(index)?.make_mut())
+    }
+    fn set_changed(&mut self, index: usize, fields: FieldsOf<M>) {
+        let components = M::changed_components(fields);
+        let item = match &mut self.current {
+            Some((current, item)) if *current == index => Some(item),
+            _ => self.fetch(index),
+        };
+        if let Some(item) = item {
+            item.set_changed(components);
+        }
     }
 }
 
-pub fn update_children_system<C, Wq: WorldQuery, BM: BevyModify>(
-    mut query: Query<(&mut LocalBindings<BM>, Option<&C>)>,
+/// Update all [`BevyModify::Items`] located in the children of [`LocalBindings`].
+///
+/// The [`LocalBindings`] are updated in parallel. [`WorldBindings`] and
+/// [`ScopedBindings`] change tracking is reset after all of them are updated,
+/// a [`BindingChanged`] event is sent for each binding that changed value
+/// beforehand.
+///
+/// [`BevyModify::Items`]: fab::modify::Modify::Items
+pub fn update_children_system<C, Wq, BM>(
+    mut query: Query<(Entity, &mut LocalBindings<BM>, Option<&C>)>,
     mut world_bindings: ResMut<WorldBindings<BM>>,
+    mut scopes: Scopes<BM>,
+    mut events: BindingEvents<BM>,
     ctx_params: StaticSystemParam<BM::Param>,
     items_query: Query<Wq>,
 ) where
-    C: Component + Deref<Target = [Entity]>,
-    BM: for<'a, 'w, 's> Parsable<Items<'a, 'w, 's> = Items<'a, 'w, 's, C, Wq>>,
-    for<'b> Wq::Item<'b>: MakeMut<'b, BM::Item<'b>>,
+    C: Component + UniqueEntities,
+    Wq: WorldQuery,
+    BM: BevyModify
+        + PartialEq
+        + for<'a, 'w, 's> Parsable<Items<'a, 'w, 's> = Items<'a, 'w, 's, C, Wq>>,
+    for<'b, 'c> Wq::Item<'b>: MakeMut<'c, BM::Item<'c>>,
+    for<'a> BM::Context<'a>: Sync,
     FieldsOf<BM>: Sync + Send,
 {
-    let context = BM::context(&ctx_params);
-    let mut items = Items { children: None, query: items_query };
-    for (mut local_data, children) in &mut query {
-        items.children = children;
-        local_data.update(&mut items, &world_bindings, &context);
+    events.send(&world_bindings, &scopes);
+    // Only `LocalBindings` changed outside of this system can have changed bindings.
+    for (entity, local, _) in query.iter_mut().filter(|(_, local, _)| local.is_changed()) {
+        events.send_local(entity, &local, &world_bindings);
     }
+
+    let context = BM::context(&ctx_params);
+    let context_changes = BM::context_changes(&ctx_params);
+    let (context, bindings, items_query) = (&context, &*world_bindings, &items_query);
+    let scopes_ref = &scopes;
+
+    query
+        .par_iter_mut()
+        .for_each_mut(|(entity, mut local_data, children)| {
+            // SAFETY: `C: UniqueEntities`, so entities in `children` are not
+            // duplicated and no two `LocalBindings` share children.
+            let mut items = unsafe { Items::shared(children, items_query) };
+            let scopes = scopes_ref.ancestors_of(entity);
+            // Resetting change tracking shouldn't mark `LocalBindings` as changed,
+            // `BindingEvents` relies on it to only visit updated `LocalBindings`.
+            let local_data = local_data.bypass_change_detection();
+            local_data.root_data.mark_updated(context_changes);
+            local_data.update(&mut items, bindings, &scopes, context);
+        });
     world_bindings.bindings.reset_changes();
+    scopes.reset_changes();
 }
 
-pub fn update_component_items<BM: BevyModify>(
-    mut query: Query<(&mut LocalBindings<BM>, &mut BM::Items<'_, '_, '_>)>,
+/// Update all [`BevyModify::Items`] located in the same entity as [`LocalBindings`].
+///
+/// The [`LocalBindings`] are updated in parallel. [`WorldBindings`] and
+/// [`ScopedBindings`] change tracking is reset after all of them are updated,
+/// a [`BindingChanged`] event is sent for each binding that changed value
+/// beforehand.
+///
+/// The `Items` component is only marked as changed if a modifier changed it,
+/// see [`fab::modify::Changed`].
+///
+/// [`BevyModify::Items`]: fab::modify::Modify::Items
+pub fn update_component_items<BM: BevyModify + PartialEq>(
+    mut query: Query<(Entity, &mut LocalBindings<BM>, &mut BM::Items<'_, '_, '_>)>,
     mut world_bindings: ResMut<WorldBindings<BM>>,
+    mut scopes: Scopes<BM>,
+    mut events: BindingEvents<BM>,
     params: StaticSystemParam<BM::Param>,
 ) where
     for<'a, 'b, 'c> BM::Items<'a, 'b, 'c>: Component,
+    for<'a> BM::Context<'a>: Sync,
     FieldsOf<BM>: Sync + Send,
 {
-    let context = BM::context(&params);
-    for (mut local_data, mut items) in &mut query {
-        local_data.update(&mut items, &world_bindings, &context);
+    events.send(&world_bindings, &scopes);
+    // Only `LocalBindings` changed outside of this system can have changed bindings.
+    for (entity, local, _) in query.iter_mut().filter(|(_, local, _)| local.is_changed()) {
+        events.send_local(entity, &local, &world_bindings);
     }
+
+    let context = BM::context(&params);
+    let context_changes = BM::context_changes(&params);
+    let (context, bindings, scopes_ref) = (&context, &*world_bindings, &scopes);
+
+    query
+        .par_iter_mut()
+        .for_each_mut(|(entity, mut local_data, mut items)| {
+            let scopes = scopes_ref.ancestors_of(entity);
+            // Resetting change tracking shouldn't mark `LocalBindings` as changed,
+            // `BindingEvents` relies on it to only visit updated `LocalBindings`.
+            let local_data = local_data.bypass_change_detection();
+            local_data.root_data.mark_updated(context_changes);
+            let items_mut = items.bypass_change_detection();
+            let changed = local_data.update(items_mut, bindings, &scopes, context);
+            if changed == Changed::Yes {
+                items.set_changed();
+            }
+        });
     world_bindings.bindings.reset_changes();
+    scopes.reset_changes();
 }
 
 /// Manages [`BevyModify`] living in the ECS as [`LocalBindings`] and a global
@@ -134,8 +326,21 @@ where
         app.add_plugin(QueryablePlugin)
             .init_resource::<WorldBindings<BM>>()
             .init_resource::<Styles<BM>>()
+            .init_resource::<MarkupTags<BM>>()
+            .add_event::<BindingChanged<BM>>()
             .add_system(update_hooked::<BM>.in_base_set(PostUpdate))
             .add_system(parse_into_resolver_system::<BM>);
+        #[cfg(feature = "style_sheet")]
+        {
+            use bevy::asset::{AddAsset, Assets};
+
+            // Several `FabPlugin`s may share the style sheet assets.
+            if !app.world.contains_resource::<Assets<StyleSheet>>() {
+                app.add_asset::<StyleSheet>()
+                    .init_asset_loader::<style_sheet::StyleSheetLoader>();
+            }
+            app.add_system(style_sheet::update_style_sheets::<BM>);
+        }
         BM::add_update_system(app);
     }
 }
@@ -1,7 +1,7 @@
 //! Local entity-scopped data relevant to [`Modify`]s located in the bevy ECS.
 use bevy::ecs::prelude::Component;
 
-use fab::{binding, modify::Changing, resolve::Resolver, Modify};
+use fab::{binding, modify::Changed, modify::Changing, resolve::Resolver, Modify};
 
 use crate::WorldBindings;
 
@@ -16,19 +16,26 @@ impl<M: Modify> LocalBindings<M> {
     ///
     /// Only the relevant sections of `to_update` are updated. The change trackers
     /// are then reset.
+    ///
+    /// `scopes` are the bindings of the ancestors of this entity, see
+    /// [`ScopedBindings`](crate::ScopedBindings).
+    ///
+    /// Returns whether any of the `items` changed.
     pub fn update(
         &mut self,
         items: &mut M::Items<'_, '_, '_>,
         world: &WorldBindings<M>,
+        scopes: &[&binding::Scope<M>],
         ctx: &M::Context<'_>,
-    ) {
+    ) -> Changed {
         let Self { root_data, bindings, resolver } = self;
 
         // TODO(clean): this code should be in cuicui_fab
-        let view = world.bindings.view_with_local(bindings).unwrap();
-        resolver.update(items, root_data, view, ctx);
+        let view = world.bindings.view_with_scopes(bindings, scopes).unwrap();
+        let changed = resolver.update(items, root_data, view, ctx);
         root_data.reset_updated();
         bindings.reset_changes();
+        changed
     }
     pub(crate) fn new(resolver: M::Resolver, root_data: M::MakeItem) -> Self {
         LocalBindings {
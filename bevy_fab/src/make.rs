@@ -2,16 +2,18 @@ use std::{marker::PhantomData, mem};
 
 use bevy::ecs::{prelude::*, system::SystemState};
 use fab::{modify::FieldsOf, resolve::Resolver};
-use fab_parse::hook as parse;
+use fab_parse::{hook as parse, Syntax, Tags};
 use log::error;
 
 #[cfg(doc)]
 use crate::world;
-use crate::{BevyModify, LocalBindings, Styles, WorldBindings};
+use crate::{BevyModify, LocalBindings, MarkupTags, Styles, WorldBindings};
 
 #[derive(Component)]
 pub struct ParseFormatString<BM: BevyModify> {
     pub format_string: String,
+    /// The markup language of `format_string`.
+    pub syntax: Syntax,
     pub default_item: BM::MakeItem,
     pub items_extra: Option<BM::ItemsCtorData>,
     _p: PhantomData<fn(BM)>,
@@ -24,19 +26,47 @@ impl<BM: BevyModify> ParseFormatString<BM> {
     ) -> Self {
         let _p = PhantomData;
         let items_extra = Some(items_extra);
-        Self { format_string, default_item, items_extra, _p }
+        let syntax = Syntax::FormatString;
+        Self {
+            format_string,
+            syntax,
+            default_item,
+            items_extra,
+            _p,
+        }
     }
     /// Drain all fields from a `&mut Self` to get an owned value.
-    fn take(&mut self) -> (BM::ItemsCtorData, BM::MakeItem, String) {
+    fn take(&mut self) -> (BM::ItemsCtorData, BM::MakeItem, String, Syntax) {
         (
             self.items_extra.take().unwrap(),
             self.default_item.clone(),
             mem::take(&mut self.format_string),
+            self.syntax,
         )
     }
 }
 
-/// Create a [`Resolver`] by parsing `format_string`.
+impl<BM: BevyModify> Clone for ParseFormatString<BM> {
+    fn clone(&self) -> Self {
+        ParseFormatString {
+            format_string: self.format_string.clone(),
+            syntax: self.syntax,
+            default_item: self.default_item.clone(),
+            items_extra: self.items_extra.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+/// The [`ParseFormatString`] an entity was made from, to parse it again when
+/// a [`StyleSheet`] changes.
+///
+/// [`StyleSheet`]: crate::StyleSheet
+#[cfg(feature = "style_sheet")]
+#[derive(Component)]
+pub(crate) struct SourceFormatString<BM: BevyModify>(pub(crate) ParseFormatString<BM>);
+
+/// Create a [`Resolver`] by parsing `format_string`, written in `syntax`.
 ///
 /// Effects:
 ///
@@ -50,13 +80,15 @@ impl<BM: BevyModify> ParseFormatString<BM> {
 fn mk<'fstr, BM: BevyModify>(
     bindings: &mut WorldBindings<BM>,
     style: &mut Styles<BM>,
+    tags: &'fstr Tags,
     default_item: &BM::MakeItem,
     context: &BM::Context<'_>,
     format_string: &'fstr str,
+    syntax: Syntax,
 ) -> anyhow::Result<(Vec<BM::MakeItem>, BM::Resolver, Vec<parse::Hook<'fstr>>)> {
     let mut new_hooks = Vec::new();
 
-    let tree = fab_parse::format_string(format_string)?;
+    let tree = syntax.parse(format_string, tags)?;
     let tree = style.process(tree.transform());
     let parsed = tree.finish(&mut bindings.bindings, &mut new_hooks);
     let parsed: Vec<_> = parsed.into_iter().collect::<anyhow::Result<_>>()?;
@@ -66,22 +98,27 @@ fn mk<'fstr, BM: BevyModify>(
     Ok((items, resolver, new_hooks))
 }
 
+/// The system parameters used by [`parse_into_resolver_system`] to make the
+/// [`LocalBindings`] once the format strings are taken out of the `World`.
+type MakeParams<BM> = (
+    Commands<'static, 'static>,
+    ResMut<'static, Styles<BM>>,
+    ResMut<'static, WorldBindings<BM>>,
+    Query<'static, 'static, &'static mut LocalBindings<BM>>,
+    <BM as BevyModify>::Param,
+);
+
 /// Replaces [`ParseFormatString`] with [`LocalBindings`],
 /// updating [`WorldBindings<BM>`].
 ///
+/// If the entity already had [`LocalBindings`], its bindings are kept.
+///
 /// This is an exclusive system, as it requires access to the [`World`] to generate
 /// the [`world::Hook`]s specified in the format string.
 pub fn parse_into_resolver_system<BM: BevyModify + 'static>(
     world: &mut World,
     mut to_make: Local<QueryState<(Entity, &mut ParseFormatString<BM>)>>,
-    mut cache: Local<
-        SystemState<(
-            Commands,
-            ResMut<Styles<BM>>,
-            ResMut<WorldBindings<BM>>,
-            BM::Param,
-        )>,
-    >,
+    mut cache: Local<SystemState<MakeParams<BM>>>,
 ) where
     FieldsOf<BM>: Sync + Send,
 {
@@ -96,6 +133,9 @@ pub fn parse_into_resolver_system<BM: BevyModify + 'static>(
     if to_make.is_empty() {
         return;
     }
+    // Cloned, since the parsed format strings borrow from it until the hooks
+    // are created, which requires exclusive world access.
+    let tags = Tags::clone(world.resource::<MarkupTags<BM>>());
 
     // The `parse::Hook`s returned by `mk`
     // have a lifetime dependent on the `format_string` used.
@@ -107,19 +147,34 @@ pub fn parse_into_resolver_system<BM: BevyModify + 'static>(
     // Furthermore, `richtext::mk` needs mutable access to WorldBindings and
     // immutable to the context, so we use the SystemState to extract them.
     {
-        let (mut cmds, mut styles, mut world_bindings, params) = cache.get_mut(world);
+        let (mut cmds, mut styles, mut world_bindings, mut locals, params) = cache.get_mut(world);
 
         let context = BM::context(&params);
 
         // TODO(perf): batch commands update.
-        for (entity, (ctor_data, item, fmt)) in to_make.iter() {
-            match mk(&mut world_bindings, &mut styles, item, &context, fmt) {
-                Ok((items, resolver, mut hooks)) => {
-                    new_hooks.append(&mut hooks);
+        for (entity, (ctor_data, item, fmt, syntax)) in to_make.iter() {
+            let bindings = &mut world_bindings;
+            let made = mk(bindings, &mut styles, &tags, item, &context, fmt, *syntax);
+            match made {
+                Ok((items, resolver, hooks)) => {
+                    new_hooks.extend(hooks.into_iter().map(|hook| (*entity, hook)));
 
+                    let mut local = LocalBindings::<BM>::new(resolver, item.clone());
+                    if let Ok(mut old_local) = locals.get_mut(*entity) {
+                        local.bindings = mem::take(&mut old_local.bindings);
+                        local.bindings.mark_changed();
+                    }
                     let mut cmds = cmds.entity(*entity);
-                    cmds.insert(LocalBindings::<BM>::new(resolver, item.clone()));
+                    cmds.insert(local);
                     BM::spawn_items(ctor_data, items, &mut cmds);
+                    #[cfg(feature = "style_sheet")]
+                    cmds.insert(SourceFormatString::<BM>(ParseFormatString {
+                        format_string: fmt.clone(),
+                        syntax: *syntax,
+                        default_item: item.clone(),
+                        items_extra: Some(ctor_data.clone()),
+                        _p: PhantomData,
+                    }));
                 }
                 Err(err) => {
                     error!("Error '{err}' when building '''{fmt}'''")
@@ -132,8 +187,15 @@ pub fn parse_into_resolver_system<BM: BevyModify + 'static>(
 
     // To convert the parse::Hook into an actual world::Hook that goes into world::Hooks,
     // we need excluisve world access.
+    //
+    // Hooks are owned by the entity of their format string, so that parsing
+    // it again (for example when a style sheet changes) replaces them.
     world.resource_scope(|world, mut bindings: Mut<WorldBindings<BM>>| {
-        let parse_hook = |&hook| bindings.parse_hook(hook, world);
-        new_hooks.iter().for_each(parse_hook);
+        for (entity, _) in &to_make {
+            bindings.remove_hooks_of(*entity);
+        }
+        for &(entity, hook) in &new_hooks {
+            bindings.parse_owned_hook(entity, hook, world);
+        }
     });
 }
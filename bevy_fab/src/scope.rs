@@ -0,0 +1,78 @@
+//! Bindings shared by all [`LocalBindings`] in an entity subtree.
+use std::iter;
+
+use bevy::ecs::{prelude::*, system::SystemParam};
+use bevy::hierarchy::Parent;
+use fab::binding::{self, BindingKey};
+use smallvec::SmallVec;
+
+use crate::BevyModify;
+#[cfg(doc)]
+use crate::{LocalBindings, WorldBindings};
+
+/// Bindings visible to all [`LocalBindings`] in the descendants of this entity.
+///
+/// When a [`LocalBindings`] is updated, the bindings of its ancestors' `ScopedBindings`
+/// are looked up before the [`WorldBindings`], the closest ancestor first.
+/// This lets a panel provide `{title}` to all format strings it contains,
+/// without affecting format strings outside of it.
+///
+/// Use [`WorldBindings::bindings`]'s `key` method to get the key of a binding name.
+#[derive(Component)]
+pub struct ScopedBindings<M: BevyModify> {
+    pub bindings: binding::Scope<M>,
+}
+impl<M: BevyModify> Default for ScopedBindings<M> {
+    fn default() -> Self {
+        ScopedBindings { bindings: Default::default() }
+    }
+}
+
+/// The [`ScopedBindings`] of the ancestors of an entity, from its parent to
+/// the hierarchy root, see [`Scopes::ancestors_of`].
+///
+/// Entities rarely have more than a few ancestors with [`ScopedBindings`],
+/// so this avoids allocating on each update.
+pub type Ancestors<'a, M> = SmallVec<[&'a binding::Scope<M>; 4]>;
+
+/// Access the [`ScopedBindings`] of the ancestors of entities.
+#[derive(SystemParam)]
+pub struct Scopes<'w, 's, M: BevyModify> {
+    parents: Query<'w, 's, &'static Parent>,
+    scopes: Query<'w, 's, (Entity, &'static mut ScopedBindings<M>)>,
+}
+impl<'w, 's, M: BevyModify> Scopes<'w, 's, M> {
+    /// The scopes of all ancestors of `entity`, from its parent to the hierarchy root.
+    pub fn ancestors_of(&self, entity: Entity) -> Ancestors<'_, M> {
+        if self.scopes.is_empty() {
+            return Ancestors::new();
+        }
+        let parent = |entity| self.parents.get(entity).ok().map(Parent::get);
+        let ancestors = iter::successors(parent(entity), |&entity| parent(entity));
+        let scopes = ancestors.filter_map(|ancestor| self.scopes.get(ancestor).ok());
+        scopes.map(|(_, scope)| &scope.bindings).collect()
+    }
+    /// Bindings changed since the last `reset_changes`, with the entity of
+    /// the scope they are in.
+    pub fn changed(&self) -> impl Iterator<Item = (Entity, BindingKey<M>, &M)> + '_ {
+        self.scopes.iter().flat_map(|(entity, scope)| {
+            let changed = scope.bindings.changed();
+            changed.map(move |(key, modify)| (entity, key, modify))
+        })
+    }
+    /// Bindings removed since the last `reset_changes`, with the entity of
+    /// the scope they were in.
+    pub fn removed(&self) -> impl Iterator<Item = (Entity, BindingKey<M>)> + '_ {
+        self.scopes.iter().flat_map(|(entity, scope)| {
+            let removed = scope.bindings.removed();
+            removed.map(move |key| (entity, key))
+        })
+    }
+    /// Reset the change tracking of all scopes.
+    pub fn reset_changes(&mut self) {
+        let reset = |(_, mut scope): (_, Mut<ScopedBindings<M>>)| {
+            scope.bypass_change_detection().bindings.reset_changes();
+        };
+        self.scopes.iter_mut().for_each(reset);
+    }
+}
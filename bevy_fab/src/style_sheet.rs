@@ -0,0 +1,357 @@
+//! Styles described in RON files, loaded as [`StyleSheet`] assets.
+//!
+//! A style sheet declares aliases and chops, like [`Styleable::alias_mods`]
+//! and [`Styleable::chop`] do in code:
+//!
+//! ```ron
+//! (
+//!     aliases: {
+//!         "Title": "Font: fonts/FiraSans-Bold.ttf, FontSize: 32, Color: gold",
+//!     },
+//!     chops: {
+//!         "Rainbow": (split: ByChar, modifier: "HueOffset", values: [(inputs: [Param, Index])]),
+//!         "Sine": (
+//!             split: ByWord,
+//!             modifier: "FontSize",
+//!             values: [(inputs: [Param, Curve], offset: 20.0)],
+//!             curve: Some(CatmullRom([1.0, 0.0, 1.0, 0.0, 1.0, 0.0])),
+//!         ),
+//!     },
+//! )
+//! ```
+//!
+//! When a style sheet file changes, the format strings parsed with the
+//! previous version are parsed again with the new one.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use bevy::asset::{AssetEvent, AssetLoader, Assets, LoadContext, LoadedAsset};
+use bevy::ecs::prelude::*;
+use bevy::math::cubic_splines::{BSpline, Bezier, CardinalSpline, CubicCurve, CubicGenerator};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use fab_parse::{Parsable, Split, Styleable};
+use log::warn;
+use serde::Deserialize;
+
+use crate::make::SourceFormatString;
+use crate::{BevyModify, Styles};
+
+// The variants have the same names as `Split`'s, for `serde(remote)`.
+#[allow(clippy::enum_variant_names)]
+#[derive(Deserialize)]
+#[serde(remote = "Split")]
+enum SplitDef {
+    ByWord,
+    ByChar,
+    ByLine,
+}
+
+/// What the [`Value`]s of a [`Chop`] depend on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Input {
+    /// The value of the chop in the format string, such as `30` in
+    /// `{Rainbow: 30|text}`.
+    Param,
+    /// The index of the chopped section, starting at `0`.
+    Index,
+    /// The index of the chopped section over the number of chopped sections,
+    /// from `0` to `1`.
+    Progress,
+    /// The position of the [`Chop::curve`] at this section.
+    Curve,
+}
+
+/// A parameter of the modifier added by a [`Chop`], `offset + scale × inputs`.
+///
+/// `inputs` are multiplied together, so with no inputs, the value is
+/// `offset + scale`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Value {
+    #[serde(default)]
+    pub inputs: Vec<Input>,
+    #[serde(default = "one")]
+    pub scale: f32,
+    #[serde(default)]
+    pub offset: f32,
+}
+fn one() -> f32 {
+    1.0
+}
+impl Value {
+    fn get(&self, sample: &Sample) -> f32 {
+        let inputs = self.inputs.iter().map(|&input| sample.get(input));
+        self.offset + self.scale * inputs.product::<f32>()
+    }
+}
+
+/// The [`Input`]s of a chopped section.
+#[derive(Default)]
+struct Sample {
+    param: f32,
+    index: usize,
+    progress: f32,
+    curve: f32,
+}
+impl Sample {
+    fn get(&self, input: Input) -> f32 {
+        match input {
+            Input::Param => self.param,
+            Input::Index => self.index as f32,
+            Input::Progress => self.progress,
+            Input::Curve => self.curve,
+        }
+    }
+}
+
+/// A one-dimensional cubic curve, see [`bevy::math::cubic_splines`].
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum Curve {
+    CatmullRom(Vec<f32>),
+    Cardinal { tension: f32, points: Vec<f32> },
+    BSpline(Vec<f32>),
+    Bezier(Vec<[f32; 4]>),
+}
+impl Curve {
+    fn has_segments(&self) -> bool {
+        match self {
+            Curve::CatmullRom(points) | Curve::BSpline(points) => points.len() >= 4,
+            Curve::Cardinal { points, .. } => points.len() >= 4,
+            Curve::Bezier(segments) => !segments.is_empty(),
+        }
+    }
+    fn to_curve(&self) -> CubicCurve<f32> {
+        match self {
+            Curve::CatmullRom(points) => CardinalSpline::new_catmull_rom(&points[..]).to_curve(),
+            Curve::Cardinal { tension, points } => {
+                CardinalSpline::new(*tension, &points[..]).to_curve()
+            }
+            Curve::BSpline(points) => BSpline::new(&points[..]).to_curve(),
+            Curve::Bezier(segments) => Bezier::new(&segments[..]).to_curve(),
+        }
+    }
+}
+
+/// Split the sections with the chop's name, and add a `modifier` to each
+/// of them, see [`Styleable::chop`].
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Chop {
+    #[serde(with = "SplitDef")]
+    pub split: Split,
+    /// The name of the modifier added to each chopped section.
+    pub modifier: String,
+    /// The parameters of `modifier`, separated by commas.
+    pub values: Vec<Value>,
+    /// The curve sampled by [`Input::Curve`], from its start on the first
+    /// chopped section to its end after the last one.
+    #[serde(default)]
+    pub curve: Option<Curve>,
+}
+impl Chop {
+    fn value(&self, sample: &Sample) -> String {
+        let value = |value: &Value| value.get(sample).to_string();
+        let values: Vec<_> = self.values.iter().map(value).collect();
+        values.join(", ")
+    }
+    fn is_valid<M: Parsable>(&self, name: &str) -> bool {
+        let Err(err) = M::parse(&self.modifier, &self.value(&Sample::default())) else {
+            return true;
+        };
+        let err: anyhow::Error = err.into();
+        warn!("Ignoring the `{name}` chop of a style sheet: {err}");
+        false
+    }
+    fn modifier<M: Parsable>(&self, sample: &Sample) -> M {
+        let parse = |value: String| M::parse(&self.modifier, &value).ok();
+        let fallback = || parse(self.value(&Sample::default()));
+        // unwrap: `is_valid` is true for chops in `Styles`, it parses `fallback`.
+        parse(self.value(sample)).or_else(fallback).unwrap()
+    }
+    fn apply<'a, M: Parsable>(&self, name: &str, tree: Styleable<'a, M>) -> Styleable<'a, M> {
+        let curve = self.curve.as_ref().map(Curve::to_curve);
+        let segment_count = |curve: &CubicCurve<f32>| curve.iter_samples(1, |_, t| t).last();
+        let segment_count = curve.as_ref().and_then(segment_count).unwrap_or(0.0);
+
+        let mut sample = Sample::default();
+        tree.chop(self.split, name, move |input: &str, count| {
+            // Each occurence of the chop in the format string starts over.
+            if sample.index >= count {
+                sample.index = 0;
+            }
+            if sample.index == 0 {
+                sample.param = input.trim().parse().unwrap_or_else(|_| {
+                    warn!("The `{name}` chop expects a number, got `{input}`");
+                    0.0
+                });
+            }
+            sample.progress = sample.index as f32 / count as f32;
+            let t = sample.progress * segment_count;
+            sample.curve = curve.as_ref().map_or(0.0, |curve| curve.position(t));
+
+            let modifier = self.modifier(&sample);
+            sample.index += 1;
+            modifier
+        })
+    }
+}
+
+/// The modifiers replacing an alias, see [`Styleable::alias_mods`].
+///
+/// In a style sheet, it is written as a list of modifiers, like in format
+/// strings: `"Font: fonts/FiraSans-Bold.ttf, Color: gold"`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Alias(pub Vec<(String, String)>);
+impl TryFrom<String> for Alias {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> anyhow::Result<Self> {
+        let owned = |(name, value): (&str, Cow<str>)| (name.to_owned(), value.into_owned());
+        let modifiers = fab_parse::modifiers(&value)?;
+        Ok(Alias(modifiers.into_iter().map(owned).collect()))
+    }
+}
+
+/// Aliases and chops loaded from a `.style.ron` file.
+///
+/// Use [`AppStylesExtension::add_style_sheet`] to use it when parsing format
+/// strings.
+///
+/// [`AppStylesExtension::add_style_sheet`]: crate::trait_extensions::AppStylesExtension::add_style_sheet
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, TypeUuid)]
+#[uuid = "a7da4f12-07a0-4b3b-9c86-78e07c5d1299"]
+pub struct StyleSheet {
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Alias>,
+    #[serde(default)]
+    pub chops: BTreeMap<String, Chop>,
+}
+impl StyleSheet {
+    /// Read a style sheet written in RON.
+    pub fn from_ron(bytes: &[u8]) -> anyhow::Result<Self> {
+        let sheet: StyleSheet = ron::de::from_bytes(bytes)?;
+        for (name, chop) in &sheet.chops {
+            let too_short = |curve: &Curve| !curve.has_segments();
+            if chop.curve.as_ref().is_some_and(too_short) {
+                anyhow::bail!("The curve of the `{name}` chop doesn't have enough points");
+            }
+        }
+        Ok(sheet)
+    }
+    /// This style sheet without the modifiers that `M` can't parse.
+    ///
+    /// A warning is logged for each ignored modifier.
+    pub fn valid_for<M: Parsable>(&self) -> StyleSheet {
+        let valid_modifier = |alias: &str, (name, value): &(String, String)| {
+            let Err(err) = M::parse(name, value) else {
+                return true;
+            };
+            let err: anyhow::Error = err.into();
+            warn!("Ignoring `{name}` in the `{alias}` alias of a style sheet: {err}");
+            false
+        };
+        let valid_alias = |(name, Alias(modifiers)): (&String, &Alias)| {
+            let modifiers = modifiers.iter().filter(|m| valid_modifier(name, m));
+            (name.clone(), Alias(modifiers.cloned().collect()))
+        };
+        let valid_chop = |(name, chop): (&String, &Chop)| {
+            let valid = chop.is_valid::<M>(name);
+            valid.then(|| (name.clone(), chop.clone()))
+        };
+        StyleSheet {
+            aliases: self.aliases.iter().map(valid_alias).collect(),
+            chops: self.chops.iter().filter_map(valid_chop).collect(),
+        }
+    }
+    /// Apply the aliases, then the chops of this style sheet to `tree`.
+    pub fn apply<'a, M: Parsable>(&self, tree: Styleable<'a, M>) -> Styleable<'a, M> {
+        let parse = |(name, value): &(String, String)| M::parse(name, value).ok();
+        let alias = |tree: Styleable<'a, M>, (name, Alias(modifiers)): (&String, &Alias)| {
+            tree.alias_mods(name, |_| modifiers.iter().filter_map(parse))
+        };
+        let tree = self.aliases.iter().fold(tree, alias);
+        let apply_chop = |tree, (name, chop): (&String, &Chop)| chop.apply(name, tree);
+        self.chops.iter().fold(tree, apply_chop)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StyleSheetLoader;
+impl AssetLoader for StyleSheetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, anyhow::Result<()>> {
+        Box::pin(async move {
+            let sheet = StyleSheet::from_ron(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(sheet));
+            Ok(())
+        })
+    }
+    fn extensions(&self) -> &[&str] {
+        &["style.ron"]
+    }
+}
+
+/// Update the [`StyleSheet`]s of [`Styles`] when they are loaded or changed,
+/// and parse again the format strings parsed with their previous version.
+pub(crate) fn update_style_sheets<BM: BevyModify>(
+    mut cmds: Commands,
+    mut events: EventReader<AssetEvent<StyleSheet>>,
+    sheets: Res<Assets<StyleSheet>>,
+    mut styles: ResMut<Styles<BM>>,
+    parsed: Query<(Entity, &SourceFormatString<BM>)>,
+) {
+    let mut changed = false;
+    for event in events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle } => handle,
+            AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { handle } => handle,
+        };
+        changed |= styles.update_sheet(handle, sheets.get(handle));
+    }
+    if !changed {
+        return;
+    }
+    for (entity, SourceFormatString(source)) in &parsed {
+        let mut cmds = cmds.entity(entity);
+        BM::despawn_items(&mut cmds);
+        cmds.insert(source.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_style_sheet() {
+        let sheet = br#"(
+            aliases: { "Title": "Font: bold.ttf, FontSize: 32" },
+            chops: {
+                "Wave": (split: ByChar, modifier: "Offset", values: [(), (inputs: [Param, Curve])], curve: Some(BSpline([0.0, 1.0, 0.0, 1.0]))),
+            },
+        )"#;
+        let sheet = StyleSheet::from_ron(sheet).unwrap();
+
+        let title = [("Font", "bold.ttf"), ("FontSize", "32")];
+        let title = title.map(|(name, value)| (name.to_owned(), value.to_owned()));
+        assert_eq!(sheet.aliases["Title"], Alias(title.to_vec()));
+
+        let wave = &sheet.chops["Wave"];
+        assert_eq!(wave.split, Split::ByChar);
+        let sample = Sample { param: 2.0, curve: 0.5, ..Sample::default() };
+        assert_eq!(wave.value(&sample), "1, 1");
+    }
+    #[test]
+    fn invalid_style_sheet() {
+        let bad_alias = br#"(aliases: { "Title": "Font: {font}" })"#;
+        assert!(StyleSheet::from_ron(bad_alias).is_err());
+        let short_curve = br#"(chops: {
+            "Sine": (split: ByWord, modifier: "FontSize", values: [()], curve: Some(CatmullRom([1.0, 0.0]))),
+        })"#;
+        assert!(StyleSheet::from_ron(short_curve).is_err());
+    }
+}
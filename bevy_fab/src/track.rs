@@ -3,7 +3,9 @@
 
 mod read;
 mod write;
+mod write_back;
 
 pub use read::{GetError, ParseError, Read};
 pub(crate) use write::UserFmts;
 pub use write::{Error as WriteError, UserFmt, Write};
+pub use write_back::{WriteBack, WriteBackError};
@@ -6,7 +6,7 @@ use bevy::core::Name;
 use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
 use bevy::ecs::world::EntityRef;
 use bevy::ecs::{prelude::*, query::QuerySingleError, reflect::ReflectResourceFns};
-use bevy::reflect::{ParsedPath, Reflect, ReflectPathError, TypeData};
+use bevy::reflect::{FromType, ParsedPath, Reflect, ReflectPathError, TypeData};
 use fab_parse::tree as parse;
 use reflect_query::queries::{EntityQuerydyn, RefQuerydyn};
 use reflect_query::{Ref, ReflectQueryable, ReflectQueryableFns};
@@ -15,7 +15,7 @@ use thiserror::Error;
 pub type NewResult<T> = Result<T, ParseError>;
 pub type GetResult<'a> = Result<Ref<'a, dyn Reflect>, GetError>;
 
-type ReflectMut = unsafe fn(UnsafeWorldCell) -> Option<Mut<dyn Reflect>>;
+pub(crate) type ReflectMut = unsafe fn(UnsafeWorldCell) -> Option<Mut<dyn Reflect>>;
 type FromEntity = fn(EntityRef) -> Option<Ref<dyn Reflect>>;
 
 #[derive(Debug, Error)]
@@ -79,6 +79,7 @@ pub(crate) enum Query {
     Name(NameAccess),
     One(OneAccess),
     Marked(MarkedAccess),
+    Entity(EntityAccess),
 }
 
 /// Returned by [`Read::query`] and used by [`Read::get`].
@@ -93,6 +94,7 @@ pub struct QueryState(QueryStateInner);
 enum QueryStateInner {
     Res,
     Name,
+    Entity,
     Marked(EntityQuerydyn),
     One(RefQuerydyn),
 }
@@ -123,11 +125,47 @@ impl Read {
         };
         Ok(Read { query })
     }
+    /// Read the `R` resource, without going through the type registry.
+    pub fn resource<R: Resource + Reflect + FromWorld>() -> Self {
+        let reflect = <ReflectResource as FromType<R>>::from_type();
+        let from_world = cast_to_resource_fns(&reflect).reflect_unchecked_mut;
+        let query = Query::Res(ResAccess { from_world, path: None });
+        Read { query }
+    }
+    /// Read the `C` component of `entity`, without going through the type registry.
+    pub fn component<C: Component + Reflect>(entity: Entity) -> Self {
+        let reflect = <ReflectQueryable as FromType<C>>::from_type();
+        let from_entity = get_queryable_fns(&reflect).reflect_ref;
+        let query = Query::Entity(EntityAccess { entity, from_entity, path: None });
+        Read { query }
+    }
+    /// Read the field at `path` of the component of `entity` reflected by `queryable`.
+    ///
+    /// Like [`Read::component`], but for components only known through the
+    /// type registry.
+    pub fn component_field(
+        entity: Entity,
+        queryable: &ReflectQueryable,
+        path: &str,
+    ) -> NewResult<Self> {
+        let from_entity = get_queryable_fns(queryable).reflect_ref;
+        let path = get_path(path)?;
+        let query = Query::Entity(EntityAccess { entity, from_entity, path });
+        Ok(Read { query })
+    }
+    /// The entity this reads from, if created with [`Read::component`].
+    pub fn entity(&self) -> Option<Entity> {
+        match &self.query {
+            Query::Entity(access) => Some(access.entity),
+            _ => None,
+        }
+    }
     /// Prepare queries so that they can be ran.
     pub fn query(&mut self, world: &mut World) -> QueryState {
         match &self.query {
             Query::Res(_) => QueryStateInner::Res.into(),
             Query::Name(_) => QueryStateInner::Name.into(),
+            Query::Entity(_) => QueryStateInner::Entity.into(),
             Query::One(access) => QueryStateInner::One(access.query(world)).into(),
             Query::Marked(access) => QueryStateInner::Marked(access.query(world)).into(),
         }
@@ -138,13 +176,14 @@ impl Read {
             (Query::Res(access), Res) => access.get(world),
             (Query::One(access), One(state)) => access.get(state, world),
             (Query::Name(access), Name) => access.get(world),
+            (Query::Entity(access), Entity) => access.get(world),
             (Query::Marked(access), Marked(state)) => access.get(state, world),
             _ => panic!("cuicui bug, shouldn't call Read::get with a query not created by it"),
         }
     }
 }
 
-fn cast_to_resource_fns(reflect: &ReflectResource) -> ReflectResourceFns {
+pub(super) fn cast_to_resource_fns(reflect: &ReflectResource) -> ReflectResourceFns {
     // SAFETY: we are casting from `ReflectResource` to `ReflectResourceFns`
     // This is ill-advised, as ReflectResource isn't #[repr(transparent)],
     // however, this isn't unsound as of the current (1.69) version of rust,
@@ -153,16 +192,16 @@ fn cast_to_resource_fns(reflect: &ReflectResource) -> ReflectResourceFns {
     let new_ref: &ReflectResourceFns = unsafe { NonNull::from(reflect).cast().as_ref() };
     new_ref.clone()
 }
-pub fn reflect_ref(f: ReflectMut, world: &World) -> Option<Ref<dyn Reflect>> {
+pub fn reflect_ref(f: ReflectMut, world: &World) -> Option<Ref<'_, dyn Reflect>> {
     // SAFETY: We convert this immediately into immutable value
     let reflect_mut = unsafe { f(world.as_unsafe_world_cell_readonly()) };
     reflect_mut.map(|r| Ref::map_from(r.into(), |i| i))
 }
-fn get_queryable_fns(reflect: &ReflectQueryable) -> ReflectQueryableFns {
+pub(super) fn get_queryable_fns(reflect: &ReflectQueryable) -> ReflectQueryableFns {
     reflect.get().clone()
 }
 
-fn get_path(path: &str) -> NewResult<Option<ParsedPath>> {
+pub(super) fn get_path(path: &str) -> NewResult<Option<ParsedPath>> {
     match path {
         "" => Ok(None),
         path => Ok(Some(ParsedPath::parse(path)?)),
@@ -180,7 +219,7 @@ fn read_opt_path<'a, 'p>(
 fn read_path<'a>(path: &Option<ParsedPath>, reflect: Ref<'a, dyn Reflect>) -> GetResult<'a> {
     Ok(reflect.map_failable(|r| read_opt_path(path, r))?)
 }
-fn get_data<T: TypeData, Out>(
+pub(super) fn get_data<T: TypeData, Out>(
     world: &World,
     type_name: &str,
     f: impl FnOnce(&T) -> Out,
@@ -208,7 +247,7 @@ fn get_data<T: TypeData, Out>(
 ///
 /// Returns the first encountered `Entity` with given `name`.
 /// `None` if no such thing exist.
-fn get_with_name(world: &mut World, name: &Name) -> Option<Entity> {
+pub(super) fn get_with_name(world: &mut World, name: &Name) -> Option<Entity> {
     world
         .query::<(Entity, &Name)>()
         .iter(world)
@@ -240,6 +279,12 @@ pub(crate) struct NameAccess {
     path: Option<ParsedPath>,
     name: Name,
 }
+#[derive(Clone)]
+pub(crate) struct EntityAccess {
+    entity: Entity,
+    from_entity: FromEntity,
+    path: Option<ParsedPath>,
+}
 impl ResAccess {
     fn get<'a>(&self, world: &'a World) -> GetResult<'a> {
         let resource = reflect_ref(self.from_world, world).ok_or(GetError::NotInWorld)?;
@@ -306,6 +351,16 @@ impl NameAccess {
     }
 }
 
+impl EntityAccess {
+    fn get<'a>(&self, world: &'a World) -> GetResult<'a> {
+        use GetError::{NoComponent, NoEntity};
+
+        let entity = world.get_entity(self.entity).ok_or(NoEntity)?;
+        let entity = (self.from_entity)(entity).ok_or(NoComponent)?;
+        read_path(&self.path, entity)
+    }
+}
+
 //
 // fmt::Display impls
 //
@@ -337,6 +392,15 @@ impl fmt::Display for NameAccess {
         Ok(())
     }
 }
+impl fmt::Display for EntityAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entity({:?}).<accessed_type>", self.entity)?;
+        if let Some(path) = &self.path {
+            write!(f, ".{}", path)?;
+        }
+        Ok(())
+    }
+}
 impl fmt::Display for MarkedAccess {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Marked(<marker_type>).<accessed_type>")?;
@@ -44,6 +44,14 @@ impl<M> UserFmt<M> {
     ) -> Self {
         UserFmt::Function(Arc::new(dyn_fn))
     }
+    /// Like [`UserFmt::from_fn`], but with a known type as input.
+    ///
+    /// Panics when ran on a value that is not a `T`.
+    pub fn from_typed_fn<T: Reflect>(
+        typed_fn: impl Fn(&T, binding::Entry<M>) + Send + Sync + 'static,
+    ) -> Self {
+        UserFmt::from_fn(move |reflect, entry| typed_fn(reflect.downcast_ref().unwrap(), entry))
+    }
     fn arc_clone(&self) -> Self {
         match self {
             UserFmt::System(sys) => UserFmt::System(Arc::clone(sys)),
@@ -102,6 +110,14 @@ pub enum Write<M> {
     Debug,
 }
 impl<M: BevyModify> Write<M> {
+    /// Print a `T` as a [`BevyModify::set_content`] displayed with its
+    /// [`fmt::Display`] implementation.
+    ///
+    /// Panics when ran on a value that is not a `T`.
+    pub fn display<T: Reflect + fmt::Display>() -> Self {
+        let display = |value: &T, entry: binding::Entry<M>| set_content(entry, value);
+        Write::Arbitrary(UserFmt::from_typed_fn(display))
+    }
     pub fn modify(&self, world: &World, value: &dyn Reflect, entry: binding::Entry<M>) {
         match self {
             Write::Format(fmt) => set_content(entry, &DisplayReflect(value, Some(fmt))),
@@ -0,0 +1,205 @@
+//! Write values into the [`World`], at a reflection path. This is the reverse
+//! of [`Read`](super::Read), used for editable widgets.
+
+use std::{any, marker::PhantomData};
+
+use bevy::core::Name;
+use bevy::ecs::{prelude::*, query::QuerySingleError, reflect::ReflectComponent};
+use bevy::reflect::{ParsedPath, Reflect};
+use fab_parse::tree as parse;
+use thiserror::Error;
+
+use super::read::{
+    cast_to_resource_fns, get_data, get_path, get_queryable_fns, get_with_name, GetError,
+    NewResult, ParseError, ReflectMut,
+};
+
+#[derive(Debug, Error)]
+pub enum WriteBackError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error(transparent)]
+    Get(#[from] GetError),
+
+    #[error("Can't write a `{0}` into the `{1}` field")]
+    BadType(&'static str, Box<str>),
+}
+
+type GetSingleMut = fn(&mut World) -> Result<Mut<dyn Reflect>, QuerySingleError>;
+type GetSingleEntity = fn(&mut World) -> Result<Entity, QuerySingleError>;
+
+#[derive(Clone)]
+enum Target {
+    Res(ReflectMut),
+    One(GetSingleMut),
+    Name(Entity, ReflectComponent),
+    Marked(GetSingleEntity, ReflectComponent),
+}
+impl Target {
+    fn new(query: parse::Query, world: &mut World) -> NewResult<Self> {
+        let component = |world: &World, name| get_data(world, name, ReflectComponent::clone);
+        Ok(match query {
+            parse::Query::Res(res) => {
+                Target::Res(get_data(world, res, cast_to_resource_fns)?.reflect_unchecked_mut)
+            }
+            parse::Query::One(one) => {
+                Target::One(get_data(world, one, get_queryable_fns)?.get_single_mut)
+            }
+            parse::Query::Name { name, access } => {
+                let name = Name::new(name.to_owned());
+                let not_in_world = || ParseError::NotInWorld(name.to_string());
+                let entity = get_with_name(world, &name).ok_or_else(not_in_world)?;
+                Target::Name(entity, component(world, access)?)
+            }
+            parse::Query::Marked { marker, access } => {
+                let get_entity = get_data(world, marker, get_queryable_fns)?.get_single_entity;
+                Target::Marked(get_entity, component(world, access)?)
+            }
+        })
+    }
+    fn get_mut<'a>(&self, world: &'a mut World) -> Result<Mut<'a, dyn Reflect>, GetError> {
+        let component_mut = |world: &'a mut World, entity, component: &ReflectComponent| {
+            let world = world.as_unsafe_world_cell();
+            let entity = world.get_entity(entity).ok_or(GetError::NoEntity)?;
+            // SAFETY: We have exclusive access to the world.
+            let reflect = unsafe { component.reflect_unchecked_mut(entity) };
+            reflect.ok_or(GetError::NoComponent)
+        };
+        match self {
+            Target::Res(from_world) => {
+                // SAFETY: We have exclusive access to the world.
+                let reflect = unsafe { from_world(world.as_unsafe_world_cell()) };
+                reflect.ok_or(GetError::NotInWorld)
+            }
+            Target::One(get_single_mut) => Ok(get_single_mut(world)?),
+            Target::Name(entity, component) => component_mut(world, *entity, component),
+            Target::Marked(get_entity, component) => {
+                let entity = get_entity(world)?;
+                component_mut(world, entity, component)
+            }
+        }
+    }
+}
+
+/// Write `T` values into the [`World`], at the location described by a
+/// binding source, such as `Res(Settings).volume`.
+///
+/// This is how editable widgets (text inputs, sliders, checkboxes…) update
+/// the value they display.
+///
+/// The target type is checked when creating the `WriteBack` if it already
+/// exists in the world, otherwise [`WriteBack::write`] returns an error.
+pub struct WriteBack<T> {
+    target: Target,
+    path: Option<ParsedPath>,
+    _value: PhantomData<fn(T)>,
+}
+impl<T: Reflect> WriteBack<T> {
+    /// Create a `WriteBack` writing into `source`.
+    ///
+    /// Use [`fab_parse::source`] to parse `source` from a string.
+    pub fn from_parsed(source: parse::Source, world: &mut World) -> Result<Self, WriteBackError> {
+        let write_back = WriteBack {
+            target: Target::new(source.query, world)?,
+            path: get_path(source.reflect_path)?,
+            _value: PhantomData,
+        };
+        write_back.check_type(world)?;
+        Ok(write_back)
+    }
+    /// Check that the target is a `T`, if it exists in the world.
+    fn check_type(&self, world: &mut World) -> Result<(), WriteBackError> {
+        let Ok(target) = self.target.get_mut(world) else { return Ok(()); };
+        let Ok(field) = self.field(&*target) else { return Ok(()); };
+
+        if field.as_any().is::<T>() {
+            Ok(())
+        } else {
+            let found = field.type_name().into();
+            Err(WriteBackError::BadType(any::type_name::<T>(), found))
+        }
+    }
+    fn field<'a>(&self, target: &'a dyn Reflect) -> Result<&'a dyn Reflect, GetError> {
+        match &self.path {
+            None => Ok(target),
+            Some(path) => Ok(path.reflect_element(target)?),
+        }
+    }
+    /// Set the target of this `WriteBack` to `value`.
+    pub fn write(&mut self, world: &mut World, value: T) -> Result<(), WriteBackError> {
+        let mut target = self.target.get_mut(world)?;
+        let field = match &mut self.path {
+            None => &mut *target,
+            Some(path) => {
+                let field = path.reflect_element_mut(&mut *target);
+                field.map_err(GetError::from)?
+            }
+        };
+        let type_name = field.type_name().into();
+        let bad_type = |_| WriteBackError::BadType(any::type_name::<T>(), type_name);
+        field.set(Box::new(value)).map_err(bad_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::AppTypeRegistry;
+    use bevy::ecs::reflect::ReflectResource;
+
+    use super::*;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Settings {
+        volume: f32,
+    }
+
+    fn registry_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Settings>();
+        world.insert_resource(registry);
+        world
+    }
+    fn write_back<T: Reflect>(
+        world: &mut World,
+        source: &str,
+    ) -> Result<WriteBack<T>, WriteBackError> {
+        WriteBack::from_parsed(fab_parse::source(source).unwrap(), world)
+    }
+
+    #[test]
+    fn write_resource_field() {
+        let mut world = registry_world();
+        world.insert_resource(Settings { volume: 1.0 });
+
+        let mut volume = write_back::<f32>(&mut world, "Res(Settings).volume").unwrap();
+        volume.write(&mut world, 0.5).unwrap();
+        assert_eq!(world.resource::<Settings>().volume, 0.5);
+    }
+    #[test]
+    fn unknown_target() {
+        let mut world = registry_world();
+        let not_registered = write_back::<f32>(&mut world, "Res(Volume).volume");
+        assert!(matches!(not_registered, Err(WriteBackError::Parse(_))));
+    }
+    #[test]
+    fn bad_type() {
+        let mut world = registry_world();
+        world.insert_resource(Settings { volume: 1.0 });
+        let bad_type = write_back::<u32>(&mut world, "Res(Settings).volume");
+        assert!(matches!(bad_type, Err(WriteBackError::BadType(..))));
+
+        // The type can't be checked before the resource exists, `write` checks it.
+        let mut world = registry_world();
+        let mut volume = write_back::<u32>(&mut world, "Res(Settings).volume").unwrap();
+        let not_in_world = volume.write(&mut world, 3);
+        assert!(matches!(not_in_world, Err(WriteBackError::Get(_))));
+
+        world.insert_resource(Settings { volume: 1.0 });
+        let bad_type = volume.write(&mut world, 3);
+        assert!(matches!(bad_type, Err(WriteBackError::BadType(..))));
+        assert_eq!(world.resource::<Settings>().volume, 1.0);
+    }
+}
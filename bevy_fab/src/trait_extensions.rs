@@ -1,11 +1,19 @@
 //! Extensions to the `App` to
+use std::fmt;
+
+#[cfg(feature = "style_sheet")]
+use bevy::asset::AssetServer;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::{Component, Entity, FromWorld, Resource, World};
 use bevy::{app::App, prelude::Mut, reflect::Reflect};
 use fab::binding::Entry;
 use fab_parse::Styleable;
 
+#[cfg(doc)]
+use crate::ScopedBindings;
 use crate::{
     fmt_system::{FmtSystem, IntoFmtSystem},
-    track::UserFmt,
+    track::{Read, UserFmt, Write},
     BevyModify, Styles, WorldBindings,
 };
 
@@ -25,6 +33,12 @@ pub trait AppStylesExtension<M: BevyModify> {
     fn add_style<F>(&mut self, style: F) -> &mut Self
     where
         F: FnMut(Styleable<M>) -> Styleable<M> + Send + Sync + 'static;
+    /// Load the [`StyleSheet`] at `path`, and apply it before the styles
+    /// added with [`AppStylesExtension::add_style`].
+    ///
+    /// [`StyleSheet`]: crate::StyleSheet
+    #[cfg(feature = "style_sheet")]
+    fn add_style_sheet(&mut self, path: &str) -> &mut Self;
 }
 impl<M: BevyModify> AppStylesExtension<M> for App {
     fn overwrite_style<F>(&mut self, style: F) -> &mut Self
@@ -43,6 +57,13 @@ impl<M: BevyModify> AppStylesExtension<M> for App {
         styles.add(style);
         self
     }
+    #[cfg(feature = "style_sheet")]
+    fn add_style_sheet(&mut self, path: &str) -> &mut Self {
+        let sheet = self.world.resource::<AssetServer>().load(path);
+        let Some(mut styles) = self.world.get_resource_mut::<Styles<M>>() else { return self; };
+        styles.add_sheet(sheet);
+        self
+    }
 }
 
 pub trait AppFormattersExtension<M: BevyModify> {
@@ -70,13 +91,7 @@ pub trait AppFormattersExtension<M: BevyModify> {
         name: impl AsRef<str>,
         fmt: impl Fn(&T, Entry<M>) + Send + Sync + 'static,
     ) -> &mut Self {
-        self.add_user_fmt(
-            name,
-            UserFmt::from_fn(move |reflect, e| {
-                let value = reflect.downcast_ref().unwrap();
-                fmt(value, e)
-            }),
-        )
+        self.add_user_fmt(name, UserFmt::from_typed_fn(fmt))
     }
 }
 impl<M: BevyModify> AppFormattersExtension<M> for App {
@@ -97,3 +112,78 @@ impl<M: BevyModify> AppFormattersExtension<M> for App {
         self
     }
 }
+
+/// Extension trait to bind resources to [`WorldBindings`] without declaring
+/// a hook in the format string.
+pub trait AppBindExtension<M: BevyModify> {
+    /// Update the `name` binding with `fmt` each time the `R` resource changes.
+    ///
+    /// Format strings can then use `{name}` instead of `{Res(R)}`.
+    fn bind_resource<R: Resource + Reflect + FromWorld>(
+        &mut self,
+        name: impl AsRef<str>,
+        fmt: impl Fn(&R, Entry<M>) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+impl<M: BevyModify> AppBindExtension<M> for App {
+    fn bind_resource<R: Resource + Reflect + FromWorld>(
+        &mut self,
+        name: impl AsRef<str>,
+        fmt: impl Fn(&R, Entry<M>) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let write = Write::Arbitrary(UserFmt::from_typed_fn(fmt));
+        let mut world_bindings = self.world.resource_mut::<WorldBindings<M>>();
+        world_bindings.bind(name, Read::resource::<R>(), write);
+        self
+    }
+}
+
+/// Extension trait to bind components of an entity to the [`ScopedBindings`]
+/// of that entity without declaring a hook in the format string.
+///
+/// Only format strings in the descendants of the entity see the binding, so
+/// that several entities can use the same binding name. The binding is not
+/// updated anymore once the entity is despawned.
+pub trait EntityCommandsBindExtension<M: BevyModify> {
+    /// Update the `name` binding with the [`Display`] of the `C` component of
+    /// this entity each time it changes.
+    ///
+    /// [`Display`]: fmt::Display
+    fn bind_component<C>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        C: Component + Reflect + fmt::Display;
+
+    /// Update the `name` binding with `fmt` each time the `C` component of
+    /// this entity changes.
+    fn bind_component_with<C: Component + Reflect>(
+        &mut self,
+        name: impl Into<String>,
+        fmt: impl Fn(&C, Entry<M>) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+impl<M: BevyModify> EntityCommandsBindExtension<M> for EntityCommands<'_, '_, '_> {
+    fn bind_component<C>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        C: Component + Reflect + fmt::Display,
+    {
+        bind_component::<M, C>(self, name.into(), Write::display::<C>())
+    }
+    fn bind_component_with<C: Component + Reflect>(
+        &mut self,
+        name: impl Into<String>,
+        fmt: impl Fn(&C, Entry<M>) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let write = Write::Arbitrary(UserFmt::from_typed_fn(fmt));
+        bind_component::<M, C>(self, name.into(), write)
+    }
+}
+fn bind_component<'c, 'w, 's, 'a, M: BevyModify, C: Component + Reflect>(
+    cmds: &'c mut EntityCommands<'w, 's, 'a>,
+    name: String,
+    write: Write<M>,
+) -> &'c mut EntityCommands<'w, 's, 'a> {
+    cmds.add(move |entity: Entity, world: &mut World| {
+        let mut world_bindings = world.resource_mut::<WorldBindings<M>>();
+        world_bindings.bind_scoped(entity, name, Read::component::<C>(entity), write);
+    })
+}
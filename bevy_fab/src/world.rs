@@ -1,13 +1,22 @@
 //! Global world-scopped data relevant to [`BevyModify`]s located in the bevy ECS.
 
-use bevy::prelude::{error, Mut, Resource, World};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "style_sheet")]
+use bevy::asset::Handle;
+use bevy::prelude::{error, DetectChangesMut, Entity, Mut, Resource, World};
 use fab::binding;
-use fab_parse::{Hook as ParsedHook, Styleable};
+use fab::resolve::{MakeModify, ModifyKind};
+use fab_parse::{Hook as ParsedHook, Styleable, Tags};
 use log::warn;
 use thiserror::Error;
 
+#[cfg(feature = "style_sheet")]
+use crate::style_sheet::StyleSheet;
 use crate::track::{GetError, ParseError, Read, UserFmt, UserFmts, Write, WriteError};
-use crate::BevyModify;
+use crate::{BevyModify, ScopedBindings};
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -68,15 +77,64 @@ pub type StyleFn<M> = Box<dyn FnMut(Styleable<'_, M>) -> Styleable<'_, M> + Send
 ///
 /// [`Styleable`] provides methods to make this a bit less error prone.
 /// Relying on mutable state in a `FnMut` is always a bit tricky.
+///
+/// # Style sheets
+///
+/// With the `style_sheet` feature, aliases and chops can also be loaded from
+/// a [`StyleSheet`] file. Style sheets are applied before the other styles.
 #[derive(Resource)]
 pub struct Styles<M> {
     styles: Vec<StyleFn<M>>,
+    /// The style sheets and their version valid for `M`, `None` until loaded.
+    #[cfg(feature = "style_sheet")]
+    sheets: Vec<(Handle<StyleSheet>, Option<StyleSheet>)>,
 }
 impl<M: BevyModify> Styles<M> {
     /// Apply all the styles to the `transform`
     pub(crate) fn process<'a>(&mut self, transform: Styleable<'a, M>) -> Styleable<'a, M> {
+        #[cfg(feature = "style_sheet")]
+        let transform = self
+            .sheets
+            .iter()
+            .filter_map(|(_, sheet)| sheet.as_ref())
+            .fold(transform, |acc, sheet| sheet.apply(acc));
         self.styles.iter_mut().fold(transform, |acc, f| f(acc))
     }
+    /// Add a [`StyleSheet`], applied after already added style sheets.
+    #[cfg(feature = "style_sheet")]
+    pub fn add_sheet(&mut self, sheet: Handle<StyleSheet>) {
+        self.sheets.push((sheet, None));
+    }
+    /// Set the content of the style sheet of `handle`, returns `false` if
+    /// `handle` is not one of the style sheets of this `Styles`.
+    #[cfg(feature = "style_sheet")]
+    pub(crate) fn update_sheet(
+        &mut self,
+        handle: &Handle<StyleSheet>,
+        sheet: Option<&StyleSheet>,
+    ) -> bool {
+        let Some((_, old)) = self.sheets.iter_mut().find(|(h, _)| h == handle) else {
+            return false;
+        };
+        *old = sheet.map(StyleSheet::valid_for::<M>);
+        true
+    }
+    /// The modifiers of `format_string` once these styles are applied, only
+    /// the static ones are kept, bindings are ignored.
+    ///
+    /// This is useful to reuse aliases outside of format strings, for example
+    /// `styles.static_modifiers("{Highlight: _|_}")`.
+    pub fn static_modifiers(&mut self, format_string: &str) -> anyhow::Result<Vec<M>> {
+        let tree = self.process(fab_parse::format_string(format_string)?.transform());
+        let mut bindings = binding::World::default();
+        let static_modifier = |modify: anyhow::Result<MakeModify<M>>| match modify?.kind {
+            ModifyKind::Modify(modify) => Ok(Some(modify)),
+            ModifyKind::Bound { .. } => Ok(None),
+        };
+        let modifiers = tree.finish(&mut bindings, &mut Vec::new());
+        let modifiers = modifiers.into_iter().map(static_modifier);
+        modifiers.filter_map(Result::transpose).collect()
+    }
     /// Insert a new style before all others.
     pub fn overwrite(
         &mut self,
@@ -89,12 +147,51 @@ impl<M: BevyModify> Styles<M> {
         self.styles.push(Box::new(style));
     }
     pub fn new(style: impl FnMut(Styleable<M>) -> Styleable<M> + Send + Sync + 'static) -> Self {
-        Styles { styles: vec![Box::new(style)] }
+        Styles {
+            styles: vec![Box::new(style)],
+            #[cfg(feature = "style_sheet")]
+            sheets: Vec::new(),
+        }
     }
 }
 impl<M> Default for Styles<M> {
     fn default() -> Self {
-        Styles { styles: Vec::new() }
+        Styles {
+            styles: Vec::new(),
+            #[cfg(feature = "style_sheet")]
+            sheets: Vec::new(),
+        }
+    }
+}
+
+/// The modifiers markup tags stand for, in format strings written in markdown
+/// or BBCode.
+///
+/// See [`fab_parse::Syntax`] for the available markup languages.
+#[derive(Resource)]
+pub struct MarkupTags<M> {
+    tags: Tags,
+    _p: PhantomData<fn(M)>,
+}
+impl<M> MarkupTags<M> {
+    pub fn new(tags: Tags) -> Self {
+        MarkupTags { tags, _p: PhantomData }
+    }
+}
+impl<M> Default for MarkupTags<M> {
+    fn default() -> Self {
+        MarkupTags::new(Tags::default())
+    }
+}
+impl<M> Deref for MarkupTags<M> {
+    type Target = Tags;
+    fn deref(&self) -> &Tags {
+        &self.tags
+    }
+}
+impl<M> DerefMut for MarkupTags<M> {
+    fn deref_mut(&mut self) -> &mut Tags {
+        &mut self.tags
     }
 }
 
@@ -113,8 +210,30 @@ pub struct Hook<M> {
     binding: binding::Id,
     read: Read,
     write: Write<M>,
+    /// The entity this hook was added for, see [`Hook::owned_by`].
+    owner: Option<Entity>,
+    /// The entity which [`ScopedBindings`] `binding` is in, see [`Hook::scoped_in`].
+    scope: Option<Entity>,
 }
 impl<M: BevyModify> Hook<M> {
+    /// Write the value read by `read` into the `binding`, as described by `write`.
+    pub fn new(binding: binding::Id, read: Read, write: Write<M>) -> Self {
+        Hook { binding, read, write, owner: None, scope: None }
+    }
+    /// Tie this hook to `owner`, it is removed when `owner` is despawned or
+    /// with [`WorldBindings::remove_hooks_of`].
+    pub fn owned_by(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+    /// Write into the [`ScopedBindings`] of `scope` rather than the [`WorldBindings`],
+    /// so that only format strings in the descendants of `scope` see the value.
+    ///
+    /// The hook is removed when `scope` is despawned.
+    pub fn scoped_in(mut self, scope: Entity) -> Self {
+        self.scope = Some(scope);
+        self
+    }
     fn from_parsed(
         hook: ParsedHook,
         world: &mut World,
@@ -125,6 +244,8 @@ impl<M: BevyModify> Hook<M> {
             binding: intern(hook.source.binding),
             read: Read::from_parsed(hook.source, world)?,
             write: Write::from_parsed(hook.format, writes)?,
+            owner: None,
+            scope: None,
         })
     }
 
@@ -139,14 +260,51 @@ impl<M: BevyModify> Hook<M> {
         world: &mut World,
         bindings: &mut binding::World<M>,
     ) -> Result<(), Error> {
+        match self.scope {
+            Some(scope) => self.read_into_scope(scope, world),
+            None => self.read_into(world, |id| bindings.entry(id)),
+        }
+    }
+    /// Like [`Hook::read_into_binding`], but write into the [`ScopedBindings`]
+    /// of `scope`, adding it if `scope` doesn't have one yet.
+    fn read_into_scope(&mut self, scope: Entity, world: &mut World) -> Result<(), Error> {
+        let mut entity = world.entity_mut(scope);
+        if !entity.contains::<ScopedBindings<M>>() {
+            entity.insert(ScopedBindings::<M>::default());
+        }
+        let mut scoped = entity.get_mut::<ScopedBindings<M>>().unwrap();
+        // `Write::modify` reads the `World`, so the bindings can't stay in it
+        // while being written to.
+        let mut bindings = mem::take(&mut scoped.bypass_change_detection().bindings);
+        let result = self.read_into(world, |id| bindings.entry(id));
+
+        let mut scoped = world.get_mut::<ScopedBindings<M>>(scope).unwrap();
+        scoped.bypass_change_detection().bindings = bindings;
+        result
+    }
+    fn read_into<'b>(
+        &mut self,
+        world: &mut World,
+        entry: impl FnOnce(binding::Id) -> binding::Entry<'b, M>,
+    ) -> Result<(), Error>
+    where
+        M: 'b,
+    {
         let state = self.read.query(world);
         let value = self.read.get(state, world)?;
         if value.is_changed() {
-            let entry = bindings.entry(self.binding);
+            let entry = entry(self.binding);
             self.write.modify(world, value.into_inner(), entry);
         }
         Ok(())
     }
+    /// Whether this hook reads from, is owned by or is scoped in an entity
+    /// that doesn't exist anymore.
+    fn is_despawned(&self, world: &World) -> bool {
+        let entities = self.read.entity().into_iter().chain(self.owner);
+        let mut entities = entities.chain(self.scope);
+        entities.any(|entity| world.get_entity(entity).is_none())
+    }
 }
 
 /// The binding for all [`M: BevyModify`] in the ECS, and the hooks used by those.
@@ -182,10 +340,55 @@ impl<M: BevyModify> WorldBindings<M> {
     pub fn add_hooks(&mut self, iter: impl IntoIterator<Item = Hook<M>>) {
         self.hooks.extend(iter)
     }
+    /// Add a hook reading `read` into the `name` binding.
+    ///
+    /// Unlike hooks declared in format strings, this doesn't need the read
+    /// value to be registered in the type registry.
+    pub fn bind(&mut self, name: impl AsRef<str>, read: Read, write: Write<M>) {
+        let binding = self.bindings.get_or_add(name);
+        self.hooks.push(Hook::new(binding, read, write));
+    }
+    /// Like [`WorldBindings::bind`], but the hook is [owned by](Hook::owned_by) `owner`.
+    pub fn bind_owned(
+        &mut self,
+        owner: Entity,
+        name: impl AsRef<str>,
+        read: Read,
+        write: Write<M>,
+    ) {
+        let binding = self.bindings.get_or_add(name);
+        let hook = Hook::new(binding, read, write).owned_by(owner);
+        self.hooks.push(hook);
+    }
+    /// Like [`WorldBindings::bind`], but the hook writes into the [`ScopedBindings`]
+    /// of `scope`, see [`Hook::scoped_in`].
+    pub fn bind_scoped(
+        &mut self,
+        scope: Entity,
+        name: impl AsRef<str>,
+        read: Read,
+        write: Write<M>,
+    ) {
+        let binding = self.bindings.get_or_add(name);
+        let hook = Hook::new(binding, read, write).scoped_in(scope);
+        self.hooks.push(hook);
+    }
+    /// Remove all hooks [owned by](Hook::owned_by) `owner`.
+    pub fn remove_hooks_of(&mut self, owner: Entity) {
+        self.hooks.retain(|hook| hook.owner != Some(owner));
+    }
     pub fn parse_hook(&mut self, hook: ParsedHook, world: &mut World) {
+        self.parse_hook_of(None, hook, world);
+    }
+    /// Like [`WorldBindings::parse_hook`], but the hook is [owned by](Hook::owned_by)
+    /// `owner`.
+    pub fn parse_owned_hook(&mut self, owner: Entity, hook: ParsedHook, world: &mut World) {
+        self.parse_hook_of(Some(owner), hook, world);
+    }
+    fn parse_hook_of(&mut self, owner: Option<Entity>, hook: ParsedHook, world: &mut World) {
         let Self { bindings, hooks, fmts: formatters } = self;
         match Hook::from_parsed(hook, world, formatters, |n| bindings.get_or_add(n)) {
-            Ok(hook) => hooks.push(hook),
+            Ok(hook) => hooks.push(Hook { owner, ..hook }),
             Err(err) => error!("A tracker failed to be loaded: {err}"),
         }
     }
@@ -195,11 +398,15 @@ impl<M: BevyModify> WorldBindings<M> {
 /// a reflection-based dependency (`Res.foo.bar`, `One(MarkerComp).path`, etc)
 /// when that dependency is updated.
 ///
+/// Hooks reading the component of a despawned entity (see [`Read::component`])
+/// or owned by a despawned entity (see [`Hook::owned_by`]) are removed.
+///
 /// [`M::Items`]: fab::modify::Modify::Items
 /// [`LocalBindings`]: crate::LocalBindings
 pub fn update_hooked<M: BevyModify>(world: &mut World) {
     world.resource_scope(|world, mut bindings: Mut<WorldBindings<M>>| {
         let WorldBindings { bindings, hooks, .. } = &mut *bindings;
+        hooks.retain(|hook| !hook.is_despawned(world));
         for hook in hooks.iter_mut() {
             if let Err(err) = hook.read_into_binding(world, bindings) {
                 warn!("Error while running binding: {err}");
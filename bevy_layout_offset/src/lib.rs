@@ -3,10 +3,67 @@ use bevy::prelude::*;
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
 pub struct UiOffset(pub Transform);
 
-fn offset(mut query: Query<(&mut Transform, &UiOffset)>) {
-    query.for_each_mut(|(mut transform, offset)| {
-        *transform = transform.mul_transform(offset.0);
-    })
+/// The `Transform` of an entity without its [`UiOffset`], and the one
+/// `offset` set last.
+///
+/// `bevy_ui` only sets the translation of nodes when their layout changes,
+/// so the offset can't be applied to the current `Transform`, it would
+/// accumulate each frame.
+#[derive(Component, Clone, Copy)]
+struct Applied {
+    base: Transform,
+    written: Transform,
+}
+impl Applied {
+    /// Update `base` with the parts of `transform` set by something else
+    /// than `offset`.
+    fn update_base(&mut self, transform: &Transform) -> Transform {
+        let Applied { base, written } = self;
+        if transform.translation != written.translation {
+            base.translation = transform.translation;
+        }
+        if transform.rotation != written.rotation {
+            base.rotation = transform.rotation;
+        }
+        if transform.scale != written.scale {
+            base.scale = transform.scale;
+        }
+        *base
+    }
+}
+
+fn offset(
+    mut cmds: Commands,
+    mut query: Query<(Entity, &mut Transform, &UiOffset, Option<&mut Applied>)>,
+) {
+    for (entity, mut transform, offset, applied) in &mut query {
+        let written = match applied {
+            Some(mut applied) => {
+                applied.written = applied.update_base(&transform).mul_transform(offset.0);
+                applied.written
+            }
+            None => {
+                let (base, written) = (*transform, transform.mul_transform(offset.0));
+                cmds.entity(entity).insert(Applied { base, written });
+                written
+            }
+        };
+        if *transform != written {
+            *transform = written;
+        }
+    }
+}
+/// Restore the `Transform` of entities which [`UiOffset`] was removed.
+fn remove_offset(
+    mut cmds: Commands,
+    mut removed: RemovedComponents<UiOffset>,
+    mut query: Query<(&mut Transform, &mut Applied)>,
+) {
+    for entity in removed.iter() {
+        let Ok((mut transform, mut applied)) = query.get_mut(entity) else { continue; };
+        *transform = applied.update_base(&transform);
+        cmds.entity(entity).remove::<Applied>();
+    }
 }
 
 pub struct OffsetPlugin;
@@ -15,11 +72,43 @@ impl Plugin for OffsetPlugin {
         use bevy::transform::TransformSystem;
         use bevy::ui::UiSystem;
 
-        app.add_system(
-            offset
+        app.add_systems(
+            (remove_offset, offset)
+                .chain()
                 .after(UiSystem::Flex)
                 .before(TransformSystem::TransformPropagate)
                 .in_base_set(CoreSet::PostUpdate),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_doesnt_accumulate() {
+        let mut world = World::new();
+        let moved_up = Transform::from_xyz(0.0, 4.0, 0.0).with_scale(Vec3::splat(2.0));
+        let entity = world.spawn((Transform::default(), UiOffset(moved_up))).id();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((remove_offset, offset).chain());
+
+        for _ in 0..3 {
+            schedule.run(&mut world);
+        }
+        assert_eq!(world.get::<Transform>(entity), Some(&moved_up));
+
+        // `bevy_ui` moves the node, the offset applies to the new position.
+        world.get_mut::<Transform>(entity).unwrap().translation = Vec3::new(10.0, 0.0, 0.0);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        let moved = moved_up.with_translation(Vec3::new(10.0, 4.0, 0.0));
+        assert_eq!(world.get::<Transform>(entity), Some(&moved));
+
+        world.entity_mut(entity).remove::<UiOffset>();
+        schedule.run(&mut world);
+        let base = Transform::from_xyz(10.0, 0.0, 0.0);
+        assert_eq!(world.get::<Transform>(entity), Some(&base));
+    }
+}
@@ -0,0 +1,152 @@
+//! Measure the hot operations of the datazoo structures used in `cuicui_fab`
+//! resolvers.
+//!
+//! Run with `cargo bench -p cuicui_datazoo`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use cuicui_datazoo::{enum_multimap, jagged_bitset, Bitset, EnumMultimap, RawIndexMap};
+use enumset::{EnumSet, EnumSetType};
+
+const SIZES: [usize; 3] = [16, 256, 4096];
+
+/// A deterministic pseudo-random sequence, so that runs are comparable.
+fn pseudo_random(seed: u32, count: usize, max: u32) -> impl Iterator<Item = u32> {
+    let mut state = seed.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    (0..count).map(move |_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state % max
+    })
+}
+
+fn bitset(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Bitset");
+    for size in SIZES {
+        let bits: Vec<_> = pseudo_random(1, size / 2, size as u32).collect();
+        let set: Bitset<Box<[u32]>> = bits.iter().copied().collect();
+
+        group.bench_with_input(BenchmarkId::new("collect", size), &bits, |b, bits| {
+            b.iter(|| black_box(bits.iter().copied().collect::<Bitset<Box<[u32]>>>()));
+        });
+        group.bench_with_input(BenchmarkId::new("bit", size), &size, |b, &size| {
+            b.iter(|| (0..size).filter(|&i| set.bit(black_box(i))).count());
+        });
+        group.bench_with_input(BenchmarkId::new("ones_in_range", size), &size, |b, &size| {
+            b.iter(|| set.ones_in_range(black_box(size / 4..size * 3 / 4)).count());
+        });
+        group.bench_with_input(BenchmarkId::new("n_at", size), &size, |b, &size| {
+            b.iter(|| (0..size - 7).filter_map(|i| set.n_at(7, black_box(i))).sum::<u32>());
+        });
+        group.bench_with_input(BenchmarkId::new("enable_disable", size), &bits, |b, bits| {
+            let mut set = Bitset(vec![0; size / 32 + 1].into_boxed_slice());
+            b.iter(|| {
+                bits.iter().for_each(|&bit| {
+                    set.enable_bit(bit as usize);
+                });
+                set.disable_range(black_box(0..size));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn jagged_bitset(c: &mut Criterion) {
+    let mut group = c.benchmark_group("JaggedBitset");
+    for size in SIZES {
+        let widths: Vec<_> = pseudo_random(2, size, 64).collect();
+        let rows: Vec<Vec<_>> = widths
+            .iter()
+            .zip(1..)
+            .map(|(&width, seed)| pseudo_random(seed, width as usize / 2, width + 1).collect())
+            .collect();
+        let build = || {
+            let mut builder = jagged_bitset::Builder::with_capacity(size);
+            rows.iter().for_each(|row| builder.add_row(row.iter().copied()));
+            builder.build()
+        };
+        let jagged = build();
+
+        group.bench_function(BenchmarkId::new("build", size), |b| b.iter(build));
+        group.bench_with_input(BenchmarkId::new("row", size), &size, |b, &size| {
+            b.iter(|| (0..size).map(|i| jagged.row(black_box(i)).count()).sum::<usize>());
+        });
+        group.bench_with_input(BenchmarkId::new("bit", size), &widths, |b, widths| {
+            let bit = |(y, &width): (usize, &u32)| jagged.bit(width as usize / 2, black_box(y));
+            b.iter(|| widths.iter().enumerate().filter(|&yw| bit(yw)).count());
+        });
+    }
+    group.finish();
+}
+
+fn raw_index_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("RawIndexMap");
+    for size in SIZES {
+        let values: Vec<_> = pseudo_random(3, size, size as u32).collect();
+        let map: RawIndexMap<usize, u32> = values.iter().copied().enumerate().collect();
+
+        group.bench_with_input(BenchmarkId::new("collect", size), &values, |b, values| {
+            let collect = || values.iter().copied().enumerate().collect::<RawIndexMap<_, _>>();
+            b.iter(|| black_box(collect()));
+        });
+        group.bench_with_input(BenchmarkId::new("get", size), &size, |b, &size| {
+            b.iter(|| (0..size).filter_map(|i| map.get(&black_box(i))).sum::<u32>());
+        });
+        group.bench_with_input(BenchmarkId::new("set", size), &values, |b, values| {
+            let mut map = RawIndexMap::<usize, u32>::with_capacity(size, size as u32);
+            b.iter(|| {
+                values.iter().enumerate().for_each(|(k, v)| {
+                    map.set(&k, v);
+                });
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("iter", size), &map, |b, map| {
+            b.iter(|| map.iter().count());
+        });
+    }
+    group.finish();
+}
+
+#[derive(EnumSetType, Debug)]
+enum Field {
+    F0,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+}
+const FIELD_CLM: usize = 7;
+
+fn enum_multimap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("EnumMultimap");
+    for size in SIZES {
+        let rows: Vec<Vec<_>> = EnumSet::<Field>::all()
+            .iter()
+            .zip(4..)
+            .map(|(_, seed)| pseudo_random(seed, size / 8, size as u32).collect())
+            .collect();
+        let build = || {
+            let mut builder = enum_multimap::Builder::<Field, u32, FIELD_CLM>::new();
+            for (field, row) in EnumSet::all().iter().zip(&rows) {
+                builder.insert(field, row.iter().copied());
+            }
+            builder.build().unwrap()
+        };
+        let map: EnumMultimap<Field, u32, FIELD_CLM> = build();
+        let some_fields = Field::F1 | Field::F4 | Field::F5;
+
+        group.bench_function(BenchmarkId::new("build", size), |b| b.iter(build));
+        group.bench_with_input(BenchmarkId::new("row", size), &map, |b, map| {
+            b.iter(|| EnumSet::<Field>::all().iter().map(|f| map.row(f).len()).sum::<usize>());
+        });
+        group.bench_with_input(BenchmarkId::new("all_rows", size), &map, |b, map| {
+            b.iter(|| map.all_rows(black_box(some_fields)).count());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bitset, jagged_bitset, raw_index_map, enum_multimap);
+criterion_main!(benches);
@@ -71,6 +71,17 @@ impl<K: Ord, V, A: AsRef<[(K, V)]>> KeySorted<A, K, V> {
         let elem = self.0.as_ref().get(index)?;
         Some(&elem.1)
     }
+    /// Like [`KeySorted::get`], but also returns the key.
+    pub fn get_entry<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let slice = self.0.as_ref();
+        let index = slice.binary_search_by_key(&key, |e| e.0.borrow()).ok()?;
+        let elem = slice.get(index)?;
+        Some((&elem.0, &elem.1))
+    }
 }
 impl<K: Ord, V, A: AsRef<[(K, V)]> + AsMut<[(K, V)]>> KeySorted<A, K, V> {
     pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
@@ -92,6 +103,14 @@ impl<K: Ord, V> ByKeyVec<K, V> {
         let (Ok(index) | Err(index)) = self.0.binary_search_by_key(&&key, |e| &e.0);
         self.0.insert(index, (key, value));
     }
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.0.binary_search_by_key(&key, |e| e.0.borrow()).ok()?;
+        Some(self.0.remove(index))
+    }
 }
 impl<K: Ord, V> From<std::vec::Vec<(K, V)>> for ByKeyBox<K, V> {
     fn from(value: std::vec::Vec<(K, V)>) -> Self {
@@ -0,0 +1,194 @@
+//! Compare [`DepsResolver`] and [`MinResolver`] creation and update time.
+//!
+//! Run with `cargo bench -p cuicui_fab`.
+//!
+//! `DepsResolver` benchmarks are named `{section count}x{dependency depth}`.
+//! A dependency depth of `n` means that updating a binding triggers a chain of
+//! `n` modifiers, each depending on the output of the previous one.
+//!
+//! [`DepsResolver`]: cuicui_fab::resolve::DepsResolver
+use std::ops::Range;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use cuicui_fab::binding::{Id, World};
+use cuicui_fab::modify::{Changing, FieldsOf};
+use cuicui_fab::resolve::{MakeModify, MinResolver, ModifyKind, Resolver};
+use cuicui_fab::{impl_modify, Modify};
+
+const SECTION_COUNTS: [u32; 3] = [16, 256, 2048];
+const DEPTHS: [u32; 3] = [1, 4, 16];
+
+#[derive(Clone, Debug, Default)]
+pub struct Section {
+    size: f32,
+    color: u32,
+    content: String,
+}
+
+/// Operations on [`Section`], resolved with the default `DepsResolver`.
+#[impl_modify]
+impl Modify for Deps {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(write(.size))]
+    fn size(set_size: f32) -> f32 {
+        set_size
+    }
+    #[modify(read_write(.size))]
+    fn rel_size(relative: f32, size: &mut f32) {
+        *size *= relative;
+    }
+    #[modify(write(.color))]
+    fn color(set_color: u32) -> u32 {
+        set_color
+    }
+    #[modify(write_mut(.content))]
+    fn content(set_content: &String, content: &mut String) {
+        content.clone_from(set_content);
+    }
+}
+
+/// Operations on [`Section`], resolved with [`MinResolver`].
+#[impl_modify]
+impl Modify for Min {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+    type Resolver = MinResolver;
+
+    #[modify(write(.color))]
+    fn color(set_color: u32) -> u32 {
+        set_color
+    }
+    #[modify(write_mut(.content))]
+    fn content(set_content: &String, content: &mut String) {
+        content.clone_from(set_content);
+    }
+}
+
+fn new_resolver<M>(modifiers: Vec<MakeModify<M>>) -> (M::Resolver, Vec<Section>)
+where
+    M: for<'a> Modify<MakeItem = Section, Context<'a> = ()>,
+{
+    <M::Resolver as Resolver<M>>::new(modifiers, Section::default, &())
+}
+fn bound<M: Modify>(binding: Id, changes: FieldsOf<M>, range: Range<u32>) -> MakeModify<M> {
+    let kind = ModifyKind::Bound { binding, depends: FieldsOf::<M>::EMPTY, changes };
+    MakeModify { kind, range }
+}
+fn statik<M: Modify>(modify: M, range: Range<u32>) -> MakeModify<M> {
+    MakeModify { kind: ModifyKind::Modify(modify), range }
+}
+
+/// One static `color` and one `content` binding per section.
+fn flat<M: Modify>(
+    count: u32,
+    world: &mut World<M>,
+    color: impl Fn(u32) -> M,
+    content: FieldsOf<M>,
+) -> Vec<MakeModify<M>> {
+    let per_section = |i: u32| {
+        let binding = world.get_or_add(format!("content{i}"));
+        [statik(color(i), i..i + 1), bound(binding, content, i..i + 1)]
+    };
+    (0..count).flat_map(per_section).collect()
+}
+
+/// Sections grouped by `depth`, each group has a `size` binding and a chain
+/// of `depth - 1` nested `rel_size` modifiers depending on it.
+fn nested(count: u32, depth: u32, world: &mut World<Deps>) -> Vec<MakeModify<Deps>> {
+    let mut modifiers = Vec::new();
+    for start in (0..count).step_by(depth as usize) {
+        let end = (start + depth).min(count);
+        let binding = world.get_or_add(format!("size{start}"));
+        modifiers.push(bound(binding, Deps::size_changes(), start..end));
+        for level in start + 1..end {
+            modifiers.push(statik(Deps::rel_size(1.1), level..end));
+        }
+    }
+    modifiers
+}
+
+fn set_all<M: Modify>(world: &mut World<M>, prefix: &str, count: u32, step: u32, value: M) {
+    for i in (0..count).step_by(step as usize) {
+        let id = world.get_or_add(format!("{prefix}{i}"));
+        world.set_id(id, value.clone());
+    }
+}
+
+fn resolver_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolver_new");
+    for count in SECTION_COUNTS {
+        let make = || flat(count, &mut World::default(), Min::color, Min::content_changes());
+        group.bench_function(BenchmarkId::new("min", count), |b| {
+            b.iter_batched(make, new_resolver::<Min>, BatchSize::SmallInput);
+        });
+        let make = || flat(count, &mut World::default(), Deps::color, Deps::content_changes());
+        group.bench_function(BenchmarkId::new("deps_flat", count), |b| {
+            b.iter_batched(make, new_resolver::<Deps>, BatchSize::SmallInput);
+        });
+        for depth in DEPTHS {
+            let id = BenchmarkId::new("deps_nested", format!("{count}x{depth}"));
+            let make = || nested(count, depth, &mut World::default());
+            group.bench_function(id, |b| {
+                b.iter_batched(make, new_resolver::<Deps>, BatchSize::SmallInput);
+            });
+        }
+    }
+    group.finish();
+}
+
+fn resolver_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolver_update");
+    for count in SECTION_COUNTS {
+        let content = || "Hello world".to_owned();
+
+        let mut world = World::default();
+        let mods = flat(count, &mut world, Min::color, Min::content_changes());
+        let (resolver, mut items) = new_resolver(mods);
+        set_all(&mut world, "content", count, 1, Min::content(content()));
+        let root = Changing::new(Section::default());
+        group.bench_function(BenchmarkId::new("min", count), |b| {
+            b.iter(|| resolver.update(&mut items, &root, world.view(), &()));
+        });
+
+        let mut world = World::default();
+        let mods = flat(count, &mut world, Deps::color, Deps::content_changes());
+        let (resolver, mut items) = new_resolver(mods);
+        set_all(&mut world, "content", count, 1, Deps::content(content()));
+        let root = Changing::new(Section::default());
+        group.bench_function(BenchmarkId::new("deps_flat", count), |b| {
+            b.iter(|| resolver.update(&mut items, &root, world.view(), &()));
+        });
+
+        for depth in DEPTHS {
+            let mut world = World::default();
+            let (resolver, mut items) = new_resolver(nested(count, depth, &mut world));
+            set_all(&mut world, "size", count, depth, Deps::size(20.0));
+            let root = Changing::new(Section::default());
+            let id = BenchmarkId::new("deps_nested", format!("{count}x{depth}"));
+            group.bench_function(id, |b| {
+                b.iter(|| resolver.update(&mut items, &root, world.view(), &()));
+            });
+
+            // Changing the root `size` triggers all `rel_size` without bindings.
+            let world = World::default();
+            let mut root = Changing::new(Section::default());
+            for field in Deps::size_changes() {
+                root.update(field, |section| section.size = 12.0);
+            }
+            let id = BenchmarkId::new("deps_nested_root", format!("{count}x{depth}"));
+            group.bench_function(id, |b| {
+                b.iter(|| resolver.update(&mut items, &root, world.view(), &()));
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, resolver_new, resolver_update);
+criterion_main!(benches);
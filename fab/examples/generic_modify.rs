@@ -0,0 +1,64 @@
+use std::fmt;
+
+use cuicui_fab::{impl_modify, modify::Modify};
+use pretty_assertions::assert_eq;
+
+pub struct Palette {
+    highlight: [f32; 4],
+}
+
+/// Something with a color and an opacity, such as a text section or a sprite.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tinted<T> {
+    color: [f32; 4],
+    opacity: f32,
+    inner: T,
+}
+
+/// Modify a [`Tinted`], whatever `T` is.
+///
+/// Note that the generated `ModifyTintedField` is not generic.
+#[impl_modify]
+impl<T> Modify for ModifyTinted<T>
+where
+    T: Clone + fmt::Debug + Send + Sync + 'static,
+{
+    type Context<'a> = Palette;
+    type Item<'a> = &'a mut Tinted<T>;
+    type MakeItem = Tinted<T>;
+    type Items<'a, 'b, 'c> = Vec<Tinted<T>>;
+
+    /// Set the color to the context's highlight color.
+    #[modify(context(.highlight), write(.color))]
+    pub fn highlight(highlight: &[f32; 4]) -> [f32; 4] {
+        *highlight
+    }
+
+    #[modify(read_write(.opacity))]
+    pub fn fade(by: f32, opacity: &mut f32) {
+        *opacity *= by;
+    }
+
+    /// Replace the tinted value.
+    #[modify(write(.inner))]
+    pub fn inner(set_inner: &T) -> T {
+        set_inner.clone()
+    }
+}
+
+fn main() {
+    let palette = Palette { highlight: [1.0, 0.0, 0.0, 1.0] };
+    let mut sprite = Tinted { color: [1.0; 4], opacity: 1.0, inner: "sprite.png" };
+    let mut section = Tinted { color: [1.0; 4], opacity: 1.0, inner: String::new() };
+
+    ModifyTinted::highlight().apply(&palette, &mut sprite).unwrap();
+    ModifyTinted::fade(0.5).apply(&palette, &mut sprite).unwrap();
+    ModifyTinted::inner("Hello".to_owned()).apply(&palette, &mut section).unwrap();
+
+    let expected = Tinted { color: [1.0, 0.0, 0.0, 1.0], opacity: 0.5, inner: "sprite.png" };
+    assert_eq!(sprite, expected);
+    assert_eq!(section.inner, "Hello");
+
+    let inner_fields = ModifyTinted::<String>::inner_changes();
+    assert_eq!(inner_fields, ModifyTinted::<&str>::inner_changes());
+}
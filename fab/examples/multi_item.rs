@@ -0,0 +1,105 @@
+use cuicui_fab::modify::{Indexed, MakeItem as _, Modify};
+use cuicui_fab::{impl_modify, MakeItem};
+use pretty_assertions::assert_eq;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform {
+    translation: [f32; 2],
+    scale: f32,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    color: [f32; 4],
+    font_size: f32,
+}
+
+/// Owned `MakeItem`s, accessed as `Modify::Item`.
+pub struct Owned<T>(Vec<T>);
+impl Indexed<ModifySprite> for Owned<Sprite> {
+    fn get_mut(&mut self, index: usize) -> Option<SpriteItem<'_>> {
+        self.0.as_mut_slice().get_mut(index).map(Sprite::as_item)
+    }
+}
+type TupleItem = (Transform, (Style, String));
+impl Indexed<ModifyTuple> for Owned<TupleItem> {
+    fn get_mut(&mut self, index: usize) -> Option<<ModifyTuple as Modify>::Item<'_>> {
+        self.0.as_mut_slice().get_mut(index).map(TupleItem::as_item)
+    }
+}
+
+/// A sprite with a label, `SpriteItem` is declared by `MakeItem`.
+#[derive(Clone, Debug, Default, PartialEq, MakeItem)]
+#[make_item(item = SpriteItem)]
+pub struct Sprite {
+    transform: Transform,
+    style: Style,
+    label: String,
+}
+
+/// Modify several values at once in a [`Sprite`].
+#[impl_modify]
+impl Modify for ModifySprite {
+    type Context<'a> = ();
+    type Item<'a> = SpriteItem<'a>;
+    type MakeItem = Sprite;
+    type Items<'a, 'b, 'c> = Owned<Sprite>;
+
+    #[modify(read_write(.transform.scale))]
+    pub fn grow(by: f32, scale: &mut f32) {
+        *scale *= by;
+    }
+
+    /// Make the label's font size proportional to the sprite scale.
+    #[modify(read(.transform.scale), write(.style.font_size))]
+    pub fn scale_font(base_size: f32, scale: &f32) -> f32 {
+        base_size * scale
+    }
+
+    #[modify(write_mut(.label))]
+    pub fn label(set_label: &String, label: &mut String) {
+        label.clone_from(set_label);
+    }
+}
+
+/// Modify a tuple of (`Transform`, (`Style`, `String`)).
+#[impl_modify]
+impl Modify for ModifyTuple {
+    type Context<'a> = ();
+    type Item<'a> = (&'a mut Transform, &'a mut (Style, String));
+    type MakeItem = TupleItem;
+    type Items<'a, 'b, 'c> = Owned<TupleItem>;
+
+    #[modify(read(scale = .0.scale), write(font_size = .1.0.font_size))]
+    pub fn scale_font(base_size: f32, scale: &f32) -> f32 {
+        base_size * scale
+    }
+
+    #[modify(write_mut(label = .1.1))]
+    pub fn label(set_label: &String, label: &mut String) {
+        label.clone_from(set_label);
+    }
+}
+
+fn main() {
+    let mut sprite = Sprite::default();
+    sprite.transform.scale = 1.0;
+
+    ModifySprite::grow(2.0).apply(&(), sprite.as_item()).unwrap();
+    ModifySprite::scale_font(12.0).apply(&(), sprite.as_item()).unwrap();
+    ModifySprite::label("Hello".to_owned()).apply(&(), sprite.as_item()).unwrap();
+
+    assert_eq!(sprite.transform.scale, 2.0);
+    assert_eq!(sprite.style.font_size, 24.0);
+    assert_eq!(sprite.label, "Hello");
+
+    // `grow` changes what `scale_font` depends on, but not `label`.
+    assert_eq!(ModifySprite::grow_changes(), ModifySprite::scale_font_depends());
+    assert!(ModifySprite::label_changes().is_disjoint(ModifySprite::scale_font_depends()));
+
+    let mut tuple = (Transform { scale: 3.0, ..Default::default() }, Default::default());
+    ModifyTuple::scale_font(10.0).apply(&(), tuple.as_item()).unwrap();
+    ModifyTuple::label("World".to_owned()).apply(&(), tuple.as_item()).unwrap();
+
+    assert_eq!(tuple.1.0.font_size, 30.0);
+    assert_eq!(tuple.1.1, "World");
+}
@@ -1,18 +1,25 @@
 //! Stores [`Modify`].
 
 mod entry;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(test)]
+mod tests;
 
-use std::{fmt, mem, num::NonZeroU32};
+use std::{fmt, iter, marker::PhantomData, mem, num::NonZeroU32};
 
 use anyhow::anyhow;
-use datazoo::{sorted, Index, SortedPairIterator};
+use datazoo::{sorted, Index};
 use smallvec::SmallVec;
 use string_interner::{backend::StringBackend, StringInterner, Symbol};
+use thiserror::Error;
 
 #[cfg(doc)]
 use crate::Modify;
 
 pub use entry::Entry;
+#[cfg(feature = "serde")]
+pub use serialize::NamedLocal;
 
 /// A binding id used in [`World`] and [`Local`] to associate a name to a
 /// [`Modify`].
@@ -57,12 +64,70 @@ impl fmt::Debug for Id {
     }
 }
 
+/// A typed handle to a binding of `M`.
+///
+/// Unlike binding names, using a `BindingKey` to access a binding doesn't
+/// require looking up the name in the string interner. Get one with
+/// [`World::key`], [`World::get_key`] or [`Local::get_key`] and keep it around.
+pub struct BindingKey<M> {
+    id: Id,
+    _modify: PhantomData<fn(M)>,
+}
+impl<M> BindingKey<M> {
+    fn new(id: Id) -> Self {
+        BindingKey { id, _modify: PhantomData }
+    }
+    /// The untyped [`Id`] of this binding.
+    #[must_use]
+    pub const fn id(self) -> Id {
+        self.id
+    }
+}
+impl<M> Clone for BindingKey<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<M> Copy for BindingKey<M> {}
+impl<M> PartialEq for BindingKey<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl<M> Eq for BindingKey<M> {}
+impl<M> fmt::Debug for BindingKey<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.id, f)
+    }
+}
+
+/// A binding name is not known to the [`World`] bindings.
+///
+/// Binding names are added when parsing format strings, or with [`World::key`].
+#[derive(Debug, Error)]
+#[error("There is no binding named '{0}'")]
+pub struct UnknownBinding(pub Box<str>);
+
 #[derive(Debug)]
 pub struct Local<M> {
     bindings: sorted::ByKeyVec<Id, (bool, M)>,
     buffered: Vec<(Box<str>, M)>,
     resolved: SmallVec<[(Box<str>, Id); 2]>,
 }
+/// Bindings shared by several [`Local`]s, but not all of them.
+///
+/// Scopes are layered between the [`World`] and [`Local`] bindings in a [`View`],
+/// a binding in a scope shadows the binding of the same name in the [`World`]
+/// and in scopes further away from the [`Local`].
+///
+/// Use [`World::key`] to get the [`BindingKey`] of a binding name.
+#[derive(Debug)]
+pub struct Scope<M> {
+    bindings: sorted::ByKeyVec<Id, (bool, M)>,
+    /// Bindings removed since the last `reset_changes`, the bindings they
+    /// were shadowing need to be applied again.
+    removed: Vec<Id>,
+}
 #[derive(Debug)]
 pub struct World<M> {
     bindings: sorted::ByKeyVec<Id, (bool, M)>,
@@ -77,6 +142,11 @@ impl<M> Default for Local<M> {
         }
     }
 }
+impl<M> Default for Scope<M> {
+    fn default() -> Self {
+        Scope { bindings: Default::default(), removed: Vec::new() }
+    }
+}
 impl<M> Default for World<M> {
     fn default() -> Self {
         World {
@@ -87,11 +157,13 @@ impl<M> Default for World<M> {
 }
 pub struct View<'a, M> {
     root: &'a sorted::ByKeyVec<Id, (bool, M)>,
+    /// From closest to the `overlay` to closest to the `root`.
+    scopes: &'a [&'a Scope<M>],
     overlay: Option<&'a sorted::ByKeyVec<Id, (bool, M)>>,
 }
 impl<'a, M> Clone for View<'a, M> {
     fn clone(&self) -> Self {
-        View { root: self.root, overlay: self.overlay }
+        *self
     }
 }
 impl<'a, M> Copy for View<'a, M> {}
@@ -103,6 +175,29 @@ impl<M> Local<M> {
     pub fn set_by_id(&mut self, id: Id, value: M) {
         self.bindings.insert(id, (true, value));
     }
+    /// Set the binding of `key`. This will mark it as changed.
+    pub fn set_key(&mut self, key: BindingKey<M>, value: M) {
+        self.set_by_id(key.id, value);
+    }
+    /// Access mutably the binding of `key`, if it was already set.
+    /// This sets the `change` bit unconditionally.
+    pub fn get_key_mut(&mut self, key: BindingKey<M>) -> Option<&mut M> {
+        let (changed, modify) = self.bindings.get_mut(&key.id)?;
+        *changed = true;
+        Some(modify)
+    }
+    /// The [`BindingKey`] of `name`.
+    ///
+    /// Names set with [`Local::set`] are only known after they are synced with
+    /// the [`World`] bindings, before that, this returns an error.
+    pub fn get_key(&self, name: impl AsRef<str>) -> Result<BindingKey<M>, UnknownBinding> {
+        let name = name.as_ref();
+        let is_name = |(known, _): &(Box<str>, _)| known.as_ref().cmp(name);
+        match self.resolved.binary_search_by(is_name) {
+            Ok(resolved) => Ok(BindingKey::new(self.resolved[resolved].1)),
+            Err(_) => Err(UnknownBinding(name.into())),
+        }
+    }
     pub fn get_mut(&mut self, binding_name: impl Into<String>) -> Option<&mut M> {
         let name = binding_name.into();
         let is_name = |(known, _): &(Box<str>, _)| known.as_ref().cmp(&name);
@@ -135,8 +230,62 @@ impl<M> Local<M> {
         Ok(())
     }
 
+    /// Bindings changed since the last `reset_changes`, with their value.
+    ///
+    /// Unlike [`Local::sync`], this doesn't require a mutable reference.
+    /// Bindings set by name since the last `sync` are included if `global`
+    /// knows their name.
+    pub fn changed<'a>(
+        &'a self,
+        global: &'a World<M>,
+    ) -> impl Iterator<Item = (BindingKey<M>, &'a M)> + 'a {
+        let buffered = self.buffered.iter().filter_map(|(name, modify)| {
+            let id = global.interner.get(&**name)?;
+            Some((BindingKey::new(id), modify))
+        });
+        changed(&self.bindings).chain(buffered)
+    }
+    pub fn reset_changes(&mut self) {
+        self.bindings.values_mut().for_each(|v| v.0 = false);
+    }
+    /// Mark all bindings as changed, so that they are applied again.
+    pub fn mark_changed(&mut self) {
+        self.bindings.values_mut().for_each(|v| v.0 = true);
+    }
+}
+impl<M> Scope<M> {
+    pub fn entry(&mut self, id: Id) -> Entry<'_, M> {
+        Entry::new(&mut self.bindings, id)
+    }
+    /// Set the binding of `key` in this scope. This will mark it as changed.
+    pub fn set_key(&mut self, key: BindingKey<M>, value: M) {
+        self.bindings.insert(key.id, (true, value));
+    }
+    /// Access mutably the binding of `key` in this scope, if it was already set.
+    /// This sets the `change` bit unconditionally.
+    pub fn get_key_mut(&mut self, key: BindingKey<M>) -> Option<&mut M> {
+        let (changed, modify) = self.bindings.get_mut(&key.id)?;
+        *changed = true;
+        Some(modify)
+    }
+    /// Remove the binding of `key` from this scope, so that it stops shadowing
+    /// the binding of the same name in the scopes further away and the [`World`].
+    pub fn remove(&mut self, key: BindingKey<M>) -> Option<M> {
+        let (_, (_, modify)) = self.bindings.remove(&key.id)?;
+        self.removed.push(key.id);
+        Some(modify)
+    }
+    /// Bindings changed since the last `reset_changes`, with their value.
+    pub fn changed(&self) -> impl Iterator<Item = (BindingKey<M>, &M)> + '_ {
+        changed(&self.bindings)
+    }
+    /// Bindings [removed](Scope::remove) since the last `reset_changes`.
+    pub fn removed(&self) -> impl Iterator<Item = BindingKey<M>> + '_ {
+        self.removed.iter().map(|&id| BindingKey::new(id))
+    }
     pub fn reset_changes(&mut self) {
         self.bindings.values_mut().for_each(|v| v.0 = false);
+        self.removed.clear();
     }
 }
 impl<M> World<M> {
@@ -144,24 +293,57 @@ impl<M> World<M> {
         Entry::new(&mut self.bindings, id)
     }
 
-    // TODO(err): Should return Result
-    pub fn set(&mut self, key: &str, value: M) -> Option<()> {
-        let id = self.interner.get(key)?;
-        self.set_id(id, value);
-        Some(())
+    /// The [`BindingKey`] of `name`, adding it to the known bindings if it
+    /// doesn't exist yet.
+    pub fn key(&mut self, name: impl AsRef<str>) -> BindingKey<M> {
+        BindingKey::new(self.get_or_add(name))
+    }
+    /// The [`BindingKey`] of `name`, if it exists.
+    pub fn get_key(&self, name: impl AsRef<str>) -> Result<BindingKey<M>, UnknownBinding> {
+        let name = name.as_ref();
+        let unknown = || UnknownBinding(name.into());
+        Ok(BindingKey::new(self.get_id(name).ok_or_else(unknown)?))
+    }
+    /// Set the binding of `key`. This will mark it as changed.
+    pub fn set_key(&mut self, key: BindingKey<M>, value: M) {
+        self.set_id(key.id, value);
+    }
+    /// Like `set_key` but do not mark as modified if `value` is same as previous
+    /// value for `key`.
+    pub fn set_key_neq(&mut self, key: BindingKey<M>, value: M)
+    where
+        M: PartialEq,
+    {
+        self.set_id_neq(key.id, value);
+    }
+    /// Access mutably the binding of `key`, if it was already set.
+    /// This sets the `change` bit unconditionally.
+    pub fn get_key_mut(&mut self, key: BindingKey<M>) -> Option<&mut M> {
+        let (changed, modify) = self.bindings.get_mut(&key.id)?;
+        *changed = true;
+        Some(modify)
+    }
+
+    /// Set the binding named `key`. This will mark it as changed.
+    ///
+    /// Prefer [`World::set_key`] when setting the same binding repeatedly.
+    pub fn set(&mut self, key: &str, value: M) -> Result<(), UnknownBinding> {
+        let key = self.get_key(key)?;
+        self.set_key(key, value);
+        Ok(())
     }
     pub fn set_id(&mut self, id: Id, value: M) {
         self.bindings.insert(id, (true, value));
     }
     /// Like `set` but do not mark as modified if `value` is same as previous
     /// value for `key`.
-    pub fn set_neq(&mut self, key: &str, value: M) -> Option<()>
+    pub fn set_neq(&mut self, key: &str, value: M) -> Result<(), UnknownBinding>
     where
         M: PartialEq,
     {
-        let id = self.interner.get(key)?;
-        self.set_id_neq(id, value);
-        Some(())
+        let key = self.get_key(key)?;
+        self.set_key_neq(key, value);
+        Ok(())
     }
     /// Like `set_id` but do not mark as modified if `value` is same as previous
     /// value for `id`.
@@ -181,11 +363,11 @@ impl<M> World<M> {
         }
     }
     /// Access mutably an existing binding. This sets the `change` bit unconditionally.
-    pub fn get_mut(&mut self, key: &str) -> Option<&mut M> {
-        let id = self.interner.get(key)?;
-        let (changed, modify) = self.bindings.get_mut(&id)?;
-        *changed = true;
-        Some(modify)
+    ///
+    /// Returns `Ok(None)` if the binding exists but was never set.
+    pub fn get_mut(&mut self, key: &str) -> Result<Option<&mut M>, UnknownBinding> {
+        let key = self.get_key(key)?;
+        Ok(self.get_key_mut(key))
     }
     pub fn get_or_add(&mut self, name: impl AsRef<str>) -> Id {
         self.interner.get_or_intern(name)
@@ -194,34 +376,84 @@ impl<M> World<M> {
         self.interner.get(name)
     }
     pub fn view(&self) -> View<M> {
-        View { root: &self.bindings, overlay: None }
+        View { root: &self.bindings, scopes: &[], overlay: None }
     }
     pub fn view_with_local<'a>(&'a self, local: &'a mut Local<M>) -> anyhow::Result<View<'a, M>> {
+        self.view_with_scopes(local, &[])
+    }
+    /// Like [`World::view_with_local`], but `scopes` bindings shadow the
+    /// `self` bindings. `scopes` are ordered from closest to `local` to furthest.
+    pub fn view_with_scopes<'a>(
+        &'a self,
+        local: &'a mut Local<M>,
+        scopes: &'a [&'a Scope<M>],
+    ) -> anyhow::Result<View<'a, M>> {
         local.sync(self)?;
         Ok(View {
             overlay: Some(&local.bindings),
+            scopes,
             root: &self.bindings,
         })
     }
 
+    /// Bindings changed since the last `reset_changes`, with their value.
+    pub fn changed(&self) -> impl Iterator<Item = (BindingKey<M>, &M)> + '_ {
+        changed(&self.bindings)
+    }
     pub fn reset_changes(&mut self) {
         self.bindings.values_mut().for_each(|v| v.0 = false);
     }
 }
+fn changed<M>(
+    bindings: &sorted::ByKeyVec<Id, (bool, M)>,
+) -> impl Iterator<Item = (BindingKey<M>, &M)> + '_ {
+    let changed = bindings.iter().filter(|(_, (changed, _))| *changed);
+    changed.map(|(id, (_, modify))| (BindingKey::new(*id), modify))
+}
 impl<'a, M> View<'a, M> {
-    pub(crate) fn changed(self) -> impl SortedPairIterator<&'a Id, &'a M, Item = (&'a Id, &'a M)> {
-        // Due to Rust's poor type inference on closures, I must write this inline:
-        // let changed = |(changed, modify): &(bool, _)| changed.then_some(modify);
-        let overlay = self.overlay.into_iter().flatten();
-        let overlay = overlay.filter_map_values(|(c, m)| c.then(|| m));
-        let root = self.root.iter().filter_map_values(|(c, m)| c.then(|| m));
-
-        overlay.outer_join(root).filter_map_values(|(l, r)| l.or(r))
+    /// All binding layers, from the one with the highest priority to the lowest.
+    fn layers(self) -> impl Iterator<Item = &'a sorted::ByKeyVec<Id, (bool, M)>> {
+        let scopes = self.scopes.iter().map(|scope| &scope.bindings);
+        self.overlay
+            .into_iter()
+            .chain(scopes)
+            .chain(iter::once(self.root))
+    }
+    /// Whether `id` is in any of the `depth` layers of highest priority.
+    fn shadowed(self, depth: usize, id: &Id) -> bool {
+        self.layers().take(depth).any(|layer| layer.contains_key(id))
+    }
+    /// Changed bindings, ignoring those shadowed by a layer of higher priority.
+    ///
+    /// Bindings removed from a [`Scope`] are also returned, with the value
+    /// of the layer they were shadowing, unless it is already changed.
+    pub(crate) fn changed(self) -> impl Iterator<Item = (&'a Id, &'a M)> {
+        let changed = self.layers().enumerate().flat_map(move |(depth, layer)| {
+            layer
+                .iter()
+                .filter(|(_, (changed, _))| *changed)
+                .filter(move |(id, _)| !self.shadowed(depth, id))
+                .map(|(id, (_, modify))| (id, modify))
+        });
+        // Scopes are layers `1..` when there is an overlay, `0..` otherwise.
+        let first_scope = usize::from(self.overlay.is_some());
+        let scopes = self.scopes.iter().enumerate();
+        let unshadowed = scopes.flat_map(move |(i, scope)| {
+            let depth = first_scope + i;
+            scope.removed.iter().filter_map(move |id| {
+                if self.shadowed(depth + 1, id) {
+                    return None;
+                }
+                let mut below = self.layers().skip(depth + 1);
+                let (id, (changed, modify)) = below.find_map(|layer| layer.get_entry(id))?;
+                (!changed).then_some((id, modify))
+            })
+        });
+        changed.chain(unshadowed)
     }
     pub fn get(&self, id: Id) -> Option<&'a M> {
-        self.overlay
-            .and_then(|btm| btm.get(&id))
-            .or_else(|| self.root.get(&id))
+        self.layers()
+            .find_map(|layer| layer.get(&id))
             .map(|(_c, modify)| modify)
     }
 }
@@ -0,0 +1,144 @@
+//! Serialize and deserialize [`World`] and [`Local`] bindings.
+//!
+//! Bindings are keyed by their name rather than their [`Id`], since [`Id`]s
+//! depend on the order in which names were first encountered, and are not
+//! stable between two runs of the same app.
+
+use std::collections::HashMap;
+
+use datazoo::sorted;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use string_interner::{backend::StringBackend, StringInterner};
+
+use super::{Id, Local, World};
+
+/// The bindings in `bindings` with their name, skipping the ones not in `interner`.
+fn named<'a, M>(
+    bindings: &'a sorted::ByKeyVec<Id, (bool, M)>,
+    interner: &'a StringInterner<StringBackend<Id>>,
+) -> impl Iterator<Item = (&'a str, &'a M)> {
+    let name = |(id, (_, modify)): (&Id, &'a (bool, M))| Some((interner.resolve(*id)?, modify));
+    bindings.iter().filter_map(name)
+}
+
+impl<M: Serialize> Serialize for World<M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(named(&self.bindings, &self.interner))
+    }
+}
+impl<'de, M: Deserialize<'de>> Deserialize<'de> for World<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut world = World::default();
+        world.load(deserializer)?;
+        Ok(world)
+    }
+}
+impl<M> World<M> {
+    /// Set the bindings serialized in `deserializer`, adding binding names
+    /// that are not known yet.
+    ///
+    /// Unlike deserializing a new `World`, this keeps the existing bindings
+    /// and [`Id`]s, so it can be used on bindings already in use.
+    ///
+    /// Loaded bindings are marked as changed.
+    pub fn load<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        M: Deserialize<'de>,
+    {
+        for (name, modify) in HashMap::<String, M>::deserialize(deserializer)? {
+            let id = self.get_or_add(name);
+            self.set_id(id, modify);
+        }
+        Ok(())
+    }
+}
+
+/// A [`Local`] with the [`World`] used to get the name of its bindings,
+/// so that it can be serialized.
+///
+/// Create one with [`Local::named`].
+pub struct NamedLocal<'a, M> {
+    local: &'a Local<M>,
+    world: &'a World<M>,
+}
+impl<M: Serialize> Serialize for NamedLocal<'_, M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Local { bindings, buffered, .. } = self.local;
+        let bindings = named(bindings, &self.world.interner);
+        let buffered = buffered.iter().map(|(name, m)| (name.as_ref(), m));
+        serializer.collect_map(bindings.chain(buffered))
+    }
+}
+impl<'de, M: Deserialize<'de>> Deserialize<'de> for Local<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut local = Local::default();
+        local.load(deserializer)?;
+        Ok(local)
+    }
+}
+impl<M> Local<M> {
+    /// Serialize this `Local`, using `world` to get the binding names.
+    ///
+    /// `world` must be the [`World`] this `Local` was [synced](Local::sync) with.
+    pub fn named<'a>(&'a self, world: &'a World<M>) -> NamedLocal<'a, M> {
+        NamedLocal { local: self, world }
+    }
+    /// Set the bindings serialized in `deserializer`, like [`Local::set`].
+    ///
+    /// Similarly to [`Local::set`], you need to [`Local::sync`] this `Local`
+    /// for the bindings to be resolved.
+    pub fn load<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        M: Deserialize<'de>,
+    {
+        for (name, modify) in HashMap::<String, M>::deserialize(deserializer)? {
+            self.set(name, modify);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn world_round_trip() {
+        let mut world = World::<u32>::default();
+        let (title, health) = (world.key("title"), world.key("health"));
+        world.set_key(title, 1);
+        world.set_key(health, 2);
+        let saved = ron::to_string(&world).unwrap();
+
+        // `Id`s depend on interning order, loading must go through names.
+        let mut loaded = World::<u32>::default();
+        let health = loaded.key("health");
+        loaded.load(&mut ron::Deserializer::from_str(&saved).unwrap()).unwrap();
+        let title = loaded.get_key("title").unwrap();
+        assert_eq!(loaded.view().get(health.id()), Some(&2));
+        assert_eq!(loaded.view().get(title.id()), Some(&1));
+
+        let deserialized: World<u32> = ron::from_str(&saved).unwrap();
+        let health = deserialized.get_key("health").unwrap();
+        assert_eq!(deserialized.view().get(health.id()), Some(&2));
+    }
+    #[test]
+    fn local_round_trip() {
+        let mut world = World::<u32>::default();
+        let title = world.key("title");
+        let mut local = Local::default();
+        local.set_key(title, 1);
+        // Not synced yet, saved from the buffered bindings.
+        local.set("health", 2);
+        let saved = ron::to_string(&local.named(&world)).unwrap();
+
+        let mut loaded: Local<u32> = ron::from_str(&saved).unwrap();
+        let mut world = World::default();
+        let (health, title) = (world.key("health"), world.key("title"));
+        let view = world.view_with_local(&mut loaded).unwrap();
+        assert_eq!(view.get(health.id()), Some(&2));
+        assert_eq!(view.get(title.id()), Some(&1));
+    }
+}
@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+
+use super::{Local, Scope, View, World};
+
+fn changed(view: View<u32>) -> Vec<u32> {
+    let mut changed: Vec<_> = view.changed().map(|(_, value)| *value).collect();
+    changed.sort_unstable();
+    changed
+}
+
+#[test]
+fn child_overrides_parent() {
+    let mut world = World::default();
+    let title = world.key("title");
+    let (mut parent, mut child) = (Scope::default(), Scope::default());
+    world.set_key(title, 1);
+    parent.set_key(title, 2);
+    child.set_key(title, 3);
+
+    let mut local = Local::default();
+    let scopes = [&child, &parent];
+    let view = world.view_with_scopes(&mut local, &scopes).unwrap();
+    assert_eq!(view.get(title.id()), Some(&3));
+    assert_eq!(changed(view), vec![3]);
+
+    let scopes = [&parent];
+    let view = world.view_with_scopes(&mut local, &scopes).unwrap();
+    assert_eq!(view.get(title.id()), Some(&2));
+    assert_eq!(changed(view), vec![2]);
+}
+#[test]
+fn parent_change_hidden_by_child() {
+    let mut world = World::default();
+    let title = world.key("title");
+    let (mut parent, mut child) = (Scope::default(), Scope::default());
+    world.set_key(title, 1);
+    parent.set_key(title, 2);
+    child.set_key(title, 3);
+    world.reset_changes();
+    parent.reset_changes();
+    child.reset_changes();
+
+    *parent.get_key_mut(title).unwrap() = 4;
+    *world.get_key_mut(title).unwrap() = 5;
+
+    let mut local = Local::default();
+    let scopes = [&child, &parent];
+    let view = world.view_with_scopes(&mut local, &scopes).unwrap();
+    assert_eq!(view.get(title.id()), Some(&3));
+    assert_eq!(changed(view), Vec::<u32>::new());
+}
+#[test]
+fn key_removed_from_child() {
+    let mut world = World::default();
+    let (title, footer) = (world.key("title"), world.key("footer"));
+    let (mut parent, mut child) = (Scope::default(), Scope::default());
+    world.set_key(footer, 10);
+    parent.set_key(title, 2);
+    child.set_key(title, 3);
+    child.set_key(footer, 30);
+    world.reset_changes();
+    parent.reset_changes();
+    child.reset_changes();
+
+    // The parent's `title` and world's `footer` are not changed, but they are
+    // not shadowed anymore, so they must be applied again.
+    assert_eq!(child.remove(title), Some(3));
+    assert_eq!(child.remove(footer), Some(30));
+
+    let mut local = Local::default();
+    let scopes = [&child, &parent];
+    let view = world.view_with_scopes(&mut local, &scopes).unwrap();
+    assert_eq!(view.get(title.id()), Some(&2));
+    assert_eq!(view.get(footer.id()), Some(&10));
+    assert_eq!(changed(view), vec![2, 10]);
+
+    child.reset_changes();
+    let scopes = [&child, &parent];
+    let view = world.view_with_scopes(&mut local, &scopes).unwrap();
+    assert_eq!(changed(view), Vec::<u32>::new());
+}
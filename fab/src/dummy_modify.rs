@@ -4,7 +4,7 @@ use enumset::{EnumSet, EnumSetType};
 
 use crate::{
     binding::View,
-    modify::{Changing, Indexed, Modify},
+    modify::{Changed, Changing, Indexed, Modify},
     resolve::{MakeModify, Resolver},
 };
 
@@ -36,7 +36,8 @@ impl Resolver<DummyModify> for () {
         _: &'a Changing<NoFields, ()>,
         _: View<'a, DummyModify>,
         _: &(),
-    ) {
+    ) -> Changed {
+        Changed::No
     }
 }
 impl Modify for DummyModify {
@@ -47,8 +48,8 @@ impl Modify for DummyModify {
     type Context<'a> = ();
     type Resolver = ();
 
-    fn apply(&self, (): &(), (): &mut ()) -> anyhow::Result<()> {
-        Ok(())
+    fn apply(&self, (): &(), (): &mut ()) -> anyhow::Result<Changed> {
+        Ok(Changed::No)
     }
     fn depends(&self) -> EnumSet<NoFields> {
         EnumSet::EMPTY
@@ -3,7 +3,7 @@ mod dummy_modify;
 pub mod modify;
 pub mod resolve;
 
-pub use fab_derive::impl_modify;
+pub use fab_derive::{impl_modify, MakeItem};
 pub use modify::Modify;
 
 #[doc(hidden)]
@@ -1,4 +1,6 @@
-use std::fmt;
+pub mod check;
+
+use std::{fmt, ops};
 
 #[cfg(doc)]
 use crate::resolve::DepsResolver;
@@ -7,6 +9,11 @@ use crate::resolve::Resolver;
 use enumset::{EnumSet, EnumSetType};
 
 /// An owned view of `I` that can be read as or written to a mutably borrowed `I`.
+///
+/// This is implemented for any [`Clone`] type `T` with `I = &mut T`, and for
+/// tuples of up to 12 `Clone` types with `I` a tuple of `&mut`.
+///
+/// For structs of `&mut`, use the [`MakeItem`](derive@crate::MakeItem) derive macro.
 pub trait MakeItem<'a, I: 'a> {
     /// Write `Self` into a pre-existing `I`.
     ///
@@ -34,22 +41,49 @@ impl<'a, T: Clone> MakeItem<'a, &'a mut T> for T {
         self
     }
 }
-impl<'a, T1: Clone, T2: Clone> MakeItem<'a, (&'a mut T1, &'a mut T2)> for (T1, T2) {
-    fn make_item<'b, 'c>(&self, item: &'c mut (&'a mut T1, &'a mut T2))
-    where
-        'a: 'c,
-    {
-        *item.0 = self.0.clone();
-        *item.1 = self.1.clone();
-    }
-    fn as_item(&mut self) -> (&mut T1, &mut T2) {
-        let (t1, t2) = self;
-        (t1, t2)
-    }
+/// Implement [`MakeItem`] for tuples of [`Clone`] items, `(T0, T1, …)` can be
+/// written into a `(&mut T0, &mut T1, …)`.
+macro_rules! impl_make_item_tuple {
+    ($( $ty:ident $index:tt ),*) => {
+        impl<'a, $( $ty: Clone ),*> MakeItem<'a, ( $( &'a mut $ty, )* )> for ( $( $ty, )* ) {
+            fn make_item<'b, 'c>(&'b self, item: &'c mut ( $( &'a mut $ty, )* ))
+            where
+                'a: 'c,
+            {
+                $( *item.$index = self.$index.clone(); )*
+            }
+            fn as_item(&'a mut self) -> ( $( &'a mut $ty, )* ) {
+                ( $( &mut self.$index, )* )
+            }
+        }
+    };
 }
+impl_make_item_tuple!(T0 0);
+impl_make_item_tuple!(T0 0, T1 1);
+impl_make_item_tuple!(T0 0, T1 1, T2 2);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_make_item_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
 
 pub trait Indexed<M: Modify + ?Sized> {
     fn get_mut(&mut self, index: usize) -> Option<M::Item<'_>>;
+    /// Called after `fields` of the item at `index` were changed.
+    ///
+    /// [`Resolver`]s call this after a modifier returned [`Changed::Yes`],
+    /// with the fields it [changes], so that items can track their changes
+    /// at a finer grain than [`Resolver::update`]'s return value. Does nothing
+    /// by default.
+    ///
+    /// [`Resolver`]: crate::resolve::Resolver
+    /// [`Resolver::update`]: crate::resolve::Resolver::update
+    /// [changes]: Modify::changes
+    fn set_changed(&mut self, _index: usize, _fields: FieldsOf<M>) {}
 }
 impl<T, M: for<'a> Modify<Item<'a> = &'a mut T>> Indexed<M> for [T] {
     fn get_mut(&mut self, index: usize) -> Option<M::Item<'_>> {
@@ -65,6 +99,37 @@ impl<T, M: for<'a> Modify<Item<'a> = &'a mut T>> Indexed<M> for Vec<T> {
 /// Several [`Modify::Field`]s.
 pub type FieldsOf<M> = EnumSet<<M as Modify>::Field>;
 
+/// Whether [`Modify::apply`] changed the item it was applied to.
+///
+/// [`Resolver::update`] returns `Changed::No` when none of the modifiers it
+/// applied changed an item, so that the items can be left untouched, for
+/// example to not trigger bevy's change detection.
+///
+/// With [`impl_modify!`], functions declared with `write_neq` return
+/// `Changed::No` when the value to write is equal to the current one.
+///
+/// [`impl_modify!`]: crate::impl_modify
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Changed {
+    Yes,
+    No,
+}
+impl ops::BitOr for Changed {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        if self == Changed::Yes {
+            self
+        } else {
+            rhs
+        }
+    }
+}
+impl ops::BitOrAssign for Changed {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
 /// A set of operations on `Item`.
 ///
 /// A `Modify` value declares which fields of `Modify::Item` it will [read] and [update].
@@ -108,13 +173,45 @@ pub trait Modify: Clone + fmt::Debug {
     /// - only updates [`Self::Field`]s returned by [`Self::changes`].
     ///
     /// Otherwise, [`Resolver`] will fail to work properly.
-    fn apply(&self, ctx: &Self::Context<'_>, item: Self::Item<'_>) -> anyhow::Result<()>;
+    ///
+    /// Enable the `check_modify` feature to detect when `apply` changes fields
+    /// not declared in [`Self::changes`], see the [`check`] module.
+    ///
+    /// Returns [`Changed::No`] if `item` wasn't changed, this is not required
+    /// and it is always correct to return [`Changed::Yes`].
+    fn apply(&self, ctx: &Self::Context<'_>, item: Self::Item<'_>) -> anyhow::Result<Changed>;
 
     /// On what data in [`Self::Item`] does this modifier depends?
     fn depends(&self) -> EnumSet<Self::Field>;
 
     /// What data in [`Self::Item`] does this `Modify` changes?
     fn changes(&self) -> EnumSet<Self::Field>;
+
+    /// The [`Self::Field`]s that are part of [`Self::Context`] rather than
+    /// [`Self::Item`].
+    ///
+    /// When a context field is [marked as updated], [`DepsResolver`] applies
+    /// again the modifiers depending on it, without resetting their sections
+    /// to the root item. By default, there is no context field.
+    ///
+    /// [marked as updated]: Changing::mark_updated
+    fn context_fields() -> EnumSet<Self::Field> {
+        EnumSet::EMPTY
+    }
+
+    /// The current value of all [`Self::Field`]s in `item`.
+    ///
+    /// This is only used with the `check_modify` feature, to verify that
+    /// [`Self::apply`] doesn't change undeclared fields.
+    ///
+    /// By default, this doesn't track any field, so **hand-written `Modify`
+    /// impls are not checked** unless they implement `snapshot`.
+    /// [`impl_modify!`] implements it for you.
+    ///
+    /// [`impl_modify!`]: crate::impl_modify
+    fn snapshot(_item: &Self::Item<'_>) -> check::Snapshot<Self::Field> {
+        check::Snapshot::EMPTY
+    }
 }
 
 /// Holds a [`Modify::Item`] and keeps track of changes to it.
@@ -141,6 +238,13 @@ impl<F: EnumSetType, T> Changing<F, T> {
         self.updated |= updated;
         f(&mut self.value);
     }
+    /// Declare that `updated` changed, without accessing the value.
+    ///
+    /// Use this for [`Modify::context_fields`], since they are not stored
+    /// in the value.
+    pub fn mark_updated(&mut self, updated: EnumSet<F>) {
+        self.updated |= updated;
+    }
     /// Reset the change tracker state.
     pub fn reset_updated(&mut self) {
         self.updated = EnumSet::EMPTY;
@@ -0,0 +1,115 @@
+//! Verify that [`Modify::apply`] only changes the fields it declares.
+//!
+//! [`Modify::apply`] must only update fields returned by [`Modify::changes`],
+//! otherwise the [`Resolver`] silently skips updating dependent modifiers.
+//!
+//! With the `check_modify` feature enabled, in debug builds, the [`Resolver`]s
+//! take a [`Snapshot`] of every [`Modify::Field`] of the item before and after
+//! running [`Modify::apply`], and report any changed field that wasn't declared.
+//! Enable `check_modify_panic` to panic instead of logging an error.
+//!
+//! Note that this can only detect undeclared _writes_. Undeclared _reads_ are
+//! not detectable by comparing snapshots.
+//!
+//! [`impl_modify!`] implements [`Modify::snapshot`] for all the fields of the
+//! item that are formattable with [`fmt::Debug`]. Fields that do not implement
+//! `Debug` are not checked.
+//!
+//! The default [`Modify::snapshot`] doesn't track any field, so hand-written
+//! `Modify` impls are only checked if they implement `snapshot` themselves.
+//!
+//! [`Resolver`]: crate::resolve::Resolver
+//! [`impl_modify!`]: crate::impl_modify
+
+use std::fmt;
+
+use enumset::{EnumSet, EnumSetType};
+
+#[cfg(any(doc, all(debug_assertions, feature = "check_modify")))]
+use super::Modify;
+
+/// The value of each [`Modify::Field`] of a [`Modify::Item`] at a given time.
+///
+/// Created by [`Modify::snapshot`].
+pub struct Snapshot<F>(Vec<(F, Option<String>)>);
+impl<F: EnumSetType> Snapshot<F> {
+    /// A `Snapshot` that doesn't track any field.
+    pub const EMPTY: Self = Snapshot(Vec::new());
+
+    /// Create a `Snapshot` from a list of fields and their debug representation.
+    ///
+    /// A field with a `None` value is never considered changed.
+    pub fn new(fields: impl IntoIterator<Item = (F, Option<String>)>) -> Self {
+        Snapshot(fields.into_iter().collect())
+    }
+    /// Fields with a different value in `self` and `after`.
+    pub fn changed(&self, after: &Self) -> EnumSet<F> {
+        let mut changed = EnumSet::new();
+        for ((field, before), (_, after)) in self.0.iter().zip(after.0.iter()) {
+            if let (Some(before), Some(after)) = (before, after) {
+                if before != after {
+                    changed |= *field;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Report fields changed between `before` and `after` that are not declared
+/// in `modify`'s [`Modify::changes`].
+///
+/// Returns the undeclared changed fields.
+#[cfg(all(debug_assertions, feature = "check_modify"))]
+pub(crate) fn verify<M: Modify>(
+    modify: &M,
+    before: &Snapshot<M::Field>,
+    after: &Snapshot<M::Field>,
+) -> EnumSet<M::Field> {
+    let undeclared = before.changed(after) - modify.changes();
+    if undeclared.is_empty() {
+        return undeclared;
+    }
+    let name = std::any::type_name::<M>();
+    let msg = format!(
+        "{name}::apply changed fields it didn't declare in `changes`.\n\
+        Modifier: {modify:?}\n\
+        Declared changes: {:?}\n\
+        Undeclared changes: {undeclared:?}",
+        modify.changes(),
+    );
+    if cfg!(feature = "check_modify_panic") {
+        panic!("{msg}");
+    } else {
+        log::error!("{msg}");
+    }
+    undeclared
+}
+
+// Autoref-based specialization, to snapshot fields that implement `Debug`
+// and silently ignore the others.
+//
+// `(&Snap(&value)).snap()` picks `SnapDebug` if `value: Debug`, since it
+// doesn't require an additional autoref, otherwise it falls back to `SnapOpaque`.
+
+#[doc(hidden)]
+pub struct Snap<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait SnapDebug {
+    fn snap(&self) -> Option<String>;
+}
+impl<T: fmt::Debug + ?Sized> SnapDebug for Snap<'_, T> {
+    fn snap(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+#[doc(hidden)]
+pub trait SnapOpaque {
+    fn snap(&self) -> Option<String>;
+}
+impl<T: ?Sized> SnapOpaque for &'_ Snap<'_, T> {
+    fn snap(&self) -> Option<String> {
+        None
+    }
+}
@@ -1,6 +1,8 @@
 mod impl_fmt;
 mod make;
 mod minimal;
+#[cfg(test)]
+mod tests;
 
 use std::{mem::size_of, ops::Range};
 
@@ -8,10 +10,11 @@ use datazoo::{
     AssumeSortedByItemExt, EnumMultimap, IndexMultimap, JaggedBitset, RawIndexMap, SortedByItem,
     SortedIterator,
 };
+use enumset::EnumSet;
 use log::warn;
 
 use crate::binding::{Id, View};
-use crate::modify::{Changing, FieldsOf, Indexed, MakeItem, Modify};
+use crate::modify::{Changed, Changing, FieldsOf, Indexed, MakeItem, Modify};
 
 pub use minimal::MinResolver;
 
@@ -56,7 +59,7 @@ pub trait Resolver<M: Modify>: Sized {
         updates: &'a Changing<M::Field, M::MakeItem>,
         bindings: View<'a, M>,
         ctx: &M::Context<'_>,
-    );
+    ) -> Changed;
 }
 
 #[rustfmt::skip]
@@ -176,9 +179,9 @@ impl<M: Modify, const MC: usize> Resolver<M> for DepsResolver<M, MC> {
         updates: &'a Changing<M::Field, M::MakeItem>,
         bindings: View<'a, M>,
         ctx: &M::Context<'_>,
-    ) {
+    ) -> Changed {
         let Changing { updated, value: root } = updates;
-        Evaluator { graph: self, root, bindings }.update_all(*updated, to_update, ctx);
+        Evaluator { graph: self, root, bindings }.update_all(*updated, to_update, ctx)
     }
 }
 impl<M: Modify, const MC: usize> DepsResolver<M, MC> {
@@ -188,6 +191,16 @@ impl<M: Modify, const MC: usize> DepsResolver<M, MC> {
     fn depends_on(&self, changes: FieldsOf<M>) -> impl Iterator<Item = ModifyIndex> + '_ {
         self.f2m.all_rows(changes).copied()
     }
+    /// Whether the modifier at `index` depends on any of `fields`.
+    fn depends_on_any(&self, index: ModifyIndex, fields: FieldsOf<M>) -> bool {
+        let modify = self.modifier_at(index).modify.as_ref();
+        modify.map_or(false, |modify| !modify.depends().is_disjoint(fields))
+    }
+    /// Whether the modifier at `index` changes any of `fields`.
+    fn changes_any(&self, index: ModifyIndex, fields: FieldsOf<M>) -> bool {
+        let modify = self.modifier_at(index).modify.as_ref();
+        modify.is_some_and(|modify| !modify.changes().is_disjoint(fields))
+    }
     fn modifier_at(&self, index: ModifyIndex) -> &Modifier<M> {
         // SAFETY: we assume that it is not possible to build an invalid `ModifyIndex`.
         // Note: it is only possible to assume this because `ModifyIndex` is not exposed
@@ -236,6 +249,12 @@ struct Evaluator<'a, T, M: Modify, const MC: usize> {
 }
 impl<'a, T: for<'m> MakeItem<'m, M::Item<'m>>, M: Modify, const MC: usize> Evaluator<'a, T, M, MC> {
     // TODO(clean): flag arguments are icky
+    /// Apply modifier at `index` and all modifiers depending on it.
+    ///
+    /// Modifiers depending on `index` are skipped if `index` didn't change
+    /// any section, unless they change a field `index` also changes: sections
+    /// then hold the dependent's output, so a `write_neq` modifier may compare
+    /// its output to a value that was since modified by the dependent.
     fn update(
         &self,
         index: ModifyIndex,
@@ -243,37 +262,76 @@ impl<'a, T: for<'m> MakeItem<'m, M::Item<'m>>, M: Modify, const MC: usize> Evalu
         ctx: &M::Context<'_>,
         field_depends: bool,
         overlay: Option<&M>,
-    ) {
-        let Some((modify, range)) = self.graph.modify_at(index, overlay) else { return; };
+    ) -> Changed {
+        let Some((modify, range)) = self.graph.modify_at(index, overlay) else {
+            return Changed::No;
+        };
+        let mut changed = if field_depends { Changed::Yes } else { Changed::No };
         for section in range {
-            let mut section = to_update.get_mut(section as usize).unwrap();
+            let section_index = section as usize;
+            let mut section = to_update.get_mut(section_index).unwrap();
             if field_depends {
                 self.root.make_item(&mut section);
             }
-            if let Err(error) = modify.apply(ctx, section) {
-                warn!("Error when applying modifier {index:?} {modify:?}: {error}");
-            };
+            #[cfg(all(debug_assertions, feature = "check_modify"))]
+            let before = M::snapshot(&section);
+
+            // `make_item` overwrites the whole section.
+            let mut changes = if field_depends { EnumSet::all() } else { EnumSet::empty() };
+            match modify.apply(ctx, section) {
+                Ok(Changed::Yes) => {
+                    changed = Changed::Yes;
+                    changes |= modify.changes();
+                }
+                Ok(Changed::No) => {}
+                Err(error) => warn!("Error when applying modifier {index:?} {modify:?}: {error}"),
+            }
+            #[cfg(all(debug_assertions, feature = "check_modify"))]
+            {
+                let after = M::snapshot(&to_update.get_mut(section_index).unwrap());
+                crate::modify::check::verify(modify, &before, &after);
+            }
+            if !changes.is_empty() {
+                to_update.set_changed(section_index, changes);
+            }
         }
+        let own_changed = changed;
         for dep_index in self.graph.m2m.get(&index) {
-            self.update(dep_index, to_update, ctx, false, None);
+            let overwrites = self.graph.changes_any(dep_index, modify.changes());
+            if own_changed == Changed::No && !overwrites {
+                continue;
+            }
+            changed |= self.update(dep_index, to_update, ctx, false, None);
         }
+        changed
     }
     fn update_all(
         &self,
         updated_fields: FieldsOf<M>,
         to_update: &mut M::Items<'_, '_, '_>,
         ctx: &M::Context<'_>,
-    ) {
+    ) -> Changed {
         let bindings = self.bindings.changed();
+        let mut changed = Changed::No;
 
         for (&binding, bound_modify) in bindings {
             let Some(mod_index) = self.graph.index_of(binding) else { continue; };
 
-            self.update(mod_index, to_update, ctx, false, Some(bound_modify));
+            changed |= self.update(mod_index, to_update, ctx, false, Some(bound_modify));
             // TODO(feat): insert modify with dependencies if !modify.depends().is_empty()
         }
-        for index in self.graph.depends_on(updated_fields) {
-            self.update(index, to_update, ctx, true, None);
+        let context_fields = updated_fields & M::context_fields();
+        let item_fields = updated_fields - context_fields;
+        for index in self.graph.depends_on(item_fields) {
+            changed |= self.update(index, to_update, ctx, true, None);
+        }
+        // The root item didn't change, so there is no need to reset the sections.
+        for index in self.graph.depends_on(context_fields) {
+            if self.graph.depends_on_any(index, item_fields) {
+                continue;
+            }
+            changed |= self.update(index, to_update, ctx, false, None);
         }
+        changed
     }
 }
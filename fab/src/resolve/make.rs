@@ -67,9 +67,15 @@ impl<M: Modify, T: for<'a> MakeItem<'a, M::Item<'a>>, F: Fn() -> T> Make<M, F> {
                 let section = unsafe { sections.get_unchecked_mut(section as usize) };
                 let ModifyKind::Modify(modifier) = &modifier.kind else { continue; };
 
-                if let Err(err) = modifier.apply(ctx, section.as_item()) {
+                let item = section.as_item();
+                #[cfg(all(debug_assertions, feature = "check_modify"))]
+                let before = M::snapshot(&item);
+
+                if let Err(err) = modifier.apply(ctx, item) {
                     self.errors.push(err);
                 };
+                #[cfg(all(debug_assertions, feature = "check_modify"))]
+                crate::modify::check::verify(modifier, &before, &M::snapshot(&section.as_item()));
             }
 
             if !checker.is_static(modifier) {
@@ -101,7 +107,7 @@ impl<M: Modify, T: for<'a> MakeItem<'a, M::Item<'a>>, F: Fn() -> T> Make<M, F> {
             let depends = modify.depends().contains(field);
             let changes = modify.changes().contains(field);
 
-            let is_child = parent_field_range_end >= modify.range.start;
+            let is_child = parent_field_range_end > modify.range.start;
             let depends_on_parent = depends && is_child;
 
             if changes && parent_field_range_end < modify.range.end {
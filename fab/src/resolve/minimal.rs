@@ -9,7 +9,7 @@ use nonmax::NonMaxU32;
 
 use super::{MakeModify, ModifyKind, Resolver};
 use crate::binding::View;
-use crate::modify::{Changing, Indexed, MakeItem, Modify};
+use crate::modify::{Changed, Changing, Indexed, MakeItem, Modify};
 
 /// A resolver with minimal overhead and functionalities.
 ///
@@ -19,6 +19,7 @@ use crate::modify::{Changing, Indexed, MakeItem, Modify};
 ///   this is a simple array lookup.
 ///   
 /// [`DepsResolver`]: super::DepsResolver
+#[derive(Debug)]
 pub struct MinResolver {
     indices: Box<[Option<NonMaxU32>]>,
 }
@@ -55,15 +56,25 @@ impl<M: Modify> Resolver<M> for MinResolver {
                     let sections = unsafe { sections.get_unchecked_mut(range) };
 
                     sections.iter_mut().for_each(|section| {
-                        if let Err(err) = modify.apply(ctx, section.as_item()) {
+                        let item = section.as_item();
+                        #[cfg(all(debug_assertions, feature = "check_modify"))]
+                        let before = M::snapshot(&item);
+
+                        if let Err(err) = modify.apply(ctx, item) {
                             error!("Error occured when applying modify: {err}");
                         }
+                        #[cfg(all(debug_assertions, feature = "check_modify"))]
+                        crate::modify::check::verify(
+                            &modify,
+                            &before,
+                            &M::snapshot(&section.as_item()),
+                        );
                     });
                 }
             }
         }
         let mut indices = if let Some(max_binding) = bindings.iter().map(|b| b.0).max() {
-            vec![None; max_binding].into_boxed_slice()
+            vec![None; max_binding + 1].into_boxed_slice()
         } else {
             return (MinResolver { indices: Box::new([]) }, sections);
         };
@@ -79,15 +90,32 @@ impl<M: Modify> Resolver<M> for MinResolver {
         _: &'a Changing<M::Field, M::MakeItem>,
         bindings: View<'a, M>,
         ctx: &M::Context<'_>,
-    ) {
-        bindings.changed().for_each(|(binding, modify)| {
-            let Some(Some(index)) = self.indices.get(binding.get()) else { return; };
+    ) -> Changed {
+        let mut changed = Changed::No;
+        for (binding, modify) in bindings.changed() {
+            let Some(Some(index)) = self.indices.get(binding.get()) else { continue; };
             let index = index.get() as usize;
 
-            let Some(section) = to_update.get_mut(index) else { return; };
-            if let Err(err) = modify.apply(ctx, section) {
-                error!("Error occured when applying modify: {err}");
+            let Some(section) = to_update.get_mut(index) else { continue; };
+            #[cfg(all(debug_assertions, feature = "check_modify"))]
+            let before = M::snapshot(&section);
+
+            let section_changed = match modify.apply(ctx, section) {
+                Ok(section_changed) => section_changed,
+                Err(err) => {
+                    error!("Error occured when applying modify: {err}");
+                    continue;
+                }
+            };
+            #[cfg(all(debug_assertions, feature = "check_modify"))]
+            if let Some(after) = to_update.get_mut(index) {
+                crate::modify::check::verify(modify, &before, &M::snapshot(&after));
             }
-        });
+            if section_changed == Changed::Yes {
+                to_update.set_changed(index, modify.changes());
+            }
+            changed |= section_changed;
+        }
+        changed
     }
 }
@@ -0,0 +1,270 @@
+use std::ops::Range;
+
+use enumset::{__internal::EnumSetTypePrivate, EnumSet, EnumSetType};
+use pretty_assertions::assert_eq;
+
+use super::{DepsResolver, MakeModify, MinResolver, ModifyKind, Resolver};
+use crate::binding::{Id, World};
+use crate::modify::{check::Snapshot, Changed, Changing, FieldsOf, Modify};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Section {
+    size: u32,
+    hits: u32,
+}
+
+#[derive(EnumSetType, Debug)]
+enum Field {
+    Size,
+    Hits,
+    /// A context field, not stored in sections.
+    Clock,
+}
+
+type Deps = DepsResolver<Op, { (<Field as EnumSetTypePrivate>::BIT_WIDTH - 1) as usize }>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Op {
+    /// Set the size.
+    Size(u32),
+    /// Set the size, returning `Changed::No` if it is already `size`.
+    SizeNeq(u32),
+    /// Multiply the size.
+    Scale(u32),
+    /// Increment `hits`, without declaring it in `changes`.
+    Sneaky,
+    /// Increment `hits` each time the `Clock` context field is updated.
+    Tick,
+    /// Increment `hits` each time the size is updated.
+    Count,
+}
+impl Modify for Op {
+    type MakeItem = Section;
+    type Item<'a> = &'a mut Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+    type Field = Field;
+    type Context<'a> = ();
+    type Resolver = Deps;
+
+    fn apply(&self, (): &(), item: &mut Section) -> anyhow::Result<Changed> {
+        match self {
+            Op::Size(size) => item.size = *size,
+            Op::SizeNeq(size) if item.size == *size => return Ok(Changed::No),
+            Op::SizeNeq(size) => item.size = *size,
+            Op::Scale(by) => item.size *= by,
+            Op::Sneaky | Op::Tick | Op::Count => item.hits += 1,
+        }
+        Ok(Changed::Yes)
+    }
+    fn depends(&self) -> EnumSet<Field> {
+        match self {
+            Op::Size(_) | Op::SizeNeq(_) | Op::Sneaky => EnumSet::EMPTY,
+            Op::Scale(_) | Op::Count => Field::Size.into(),
+            Op::Tick => Field::Clock.into(),
+        }
+    }
+    fn changes(&self) -> EnumSet<Field> {
+        match self {
+            Op::Size(_) | Op::SizeNeq(_) | Op::Scale(_) => Field::Size.into(),
+            Op::Sneaky => EnumSet::EMPTY,
+            Op::Tick | Op::Count => Field::Hits.into(),
+        }
+    }
+    fn context_fields() -> EnumSet<Field> {
+        Field::Clock.into()
+    }
+    fn snapshot(item: &&mut Section) -> Snapshot<Field> {
+        let Section { size, hits } = item;
+        Snapshot::new([
+            (Field::Size, Some(size.to_string())),
+            (Field::Hits, Some(hits.to_string())),
+        ])
+    }
+}
+
+fn bound(binding: Id, changes: FieldsOf<Op>, range: Range<u32>) -> MakeModify<Op> {
+    let kind = ModifyKind::Bound { binding, depends: EnumSet::EMPTY, changes };
+    MakeModify { kind, range }
+}
+fn statik(modify: Op, range: Range<u32>) -> MakeModify<Op> {
+    MakeModify { kind: ModifyKind::Modify(modify), range }
+}
+fn sizes(sections: &[Section]) -> Vec<u32> {
+    sections.iter().map(|s| s.size).collect()
+}
+fn hits(sections: &[Section]) -> Vec<u32> {
+    sections.iter().map(|s| s.hits).collect()
+}
+
+#[test]
+fn min_resolver_last_binding() {
+    let mut world = World::default();
+    let (first, last) = (world.get_or_add("first"), world.get_or_add("last"));
+    let modifiers = vec![
+        bound(first, Field::Size.into(), 0..1),
+        bound(last, Field::Size.into(), 1..2),
+    ];
+    let (resolver, mut sections) = MinResolver::new(modifiers, Section::default, &());
+
+    world.set_id(last, Op::Size(5));
+    let root = Changing::new(Section::default());
+    resolver.update(&mut sections, &root, world.view(), &());
+
+    assert_eq!(sizes(&sections), vec![0, 5]);
+}
+/// A modifier starting where the range of a previous modifier ends is not
+/// within it, so it depends on the root rather than on the previous modifier.
+///
+/// This used to be registered as a child of the `size` binding, so the
+/// `Scale(5)` modifier ignored changes to the root.
+#[test]
+fn adjacent_range_depends_on_root() {
+    let mut world = World::default();
+    let size = world.get_or_add("size");
+    let modifiers = vec![
+        statik(Op::Scale(2), 0..1),
+        bound(size, Field::Size.into(), 1..2),
+        statik(Op::Scale(3), 1..2),
+        // Starts where the `size` binding ends, so it depends on the root.
+        statik(Op::Scale(5), 2..3),
+    ];
+    let (resolver, mut sections) = Deps::new(modifiers, Section::default, &());
+
+    let mut root = Changing::new(Section::default());
+    root.update(Field::Size, |section| section.size = 10);
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(sizes(&sections), vec![20, 0, 50]);
+
+    world.set_id(size, Op::Size(4));
+    let root = Changing::new(Section::default());
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(sizes(&sections), vec![20, 12, 50]);
+}
+/// A `write_neq` modifier compares its output with the section as modified by
+/// the modifiers depending on it, those must run even if it returns `Changed::No`.
+#[test]
+fn unchanged_neq_write_updates_dependents() {
+    let mut world = World::default();
+    let size = world.get_or_add("size");
+    let modifiers = vec![
+        bound(size, Field::Size.into(), 0..1),
+        statik(Op::Scale(2), 0..1),
+    ];
+    let (resolver, mut sections) = Deps::new(modifiers, Section::default, &());
+    let root = Changing::new(Section::default());
+
+    world.set_id(size, Op::SizeNeq(20));
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(sizes(&sections), vec![40]);
+    world.reset_changes();
+
+    // The section's size is already 40, but `Scale(2)` must run again.
+    world.set_id(size, Op::SizeNeq(40));
+    let changed = resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(sizes(&sections), vec![80]);
+    assert_eq!(changed, Changed::Yes);
+}
+/// A modifier that doesn't change any section doesn't re-run the modifiers
+/// depending on it, if they don't change the same fields.
+#[test]
+fn unchanged_write_skips_dependents() {
+    let mut world = World::default();
+    let size = world.get_or_add("size");
+    let modifiers = vec![
+        bound(size, Field::Size.into(), 0..2),
+        statik(Op::Count, 1..2),
+    ];
+    let (resolver, mut sections) = Deps::new(modifiers, Section::default, &());
+    let root = Changing::new(Section::default());
+
+    world.set_id(size, Op::SizeNeq(20));
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![20, 20], vec![0, 2])
+    );
+    world.reset_changes();
+
+    world.set_id(size, Op::SizeNeq(20));
+    let changed = resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![20, 20], vec![0, 2])
+    );
+    assert_eq!(changed, Changed::No);
+    world.reset_changes();
+
+    world.set_id(size, Op::SizeNeq(30));
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![30, 30], vec![0, 3])
+    );
+}
+/// Modifiers depending on a context field run again when it is updated, but
+/// their sections are not reset to the root, since it didn't change.
+#[test]
+fn context_field_keeps_sections() {
+    let world = World::default();
+    let modifiers = vec![statik(Op::Size(3), 0..2), statik(Op::Tick, 1..2)];
+    let (resolver, mut sections) = Deps::new(modifiers, Section::default, &());
+    let mut root = Changing::new(Section::default());
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![3, 3], vec![0, 1])
+    );
+
+    root.mark_updated(Field::Clock.into());
+    resolver.update(&mut sections, &root, world.view(), &());
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![3, 3], vec![0, 3])
+    );
+
+    root.reset_updated();
+    resolver.update(&mut sections, &root, world.view(), &());
+    assert_eq!(
+        (sizes(&sections), hits(&sections)),
+        (vec![3, 3], vec![0, 3])
+    );
+}
+#[cfg(all(debug_assertions, feature = "check_modify"))]
+#[cfg_attr(
+    feature = "check_modify_panic",
+    should_panic(expected = "didn't declare")
+)]
+#[test]
+fn undeclared_write() {
+    use crate::modify::check::verify;
+
+    let mut section = Section::default();
+    let before = Op::snapshot(&&mut section);
+    Op::Size(3).apply(&(), &mut section).unwrap();
+    let after = Op::snapshot(&&mut section);
+    assert_eq!(
+        verify(&Op::Size(3), &before, &after),
+        EnumSet::<Field>::EMPTY
+    );
+
+    let before = after;
+    Op::Sneaky.apply(&(), &mut section).unwrap();
+    let after = Op::snapshot(&&mut section);
+    let expected: EnumSet<Field> = Field::Hits.into();
+    assert_eq!(verify(&Op::Sneaky, &before, &after), expected);
+}
+/// The resolver checks modifiers bound after creation, not only the static ones.
+#[cfg(all(debug_assertions, feature = "check_modify_panic"))]
+#[should_panic(expected = "didn't declare")]
+#[test]
+fn resolver_catches_undeclared_write() {
+    let mut world = World::default();
+    let sneaky = world.get_or_add("sneaky");
+    let modifiers = vec![bound(sneaky, EnumSet::EMPTY, 0..1)];
+    let (resolver, mut sections) = Deps::new(modifiers, Section::default, &());
+
+    world.set_id(sneaky, Op::Sneaky);
+    let root = Changing::new(Section::default());
+    resolver.update(&mut sections, &root, world.view(), &());
+}
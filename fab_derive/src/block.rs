@@ -1,13 +1,14 @@
 use std::mem;
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use syn::{meta::ParseNestedMeta, spanned::Spanned, Visibility};
 
 use crate::{
-    extensions::{GetIdentExt, IntoSynErrorsExt},
+    extensions::IntoSynErrorsExt,
     modifiers::AtomicAccessors,
     modify_fn::ModifyFn,
+    parsable,
 };
 
 const BAD_ASSOC_TYPE: &str = "Modify as a trait requires the following \
@@ -28,6 +29,12 @@ struct Generic {
     ty: syn::Type,
     gens: syn::Generics,
 }
+impl Generic {
+    /// `where` clause of the associated type, to add after `= #ty`.
+    fn where_clause(&self) -> Option<&syn::WhereClause> {
+        self.gens.where_clause.as_ref()
+    }
+}
 impl From<syn::ImplItemType> for Generic {
     fn from(value: syn::ImplItemType) -> Self {
         Generic { ty: value.ty, gens: value.generics }
@@ -88,32 +95,108 @@ fn read_item(item: syn::ImplItem, assoc: &mut AssociatedTypes) -> syn::Result<Op
     }
 }
 
+/// The name of the modify type in `impl Modify for #self_ty`.
+///
+/// `self_ty` may have generic arguments, but they must be exactly the generic
+/// parameters of the `impl` block, in the same order, since `impl_modify`
+/// declares the modify enum with the `impl` block's generic parameters.
+fn modify_ident(self_ty: &syn::Type, generics: &syn::Generics) -> syn::Result<Ident> {
+    use syn::{GenericParam, PathArguments, Type::Path, TypePath};
+
+    let msg = "impl_modify's type must be a single identifier with optionally \
+        the impl block's generic parameters as arguments, such as `MyModify<T>`";
+    let err = || syn::Error::new(self_ty.span(), msg);
+
+    let Path(TypePath { qself: None, path }) = self_ty else { return Err(err()); };
+    let (Some(segment), 1) = (path.segments.first(), path.segments.len()) else {
+        return Err(err());
+    };
+    let arguments: Vec<_> = match &segment.arguments {
+        PathArguments::None => Vec::new(),
+        PathArguments::AngleBracketed(args) => {
+            args.args.iter().map(|a| a.to_token_stream().to_string()).collect()
+        }
+        PathArguments::Parenthesized(_) => return Err(err()),
+    };
+    let parameters: Vec<_> = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream().to_string(),
+            GenericParam::Type(param) => param.ident.to_string(),
+            GenericParam::Const(param) => param.ident.to_string(),
+        })
+        .collect();
+    if arguments != parameters {
+        let msg = format!(
+            "impl_modify's type generic arguments must be exactly the impl \
+            block's generic parameters: <{}>",
+            parameters.join(", ")
+        );
+        return Err(syn::Error::new(segment.arguments.span(), msg));
+    }
+    Ok(segment.ident.clone())
+}
+/// The `#[doc(hidden)]` variant of the modify enum that uses the `impl` block's
+/// type and lifetime parameters.
+///
+/// Rust requires that all type and lifetime parameters of an `enum` are used.
+/// Since it is possible that not all variants use all parameters, we add a
+/// variant that can't be constructed, using all of them.
+///
+/// Returns `None` when there is no such parameters.
+fn phantom_variant(generics: &syn::Generics) -> Option<TokenStream> {
+    let lifetimes = generics.lifetimes().map(|l| {
+        let lifetime = &l.lifetime;
+        quote!(&#lifetime ())
+    });
+    let types = generics.type_params().map(|t| t.ident.to_token_stream());
+    let phantoms: Vec<_> = lifetimes.chain(types).collect();
+    (!phantoms.is_empty()).then(|| {
+        quote! {
+            #[doc(hidden)]
+            __Generics(
+                ::std::convert::Infallible,
+                ::std::marker::PhantomData<fn() -> ( #( #phantoms, )* )>,
+            )
+        }
+    })
+}
+
 pub(crate) struct Config {
     fab_path: syn::Path,
+    fab_parse_path: syn::Path,
     enumset_crate: Ident,
     visibility: syn::Visibility,
     no_debug_derive: bool,
     no_clone_derive: bool,
+    parsable: bool,
 }
 impl Default for Config {
     fn default() -> Self {
         Config {
             fab_path: syn::parse_quote!(::cuicui_fab),
+            fab_parse_path: syn::parse_quote!(::cuicui_fab_parse),
             enumset_crate: Ident::new("enumset", Span::call_site()),
             visibility: Visibility::Public(syn::token::Pub { span: Span::call_site() }),
             no_debug_derive: false,
             no_clone_derive: false,
+            parsable: false,
         }
     }
 }
 const CONFIG_ATTR_DESCR: &str = "\
 - `cuicui_fab_path = alternate::path`: specify which path to use for the \
   `cuicui_fab` crate by default, it is `::cuicui_fab`
+- `cuicui_fab_parse_path = alternate::path`: specify which path to use for the \
+  `cuicui_fab_parse` crate by default, it is `::cuicui_fab_parse`
 - `enumset_crate = identifier`: specify which path to use for the `enumset` \
   crate by default, it is `enumset`
 - `no_derive(Debug | Clone)`: Do not automatically implement given trait for Modifier.
 - `visibility = [pub(crate)]`: specify the visibility for the generated enums.
-  by default, it is `pub`\n";
+  by default, it is `pub`
+- `parsable`: implement `cuicui_fab_parse::Parsable` for the Modifier, \
+  see the `#[parse(…)]` function attribute\n";
 
 impl Config {
     pub(crate) fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
@@ -122,6 +205,11 @@ impl Config {
                 let value = meta.value()?;
                 self.fab_path = value.parse()?;
             }
+            () if meta.path.is_ident("cuicui_fab_parse_path") => {
+                let value = meta.value()?;
+                self.fab_parse_path = value.parse()?;
+            }
+            () if meta.path.is_ident("parsable") => self.parsable = true,
             () if meta.path.is_ident("enumset_crate") => {
                 let value = meta.value()?;
                 self.enumset_crate = value.parse()?;
@@ -131,7 +219,7 @@ impl Config {
                     self.no_debug_derive = true;
                 }
                 if meta.path.is_ident("Clone") {
-                    self.no_debug_derive = true;
+                    self.no_clone_derive = true;
                 }
                 Ok(())
             })?,
@@ -157,9 +245,12 @@ pub(crate) struct Block {
     make_item: Option<syn::Type>,
     functions: Vec<ModifyFn>,
     field_accessors: AtomicAccessors,
-    // TODO(feat): allow generics
     modify_ty: Ident,
+    /// Generic parameters of the `impl` block, also used for the modify enum.
+    generics: syn::Generics,
     fab_path: syn::Path,
+    /// Path to `cuicui_fab_parse` if `Parsable` should be implemented.
+    fab_parse_path: Option<syn::Path>,
     enumset_ident: Ident,
     visibility: Visibility,
     debug_derive: bool,
@@ -176,9 +267,8 @@ impl Block {
             }
             Ok(valid) => valid,
         };
-        let msg = "Modify derive with generic parameter not supported yet";
-        let err = syn::Error::new(input.span(), msg);
-        let modify_ty = input.self_ty.get_ident().ok_or(err)?.clone();
+        let generics = mem::take(&mut input.generics);
+        let modify_ty = modify_ident(&input.self_ty, &generics)?;
         let attributes = mem::take(&mut input.attrs);
         let mut functions: Vec<_> = input.items.drain(..).filter_map(read_fn).collect();
         let field_accessors = AtomicAccessors::from_non_atomic(
@@ -190,6 +280,12 @@ impl Block {
             .iter_mut()
             .for_each(|f| f.atomize_accessors(&field_accessors));
 
+        if !config.parsable {
+            let parse_attrs = functions.iter().filter_map(|f| f.parse.span);
+            let msg = "#[parse(…)] requires the `parsable` impl_modify option: \
+                `#[impl_modify(parsable)]`";
+            errors.extend(parse_attrs.map(|span| syn::Error::new(span, msg)));
+        }
         if let Some(error) = errors.into_syn_errors() {
             return Err(error);
         }
@@ -216,7 +312,9 @@ impl Block {
             functions,
             field_accessors,
             modify_ty,
+            generics,
             fab_path: config.fab_path,
+            fab_parse_path: config.parsable.then_some(config.fab_parse_path),
             enumset_ident: config.enumset_crate,
             visibility: config.visibility,
             debug_derive: !config.no_debug_derive,
@@ -232,7 +330,9 @@ impl Block {
             resolver,
             field_accessors,
             modify_ty,
+            generics,
             fab_path,
+            fab_parse_path,
             enumset_ident,
             visibility,
             debug_derive,
@@ -246,8 +346,18 @@ impl Block {
         let ctx = Ident::new("ctx", Span::call_site());
         let item_param = Ident::new("item", Span::call_site());
 
-        let field_accessors = field_accessors.all_variants();
         let field_ty = format_ident!("{modify_ty}Field");
+        let snapshots = field_accessors.item_snapshots(&field_ty, &item_param);
+        let context_fields = field_accessors.context_variants(&field_ty);
+        let field_accessors = field_accessors.all_variants();
+
+        let (impl_gens, ty_gens, where_clause) = generics.split_for_impl();
+        let items_where = self.items.where_clause();
+        let context_where = self.context.where_clause();
+        let phantom_variant = phantom_variant(generics);
+        let phantom_arm = phantom_variant
+            .as_ref()
+            .map(|_| quote!(Self::__Generics(never, _) => match *never {},));
 
         let fns = || self.functions.iter();
         let ty_variants = fns().map(|f| f.ty_variant(enumset_ident, &field_ty));
@@ -256,14 +366,16 @@ impl Block {
         let depends_arms = fns().map(|f| f.depends_arm(enumset_ident, &field_ty));
         let field_assoc_fns = fns().map(|f| f.fields_assoc_fns(enumset_ident, &field_ty));
         let ty_constructors = fns().map(|m| &m.constructor);
-        let ty_function_defs = fns().map(|m| &m.declaration);
-        let ty_function_calls = fns().map(|m| m.call(&ctx, &item_param));
+        let ty_function_defs = fns().map(|m| m.declaration(generics));
+        let ty_function_calls = fns().map(|m| m.call(fab_path, &ctx, &item_param, generics));
         let debug_derive = debug_derive.then(|| quote!(#[derive( ::std::fmt::Debug )]));
         let clone_derive = clone_derive.then(|| quote!(#[derive( ::std::clone::Clone )]));
         let resolver = resolver.as_ref().map_or_else(
             || {
                 let enumset_private = quote!(::#enumset_ident::__internal::EnumSetTypePrivate);
-                let bit_width = quote!((<Self::Field as #enumset_private>::BIT_WIDTH - 1) as usize);
+                // NOTE: we use `#field_ty` rather than `Self::Field`, since `Self`
+                // can't be used in const expressions when `Self` is generic.
+                let bit_width = quote!((<#field_ty as #enumset_private>::BIT_WIDTH - 1) as usize);
                 quote!(#fab_path::resolve::DepsResolver::<Self, {#bit_width} >)
             },
             |ty| quote!(#ty),
@@ -271,6 +383,10 @@ impl Block {
         let make_item = make_item
             .as_ref()
             .map_or_else(|| quote!(#item_ty), |ty| quote!(#ty));
+        let parsable_impl = fab_parse_path.as_ref().map(|parse_path| {
+            let fns = &self.functions;
+            parsable::impl_parsable(fns, parse_path, enumset_ident, &field_ty, modify_ty, generics)
+        });
 
         quote! {
             #[doc = concat!("Fields accessed by [`", stringify!(#modify_ty), "`].")]
@@ -292,65 +408,83 @@ impl Block {
             #( #attributes )*
             #debug_derive
             #clone_derive
-            #visibility enum #modify_ty {
-                #( #ty_variants ),*
+            #visibility enum #modify_ty #impl_gens #where_clause {
+                #( #ty_variants, )*
+                #phantom_variant
             }
             /// Functions returning which field each modify function changes
             /// and depends on.
             ///
             /// Note that if the modify function in question doesn't depend on
             /// anything, no function is provided.
-            impl #modify_ty {
+            impl #impl_gens #modify_ty #ty_gens #where_clause {
                 #(
                     #field_assoc_fns
                 )*
             }
             /// Constructors for each individual modify variant.
-            impl #modify_ty {
+            impl #impl_gens #modify_ty #ty_gens #where_clause {
                 #(
                     #ty_constructors
                 )*
             }
             #[allow(clippy::ptr_arg)]
-            impl Modify for #modify_ty {
+            impl #impl_gens Modify for #modify_ty #ty_gens #where_clause {
                 type Field = #field_ty;
-                type Context #context_gens = #context_ty;
+                type Context #context_gens = #context_ty #context_where;
                 // TODO: add &'a mut if Item not declared with <'a>
                 type Item #item_gens = #item_ty;
-                type Items #items_gens = #items_ty;
+                type Items #items_gens = #items_ty #items_where;
                 type MakeItem = #make_item;
                 type Resolver = #resolver;
 
                 fn apply #item_gens(
                     &self,
                     #ctx: &Self::Context<'_>,
-                    #item_param: #item_ty,
-                ) -> #fab_path::__private::anyhow::Result<()> {
-                    match self {
+                    #[allow(unused_mut)] mut #item_param: #item_ty,
+                ) -> #fab_path::__private::anyhow::Result<#fab_path::modify::Changed> {
+                    let changed = match self {
                         #(
                             Self::#ty_matcher => {
                                 #ty_function_defs
-                                #ty_function_calls;
+                                #ty_function_calls
                             }
-                        ),*
-                    }
-                    Ok(())
+                        )*
+                        #phantom_arm
+                    };
+                    Ok(changed)
                 }
 
                 #[inline]
                 fn depends(&self) -> ::#enumset_ident::EnumSet<Self::Field> {
                     match self {
-                        #( #depends_arms ),*
+                        #( #depends_arms, )*
+                        #phantom_arm
                     }
                 }
 
                 #[inline]
                 fn changes(&self) -> ::#enumset_ident::EnumSet<Self::Field> {
                     match self {
-                        #( #changes_arms ),*
+                        #( #changes_arms, )*
+                        #phantom_arm
                     }
                 }
+
+                #[inline]
+                fn context_fields() -> ::#enumset_ident::EnumSet<Self::Field> {
+                    ::#enumset_ident::EnumSet::EMPTY #( | #context_fields )*
+                }
+
+                #[allow(unused_imports)]
+                fn snapshot(
+                    #item_param: &Self::Item<'_>,
+                ) -> #fab_path::modify::check::Snapshot<Self::Field> {
+                    use #fab_path::modify::check::{Snap, SnapDebug as _, SnapOpaque as _};
+                    #fab_path::modify::check::Snapshot::new([ #( #snapshots ),* ])
+                }
             }
+            #parsable_impl
         }
     }
 }
@@ -23,15 +23,23 @@
 // `modify1` depends on `item.field` and `modify2` changes `item.field.inner`.
 // `modify1` is affected by changes made by `modify2`, yet, `Modifiers` doesn't know that.
 // `deps` provides way to split to the most precise dependency a set of `Modifiers`.
+//
+// `parsable` implements `Parsable` for the modify enum when the `parsable`
+// option is set, based on the `#[parse(…)]` attribute of each `ModifyFn`.
+//
+// `make_item` is the `MakeItem` derive macro, it declares a struct of `&mut`
+// to use as `Modify::Item`, independently from `impl_modify`.
 
 mod block;
 mod extensions;
+mod make_item;
 mod modifiers;
 mod modify_fn;
+mod parsable;
 
 use block::Config;
 use proc_macro::TokenStream as TokenStream1;
-use syn::{parse_macro_input, ItemImpl};
+use syn::{parse_macro_input, DeriveInput, ItemImpl};
 
 use crate::block::Block;
 
@@ -50,3 +58,39 @@ pub fn impl_modify(attrs: TokenStream1, input: TokenStream1) -> TokenStream1 {
         Ok(block) => block.generate_impl().into(),
     }
 }
+
+/// Declare a struct of `&mut` to each field of the derived struct, and implement
+/// `MakeItem` for the derived struct with the declared struct as item.
+///
+/// Use this to define a `Modify::Item` that spans several values. `impl_modify`
+/// field paths such as `.transform.scale` are then split into fine grained
+/// `Modify::Field`s.
+///
+/// ```text
+/// #[derive(Clone, Debug, MakeItem)]
+/// #[make_item(item = SpriteItem)]
+/// pub struct Sprite {
+///     transform: Transform,
+///     color: Color,
+/// }
+/// // Generates:
+/// pub struct SpriteItem<'a> {
+///     transform: &'a mut Transform,
+///     color: &'a mut Color,
+/// }
+/// impl<'a> MakeItem<'a, SpriteItem<'a>> for Sprite { /* ... */ }
+/// ```
+///
+/// Attributes:
+///
+/// - `item = Ident`: (required) name of the struct of `&mut` to declare.
+/// - `cuicui_fab_path = alternate::path`: specify which path to use for the
+///   `cuicui_fab` crate by default, it is `::cuicui_fab`.
+#[proc_macro_derive(MakeItem, attributes(make_item))]
+pub fn make_item(input: TokenStream1) -> TokenStream1 {
+    let input = parse_macro_input!(input as DeriveInput);
+    match make_item::derive(input) {
+        Err(errors) => errors.into_compile_error().into(),
+        Ok(tokens) => tokens.into(),
+    }
+}
@@ -0,0 +1,115 @@
+//! The `MakeItem` derive macro.
+//!
+//! Declares a struct of `&mut` with the same fields as the derived struct,
+//! and implements `MakeItem` for the derived struct with it as item.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{meta::ParseNestedMeta, spanned::Spanned};
+
+const MAKE_ITEM_ATTR_DESCR: &str = "\
+- `item = Ident`: (required) name of the struct of `&mut` to declare
+- `cuicui_fab_path = alternate::path`: specify which path to use for the \
+  `cuicui_fab` crate by default, it is `::cuicui_fab`\n";
+
+struct Config {
+    item: Option<syn::Ident>,
+    fab_path: syn::Path,
+}
+impl Config {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        match () {
+            () if meta.path.is_ident("item") => {
+                let value = meta.value()?;
+                self.item = Some(value.parse()?);
+            }
+            () if meta.path.is_ident("cuicui_fab_path") => {
+                let value = meta.value()?;
+                self.fab_path = value.parse()?;
+            }
+            () => {
+                let msg = format!("Unrecognized make_item meta attribute\n{MAKE_ITEM_ATTR_DESCR}");
+                return Err(meta.error(msg));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let mut config = Config { item: None, fab_path: syn::parse_quote!(::cuicui_fab) };
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("make_item")) {
+        attr.parse_nested_meta(|meta| config.parse(meta))?;
+    }
+    let Some(item_ty) = config.item else {
+        let msg = format!("MakeItem requires a #[make_item(…)] attribute\n{MAKE_ITEM_ATTR_DESCR}");
+        return Err(syn::Error::new(input.ident.span(), msg));
+    };
+    let syn::Data::Struct(data) = &input.data else {
+        let msg = "MakeItem can only be derived on structs";
+        return Err(syn::Error::new(input.ident.span(), msg));
+    };
+    let fab_path = &config.fab_path;
+    let make_ty = &input.ident;
+    let vis = &input.vis;
+
+    let lifetime = syn::Lifetime::new("'__item", Span::call_site());
+    let (_, make_ty_gens, _) = input.generics.split_for_impl();
+
+    // The item struct has the same generics as `make_ty` with an additional lifetime
+    // and `T: '__item` bounds, so that it can hold `&'__item mut T`.
+    let mut item_generics = input.generics.clone();
+    let lifetime_param = syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime.clone()));
+    item_generics.params.insert(0, lifetime_param);
+    let outlives = input.generics.type_params().map(|t| &t.ident);
+    let outlives = outlives.map(|t| -> syn::WherePredicate { syn::parse_quote!(#t: #lifetime) });
+    item_generics.make_where_clause().predicates.extend(outlives);
+    let (item_impl_gens, item_ty_gens, item_where) = item_generics.split_for_impl();
+
+    // `make_item` clones each field of `make_ty`.
+    let mut impl_generics = item_generics.clone();
+    let field_tys = data.fields.iter().map(|f| &f.ty);
+    let clone = field_tys.map(|t| -> syn::WherePredicate { syn::parse_quote!(#t: ::std::clone::Clone) });
+    impl_generics.make_where_clause().predicates.extend(clone);
+    let impl_where = &impl_generics.where_clause;
+
+    let item_fields = data.fields.iter().map(|field| {
+        let syn::Field { attrs, vis, ident, colon_token, ty, .. } = field;
+        let docs = attrs.iter().filter(|a| a.path().is_ident("doc"));
+        quote!( #( #docs )* #vis #ident #colon_token &#lifetime mut #ty )
+    });
+    let members: Vec<_> = data.fields.members().collect();
+    let item_declaration = match &data.fields {
+        syn::Fields::Named(_) => quote!(
+            #vis struct #item_ty #item_impl_gens #item_where { #( #item_fields ),* }
+        ),
+        syn::Fields::Unnamed(_) => quote!(
+            #vis struct #item_ty #item_impl_gens ( #( #item_fields ),* ) #item_where;
+        ),
+        syn::Fields::Unit => {
+            let msg = "MakeItem can't be derived on unit structs, they have no fields to modify";
+            return Err(syn::Error::new(input.span(), msg));
+        }
+    };
+    let item_doc = format!("A mutable view of [`{make_ty}`], used as `Modify::Item`.");
+
+    Ok(quote! {
+        #[doc = #item_doc]
+        #item_declaration
+
+        impl #item_impl_gens #fab_path::modify::MakeItem<#lifetime, #item_ty #item_ty_gens>
+            for #make_ty #make_ty_gens
+        #impl_where
+        {
+            fn make_item<'__b, '__c>(&'__b self, item: &'__c mut #item_ty #item_ty_gens)
+            where
+                #lifetime: '__c,
+            {
+                #( *item.#members = ::std::clone::Clone::clone(&self.#members); )*
+            }
+            fn as_item(&#lifetime mut self) -> #item_ty #item_ty_gens {
+                #item_ty { #( #members: &mut self.#members ),* }
+            }
+        }
+    })
+}
@@ -4,7 +4,7 @@ mod path;
 use std::fmt;
 
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{meta::ParseNestedMeta, parenthesized, parse::Parse, spanned::Spanned, ItemFn, Token};
 
 use crate::extensions::{GetIdentExt, IntoSynErrorsExt};
@@ -52,6 +52,8 @@ impl DynamicReadWrite {
 const MODIFY_ATTR_DESCR: &str = "\
 - `context([ident =] .path.in.context)`: The context declared in `type Context = Foo;`
 - `write(.path.in.item)`: path in item to write return value
+- `write_neq(.path.in.item)`: like `write`, but do not write the return value if
+  it is equal to the current one, and tell the resolver nothing changed
 - `write_mut([ident =] .path.in.item)`: write-only path in item to pass as `&mut ident`
 - `read([ident =] .path.in.item)`: read-only path in item to pass as `&ident`
 - `read_write([ident =] .path.in.item)`: read/write path in item to pass as `&mut ident`
@@ -64,6 +66,7 @@ const MODIFY_ATTR_DESCR: &str = "\
 enum ModifyType {
     Context,
     Write,
+    WriteNeq,
     WriteMut,
     Read,
     ReadWrite,
@@ -79,6 +82,7 @@ impl fmt::Display for Modify {
         let ty = match self.ty {
             Context => "context",
             Write => "write",
+            WriteNeq => "write_neq",
             WriteMut => "write_mut",
             Read => "read",
             ReadWrite => "read_write",
@@ -89,7 +93,7 @@ impl fmt::Display for Modify {
 impl Modify {
     fn is_write(&self) -> bool {
         use ModifyType::*;
-        matches!(self.ty, Write | WriteMut | ReadWrite)
+        matches!(self.ty, Write | WriteNeq | WriteMut | ReadWrite)
     }
     fn is_read(&self) -> bool {
         use ModifyType::*;
@@ -100,10 +104,14 @@ impl Modify {
         &self.path.ident
     }
     fn requires_identifier(&self) -> bool {
-        !matches!(self.ty, ModifyType::Write)
+        !matches!(self.ty, ModifyType::Write | ModifyType::WriteNeq)
     }
+    /// Whether this is passed to the modify function as the `ident` parameter.
+    ///
+    /// `write` and `write_neq` are the function's return value, they are never
+    /// passed as parameter.
     fn has_ident(&self, ident: &Ident) -> bool {
-        self.ident() == ident
+        self.requires_identifier() && self.ident() == ident
     }
 
     fn call_param(&self, ctx: &Ident, item: &Ident) -> Option<TokenStream> {
@@ -115,18 +123,7 @@ impl Modify {
             WriteMut | ReadWrite => Some(quote! { &mut #item #path }),
             Context => Some(quote! { & #ctx #path }),
             Read => Some(quote! { & #item #path }),
-            Write => None,
-        }
-    }
-
-    fn as_output(&self, item: &Ident) -> Option<TokenStream> {
-        use ModifyType::Write;
-
-        if let Write = self.ty {
-            let tokens = self.path.to_tokens();
-            Some(quote! { #item #tokens = })
-        } else {
-            None
+            Write | WriteNeq => None,
         }
     }
 }
@@ -140,6 +137,7 @@ impl Modifiers {
         let non_dynamic_idents = [
             ("context", (ModifyType::Context, Source::Context)),
             ("write", (ModifyType::Write, Source::Item)),
+            ("write_neq", (ModifyType::WriteNeq, Source::Item)),
             ("write_mut", (ModifyType::WriteMut, Source::Item)),
             ("read", (ModifyType::Read, Source::Item)),
             ("read_write", (ModifyType::ReadWrite, Source::Item)),
@@ -160,26 +158,27 @@ impl Modifiers {
                 self.dynamic = Some(dynamic.parse()?);
             }
             None => {
-                return Err(syn::Error::new(meta.input.span(), MODIFY_ATTR_DESCR));
+                let msg = format!("Unrecognized modify attribute\n{MODIFY_ATTR_DESCR}");
+                return Err(meta.error(msg));
             }
         }
         Ok(())
     }
-    /// The field in the item to write to: `item.path.to.field =`
-    ///
-    /// Returns empty stream if write modify is passed by reference.
-    fn write_field(&self, item: &Ident) -> TokenStream {
-        let found = self.mods.iter().find_map(|m| m.as_output(item));
-        found.unwrap_or(quote!())
-    }
     /// The call site: `item.path = fn_name(field1, field2, &item.input1, &mut item.inout)`
+    ///
+    /// `fn_name` may have a turbofish: `fn_name::<T>`. The call site evaluates
+    /// to a `Changed`, `Changed::Yes` unless the function has a `write_neq`
+    /// output equal to the current value.
     pub fn call<'a>(
         &self,
-        fn_name: &Ident,
+        fab_path: &syn::Path,
+        fn_name: TokenStream,
         ctx: &Ident,
         item: &Ident,
         inputs: impl Iterator<Item = (bool, &'a Ident)>,
     ) -> TokenStream {
+        use ModifyType::{Write, WriteNeq};
+
         let is_dynamic = |it| self.dynamic.as_ref().is_some_and(|d| &d.param_name == it);
         let parameters = inputs.map(|(must_deref, param)| {
             let must_deref = must_deref && !is_dynamic(param);
@@ -192,9 +191,33 @@ impl Modifiers {
                 None => quote!(#deref #param),
             }
         });
-        let write_field = self.write_field(item);
-        quote! {
-            #write_field #fn_name ( #( #parameters ),* )
+        let call = quote!(#fn_name ( #( #parameters ),* ));
+        let changed = quote!(#fab_path::modify::Changed);
+        let output = self.mods.iter().find(|m| matches!(m.ty, Write | WriteNeq));
+        match output {
+            Some(Modify { ty: WriteNeq, path }) => {
+                let path = path.to_tokens();
+                quote!({
+                    let new_value = #call;
+                    if #item #path != new_value {
+                        #item #path = new_value;
+                        #changed::Yes
+                    } else {
+                        #changed::No
+                    }
+                })
+            }
+            Some(Modify { path, .. }) => {
+                let path = path.to_tokens();
+                quote!({
+                    #item #path = #call;
+                    #changed::Yes
+                })
+            }
+            None => quote!({
+                #call;
+                #changed::Yes
+            }),
         }
     }
     /// Returns `Err` when `function` is invalid.
@@ -223,7 +246,8 @@ impl Modifiers {
 
         for input in &function.sig.inputs {
             let Some(ident) = input.get_ident() else {
-                bail!(format!("has a non-identifier input: '{input:?}', not supported"));
+                let input = input.to_token_stream();
+                bail!(format!("has a non-identifier input: '{input}', not supported"));
             };
             let Some(index) = self.mods.iter().position(|m| m.has_ident(ident)) else {
                 continue;
@@ -4,7 +4,7 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 
 use super::{
-    path::{Components, Path},
+    path::{Components, Path, Source},
     Mode, Modifiers,
 };
 
@@ -137,6 +137,36 @@ impl AtomicAccessors {
         atomic
     }
 
+    /// `(Field::Variant, snapshot)` for each accessor into the item, used in
+    /// the `Modify::snapshot` method.
+    ///
+    /// Accessors into the context are skipped, since the context is immutable.
+    pub(crate) fn item_snapshots<'a>(
+        &'a self,
+        field_ty: &'a Ident,
+        item: &'a Ident,
+    ) -> impl Iterator<Item = TokenStream> + 'a {
+        let is_item = |a: &&Accessor| a.comps.source == Source::Item;
+        self.0.iter().filter(is_item).map(move |a| {
+            let variant = a.comps.variant_ident(a.span);
+            let path = a.comps.to_tokens();
+            quote!( (#field_ty::#variant, (&Snap(&#item #path)).snap()) )
+        })
+    }
+
+    /// `Field::Variant` for each accessor into the context, used in the
+    /// `Modify::context_fields` method.
+    pub(crate) fn context_variants<'a>(
+        &'a self,
+        field_ty: &'a Ident,
+    ) -> impl Iterator<Item = TokenStream> + 'a {
+        let is_context = |a: &&Accessor| a.comps.source == Source::Context;
+        self.0.iter().filter(is_context).map(move |a| {
+            let variant = a.comps.variant_ident(a.span);
+            quote!(#field_ty::#variant)
+        })
+    }
+
     pub(crate) fn all_variants(&self) -> impl Iterator<Item = TokenStream> + '_ {
         let to_variant = |a: &Accessor| {
             let literal = syn::LitStr::new(&a.comps.doc_string(), a.span);
@@ -81,14 +81,31 @@ impl Source {
     }
     fn parse_comps(&self, input: syn::parse::ParseStream) -> syn::Result<Components> {
         let span = input.span();
-        let parse_single = || match () {
+        let mut components = Vec::new();
+        let mut parse_single = || match () {
             () if input.peek(Token![.]) && input.peek2(syn::Ident) => {
                 let _ = input.parse::<Token![.]>()?;
-                Ok(Some(Component::Field(input.parse()?)))
+                components.push(Component::Field(input.parse()?));
+                Ok(true)
+            }
+            // `.0.1` is tokenized as `.` followed by the float literal `0.1`.
+            () if input.peek(Token![.]) && input.peek2(syn::LitFloat) => {
+                let _ = input.parse::<Token![.]>()?;
+                let float = input.parse::<syn::LitFloat>()?;
+                let msg = "Invalid tuple field index in modify path";
+                let err = || syn::Error::new(float.span(), msg);
+                let (first, second) = float.base10_digits().split_once('.').ok_or_else(err)?;
+                for index in [first, second] {
+                    let index = index.parse().map_err(|_| err())?;
+                    let index = syn::Index { index, span: float.span() };
+                    components.push(Component::TupleField(index));
+                }
+                Ok(true)
             }
             () if input.peek(Token![.]) => {
                 let _ = input.parse::<Token![.]>()?;
-                Ok(Some(Component::TupleField(input.parse()?)))
+                components.push(Component::TupleField(input.parse()?));
+                Ok(true)
             }
             () if input.peek(syn::token::Bracket) => {
                 let content;
@@ -96,21 +113,19 @@ impl Source {
 
                 match content.parse()? {
                     syn::ExprLit { lit: syn::Lit::Int(value), .. } => {
-                        Ok(Some(Component::IntIndex(value)))
+                        components.push(Component::IntIndex(value));
                     }
                     syn::ExprLit { lit: syn::Lit::Str(value), .. } => {
-                        Ok(Some(Component::StringIndex(value)))
+                        components.push(Component::StringIndex(value));
                     }
                     // TODO: be less vague
-                    _ => Err(syn::Error::new(span, "Invalid modify path specifier")),
+                    _ => return Err(syn::Error::new(span, "Invalid modify path specifier")),
                 }
+                Ok(true)
             }
-            () => Ok(None),
+            () => Ok(false),
         };
-        let mut components = Vec::new();
-        while let Some(parsed) = parse_single()? {
-            components.push(parsed);
-        }
+        while parse_single()? {}
         if components.is_empty() {
             Err(syn::Error::new(span, "modify targets must be non-empty"))
         } else {
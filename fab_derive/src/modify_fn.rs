@@ -1,11 +1,13 @@
-use std::mem;
+use std::{collections::HashSet, mem};
 
 use heck::{AsSnakeCase, AsUpperCamelCase};
-use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{punctuated::Punctuated, spanned::Spanned, ItemFn, Token};
 
+use crate::extensions::GetIdentExt;
 use crate::modifiers::{AtomicAccessors, FnAtomicAccessors, Mode, Modifiers};
+use crate::parsable::ParseAttr;
 
 type Tokens = TokenStream;
 
@@ -127,18 +129,90 @@ fn mk_constructor(
     function
 }
 /// Declaration of modifier to insert into the match arms of the `apply` `Modify` method.
+///
+/// It is renamed with [`mk_declaration_name`], since the match arm binds
+/// variant fields that may have the same name as the function.
 fn mk_declaration(function: &mut ItemFn) {
     function.attrs.clear();
     function.vis = syn::Visibility::Inherited;
+    function.sig.ident = mk_declaration_name(&function.sig.ident);
+}
+fn mk_declaration_name(name: &Ident) -> Ident {
+    format_ident!("__modify_{name}", span = name.span())
+}
+
+/// Names of identifiers and lifetimes (with their leading `'`) in `tokens`.
+fn mentioned_names(tokens: TokenStream, names: &mut HashSet<String>) {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => mentioned_names(group.stream(), names),
+            TokenTree::Ident(ident) => {
+                names.insert(ident.to_string());
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(TokenTree::Ident(ident)) = tokens.next() {
+                    names.insert(format!("'{ident}"));
+                }
+            }
+            TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+        }
+    }
+}
+fn param_name(param: &syn::GenericParam) -> String {
+    match param {
+        syn::GenericParam::Lifetime(param) => param.lifetime.to_string(),
+        syn::GenericParam::Type(param) => param.ident.to_string(),
+        syn::GenericParam::Const(param) => param.ident.to_string(),
+    }
+}
+/// The subset of the `impl` block's `generics` used by `function`.
+///
+/// A parameter is used if it appears in `function`, or in the bounds of
+/// another used parameter. `where` predicates are kept if they mention a used
+/// parameter. Adding unused parameters to a function triggers
+/// `clippy::extra_unused_type_parameters`.
+fn used_generics(function: &ItemFn, generics: &syn::Generics) -> syn::Generics {
+    let params: HashSet<_> = generics.params.iter().map(param_name).collect();
+    let mentions = |tokens: TokenStream| {
+        let mut names = HashSet::new();
+        mentioned_names(tokens, &mut names);
+        names.retain(|name| params.contains(name));
+        names
+    };
+    let predicates = generics.where_clause.iter().flat_map(|w| &w.predicates);
+    let predicates: Vec<_> = predicates.map(|p| (p, mentions(p.to_token_stream()))).collect();
+    let param_bounds: Vec<_> = generics.params.iter().map(|p| mentions(p.to_token_stream())).collect();
+
+    let mut used = mentions(function.to_token_stream());
+    loop {
+        let used_count = used.len();
+        let bounds = predicates.iter().map(|(_, names)| names).chain(&param_bounds);
+        for names in bounds.filter(|names| !names.is_disjoint(&used)).collect::<Vec<_>>() {
+            used.extend(names.iter().cloned());
+        }
+        if used.len() == used_count {
+            break;
+        }
+    }
+    let params = generics.params.iter().filter(|p| used.contains(&param_name(p)));
+    let predicates = predicates.iter().filter(|(_, names)| !names.is_disjoint(&used));
+    let predicates: Punctuated<_, Token![,]> = predicates.map(|(p, _)| (*p).clone()).collect();
+    let where_clause = (!predicates.is_empty()).then(|| syn::WhereClause {
+        where_token: Default::default(),
+        predicates,
+    });
+    syn::Generics { params: params.cloned().collect(), where_clause, ..generics.clone() }
 }
 
 pub struct ModifyFn {
-    name: Ident,
+    pub name: Ident,
     inputs: Vec<syn::FnArg>,
     vis: syn::Visibility,
-    pub declaration: ItemFn,
+    declaration: ItemFn,
     pub constructor: Option<ItemFn>,
     pub modifiers: Modifiers,
+    pub parse: ParseAttr,
     accessors: Option<FnAtomicAccessors>,
 }
 impl ModifyFn {
@@ -147,6 +221,7 @@ impl ModifyFn {
         let name = input.sig.ident.clone();
         let ty_name = mk_tyname(&name);
 
+        let parse = ParseAttr::from_attrs(&mut input.attrs)?;
         let modifiers = Modifiers::from_attrs(&mut input.attrs)?;
         modifiers.validate(&input)?;
 
@@ -172,15 +247,67 @@ impl ModifyFn {
             declaration,
             constructor,
             modifiers,
+            parse,
             accessors: None,
         })
     }
     pub fn atomize_accessors(&mut self, atomic: &AtomicAccessors) {
         self.accessors = Some(FnAtomicAccessors::new(&self.modifiers, atomic));
     }
+    /// The modify function declared within the `apply` method.
+    ///
+    /// Functions nested in `apply` can't use the generic parameters of the
+    /// `impl` block, so we add the ones it uses to the function's own generic
+    /// parameters, see [`used_generics`].
+    pub fn declaration(&self, generics: &syn::Generics) -> ItemFn {
+        let mut declaration = self.declaration.clone();
+        let generics = used_generics(&declaration, generics);
+        if generics.params.is_empty() && generics.where_clause.is_none() {
+            return declaration;
+        }
+        let sig_generics = &mut declaration.sig.generics;
+        let own_params = mem::take(&mut sig_generics.params);
+        sig_generics.params = generics.params.into_iter().chain(own_params).collect();
+
+        if let Some(impl_where) = generics.where_clause {
+            let where_clause = sig_generics.make_where_clause();
+            where_clause.predicates.extend(impl_where.predicates);
+        }
+        declaration
+    }
     /// The call site: `item.path = fn_name(field1, field2, &item.input1, &mut item.inout)`
-    pub fn call(&self, ctx: &Ident, item: &Ident) -> Tokens {
-        let name = &self.name;
+    ///
+    /// When the `impl` block has type or const parameters, they are explicitly
+    /// passed to the function declared with [`Self::declaration`]:
+    /// `fn_name::<T, N, _>(…)`. Lifetimes are left to inference.
+    pub fn call(
+        &self,
+        fab_path: &syn::Path,
+        ctx: &Ident,
+        item: &Ident,
+        generics: &syn::Generics,
+    ) -> Tokens {
+        use syn::GenericParam::{Const, Lifetime, Type};
+
+        let name = mk_declaration_name(&self.name);
+        let generics = used_generics(&self.declaration, generics);
+        let impl_params = generics.params.iter().filter_map(|param| match param {
+            Lifetime(_) => None,
+            Type(param) => Some(param.ident.to_token_stream()),
+            Const(param) => Some(param.ident.to_token_stream()),
+        });
+        let own_params = self.declaration.sig.generics.params.iter();
+        let own_params = own_params.filter_map(|param| match param {
+            Lifetime(_) => None,
+            Type(_) | Const(_) => Some(quote!(_)),
+        });
+        let turbofish: Vec<_> = impl_params.collect();
+        let name = if turbofish.is_empty() {
+            quote!(#name)
+        } else {
+            let turbofish = turbofish.into_iter().chain(own_params);
+            quote!(#name::< #( #turbofish ),* >)
+        };
         let arguments = self.declaration.sig.inputs.iter().filter_map(|i| match i {
             syn::FnArg::Receiver(_) => None,
             syn::FnArg::Typed(pat) => match &*pat.pat {
@@ -191,11 +318,21 @@ impl ModifyFn {
                 _ => None,
             },
         });
-        self.modifiers.call(name, ctx, item, arguments)
+        self.modifiers.call(fab_path, name, ctx, item, arguments)
     }
-    fn ty_name(&self) -> Ident {
+    pub fn ty_name(&self) -> Ident {
         mk_tyname(&self.name)
     }
+    /// Names of the constructor arguments, in order.
+    pub fn constructor_inputs(&self) -> impl Iterator<Item = &Ident> + '_ {
+        let inputs = self.inputs.iter().filter(|i| self.modifiers.is_constructor_input(i));
+        inputs.filter_map(GetIdentExt::get_ident)
+    }
+    /// `EnumSet::EMPTY | Field::Foo | Field::Bar`, the fields read or written by this function.
+    pub fn field_set(&self, root: &Ident, field_ty_name: &Ident, mode: Mode) -> Tokens {
+        let fields = self.accessors.as_ref().unwrap().variant_idents(mode);
+        quote!(::#root::EnumSet::EMPTY #(| #field_ty_name::#fields)*)
+    }
     pub fn ty_variant(&self, root: &Ident, field_ty_name: &Ident) -> Tokens {
         let set_ty = quote!(::#root::EnumSet<#field_ty_name>);
         let ctor = self.constructor.as_ref();
@@ -250,10 +387,12 @@ impl ModifyFn {
     fn arm(&self, root: &Ident, field_ty_name: &Ident, mode: Mode) -> Tokens {
         let ty_name = self.ty_name();
         let ty = quote!(Self::#ty_name);
-        let fields = self.accessors.as_ref().unwrap().variant_idents(mode);
         match self.modifiers.dynamic_field(mode) {
             Some(dynamic) => quote! { #ty { #dynamic , .. } => *#dynamic },
-            None => quote! { #ty { .. } => ::#root::EnumSet::EMPTY #(| #field_ty_name::#fields)* },
+            None => {
+                let fields = self.field_set(root, field_ty_name, mode);
+                quote! { #ty { .. } => #fields }
+            }
         }
     }
     pub fn depends_arm(&self, root: &Ident, field_ty_name: &Ident) -> Tokens {
@@ -0,0 +1,146 @@
+//! Implement `cuicui_fab_parse::Parsable` for the modify enum, when the
+//! `parsable` `impl_modify` option is set.
+//!
+//! Each modify function is parsed from a format string modifier with the
+//! function's name in UpperCamelCase, the `#[parse(…)]` attribute controls
+//! how the modifier is named and how its value is parsed.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+
+use crate::{extensions::IntoSynErrorsExt, modifiers::Mode, modify_fn::ModifyFn};
+
+const PARSE_ATTR_DESCR: &str = "\
+- `name = \"Name\"`: name of the modifier in format strings, by default it is \
+  the function name in UpperCamelCase
+- `with = path::to::function`: parse the modifier value with \
+  `function(&str) -> Result<T, E>` instead of `FromStr`. If the modify function \
+  has several arguments, `T` is a tuple of all of them
+- `skip`: do not parse this modify function from format strings\n";
+
+/// The `#[parse(…)]` attribute of a modify function.
+#[derive(Default)]
+pub struct ParseAttr {
+    name: Option<syn::LitStr>,
+    with: Option<syn::Path>,
+    skip: bool,
+    /// Span of the `#[parse(…)]` attribute, `None` if the function has none.
+    pub span: Option<Span>,
+}
+impl ParseAttr {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        match () {
+            () if meta.path.is_ident("name") => {
+                let value = meta.value()?;
+                self.name = Some(value.parse()?);
+            }
+            () if meta.path.is_ident("with") => {
+                let value = meta.value()?;
+                self.with = Some(value.parse()?);
+            }
+            () if meta.path.is_ident("skip") => self.skip = true,
+            () => {
+                let msg = format!("Unrecognized parse meta attribute\n{PARSE_ATTR_DESCR}");
+                return Err(meta.error(msg));
+            }
+        }
+        Ok(())
+    }
+    /// Create self while removing `parse` attributes from `attrs`.
+    pub fn from_attrs(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut ret = Self::default();
+        let mut errs = Vec::new();
+
+        attrs.retain_mut(|attr| {
+            let is_parse = attr.meta.path().is_ident("parse");
+            if is_parse {
+                ret.span.get_or_insert(attr.pound_token.spans[0]);
+                if let Err(err) = attr.parse_nested_meta(|m| ret.parse(m)) {
+                    errs.push(err);
+                }
+            }
+            !is_parse
+        });
+        if let Some(err) = errs.into_syn_errors() {
+            return Err(err);
+        }
+        Ok(ret)
+    }
+}
+
+/// The `dependencies_of` and `parse` match arms for `function`.
+///
+/// Returns `None` if `function` can't be parsed, this is the case of
+/// `dynamic_read_write` functions and functions with `#[parse(skip)]`.
+fn arms(
+    function: &ModifyFn,
+    parse_path: &syn::Path,
+    root: &Ident,
+    field_ty: &Ident,
+) -> Option<(TokenStream, TokenStream)> {
+    let attr = &function.parse;
+    if attr.skip || function.constructor.is_none() {
+        return None;
+    }
+    let ty_name = function.ty_name();
+    let name = attr.name.clone();
+    let name = name.unwrap_or_else(|| syn::LitStr::new(&ty_name.to_string(), ty_name.span()));
+
+    let changes = function.field_set(root, field_ty, Mode::Write);
+    let depends = function.field_set(root, field_ty, Mode::Read);
+    let deps_arm = quote!(#name => #parse_path::Deps::Some { changes: #changes, depends: #depends });
+
+    let error = quote!(#parse_path::ParsableError);
+    let constructor = &function.name;
+    let inputs: Vec<_> = function.constructor_inputs().collect();
+    let parser = attr.with.as_ref().map_or_else(
+        || quote!(::std::str::FromStr::from_str),
+        |with| quote!(#with),
+    );
+    let value = quote!(#parser(input).map_err(|err| #error::bad_value(#name, input, err))?);
+    let parse_arm = match (inputs.as_slice(), &attr.with) {
+        ([], _) => quote!(#name => Ok(Self::#constructor())),
+        ([_], _) => quote!(#name => Ok(Self::#constructor(#value))),
+        (inputs, Some(_)) => quote!(#name => {
+            let ( #( #inputs ),* ) = #value;
+            Ok(Self::#constructor( #( #inputs ),* ))
+        }),
+        (_, None) => quote!(#name => Err(#error::NotParsable(#name))),
+    };
+    Some((deps_arm, parse_arm))
+}
+
+/// `impl Parsable for #modify_ty`.
+pub fn impl_parsable(
+    functions: &[ModifyFn],
+    parse_path: &syn::Path,
+    root: &Ident,
+    field_ty: &Ident,
+    modify_ty: &Ident,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let (impl_gens, ty_gens, where_clause) = generics.split_for_impl();
+    let arms = functions.iter().filter_map(|f| arms(f, parse_path, root, field_ty));
+    let (deps_arms, parse_arms): (Vec<_>, Vec<_>) = arms.unzip();
+
+    quote! {
+        impl #impl_gens #parse_path::Parsable for #modify_ty #ty_gens #where_clause {
+            type Err = #parse_path::ParsableError;
+
+            fn dependencies_of(name: &str) -> #parse_path::Deps<Self::Field> {
+                match name {
+                    #( #deps_arms, )*
+                    _ => #parse_path::Deps::NoneWithName,
+                }
+            }
+            #[allow(unused_variables)]
+            fn parse(name: &str, input: &str) -> ::std::result::Result<Self, Self::Err> {
+                match name {
+                    #( #parse_arms, )*
+                    _ => Err(#parse_path::ParsableError::Unknown(name.into())),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Compile-time tests for `impl_modify` and `MakeItem`.
+//!
+//! - `ui/pass`: must compile, their `main` function is then ran.
+//! - `ui/fail`: must fail to compile with the error in their `.stderr` file.
+//!
+//! Run `TRYBUILD=overwrite cargo test -p cuicui_fab_derive` to update the
+//! `.stderr` files after deliberately changing an error message.
+
+#[test]
+fn ui() {
+    let tests = trybuild::TestCases::new();
+    tests.pass("tests/ui/pass/*.rs");
+    tests.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,21 @@
+use cuicui_fab::impl_modify;
+
+pub struct Tinted<T> {
+    opacity: f32,
+    inner: T,
+}
+
+#[impl_modify]
+impl<T: Send + Sync + 'static> Modify for ModifyTinted<u32> {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Tinted<T>;
+    type MakeItem = Tinted<T>;
+    type Items<'a, 'b, 'c> = Vec<Tinted<T>>;
+
+    #[modify(write(.opacity))]
+    fn opacity(set_opacity: f32) -> f32 {
+        set_opacity
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use cuicui_fab::MakeItem;
+
+#[derive(Clone, MakeItem)]
+#[make_item(item = SpriteItem)]
+pub enum Sprite {
+    Scaled(f32),
+    Labeled(String),
+}
+
+fn main() {}
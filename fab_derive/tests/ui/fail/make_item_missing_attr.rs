@@ -0,0 +1,9 @@
+use cuicui_fab::MakeItem;
+
+#[derive(Clone, MakeItem)]
+pub struct Sprite {
+    scale: f32,
+    label: String,
+}
+
+fn main() {}
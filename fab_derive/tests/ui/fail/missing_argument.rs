@@ -0,0 +1,22 @@
+use cuicui_fab::impl_modify;
+
+pub struct Section {
+    content: String,
+    size: f32,
+    lines: Vec<String>,
+}
+
+#[impl_modify]
+impl Modify for ModifySection {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(read(.size), write(.content))]
+    fn describe(factor: f32) -> String {
+        factor.to_string()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use cuicui_fab::impl_modify;
+
+pub struct Section {
+    size: f32,
+}
+
+#[impl_modify]
+impl Modify for ModifySection {
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(write(.size))]
+    fn size(set_size: f32) -> f32 {
+        set_size
+    }
+}
+
+fn main() {}
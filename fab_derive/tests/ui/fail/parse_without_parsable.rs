@@ -0,0 +1,23 @@
+use cuicui_fab::impl_modify;
+
+pub struct Section {
+    content: String,
+    size: f32,
+    lines: Vec<String>,
+}
+
+#[impl_modify]
+impl Modify for ModifySection {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(write(.size))]
+    #[parse(name = "Size")]
+    fn size(set_size: f32) -> f32 {
+        set_size
+    }
+}
+
+fn main() {}
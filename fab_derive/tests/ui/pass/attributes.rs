@@ -0,0 +1,120 @@
+//! Every `#[modify(…)]` attribute, and which fields they read and write.
+use cuicui_fab::{impl_modify, modify::Changed, modify::Modify};
+
+pub struct Context {
+    base_size: f32,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    color: u32,
+    font_size: f32,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    content: String,
+    style: Style,
+    offset: (i32, i32),
+}
+
+#[impl_modify]
+impl Modify for ModifySection {
+    type Context<'a> = Context;
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(write(.style.color))]
+    pub fn color(set_color: u32) -> u32 {
+        set_color
+    }
+
+    #[modify(write_neq(.style.color))]
+    pub fn color_neq(set_color: u32) -> u32 {
+        set_color
+    }
+
+    // The function and its parameter can have the same name.
+    #[modify(write_neq(offset = .offset.0))]
+    pub fn offset(offset: i32) -> i32 {
+        offset
+    }
+
+    #[modify(read(.style.color), write_mut(.content))]
+    pub fn color_name(color: &u32, content: &mut String) {
+        *content = format!("#{color:06x}");
+    }
+
+    #[modify(read_write(size = .style.font_size))]
+    pub fn rel_size(relative: f32, size: &mut f32) {
+        *size *= relative;
+    }
+
+    #[modify(context(.base_size), write(.style.font_size))]
+    pub fn base_size(base_size: &f32) -> f32 {
+        *base_size
+    }
+
+    #[modify(context(ctx), write(.style.font_size))]
+    pub fn double_base_size(ctx: &Context) -> f32 {
+        ctx.base_size * 2.0
+    }
+
+    #[modify(dynamic_read_write(depends, changes, item), context(ctx))]
+    pub fn dynamic(add: u32, ctx: &Context, item: &mut Section) {
+        item.style.color += add;
+        item.style.font_size = ctx.base_size;
+    }
+}
+
+fn main() {
+    use ModifySectionField::{ContextBaseSize, ItemContent, ItemStyleColor, ItemStyleFontSize};
+
+    let ctx = Context { base_size: 10.0 };
+    let mut section = Section::default();
+
+    assert_eq!(ModifySection::color_changes(), ItemStyleColor);
+    assert_eq!(ModifySection::color_name_depends(), ItemStyleColor);
+    assert_eq!(ModifySection::color_name_changes(), ItemContent);
+    assert_eq!(ModifySection::rel_size_depends(), ItemStyleFontSize);
+    assert_eq!(ModifySection::rel_size_changes(), ItemStyleFontSize);
+    assert_eq!(ModifySection::base_size_depends(), ContextBaseSize);
+    assert_eq!(ModifySection::base_size_changes(), ItemStyleFontSize);
+
+    // `context(ctx)` reads the whole context, including `.base_size`.
+    assert_eq!(ModifySection::double_base_size_depends(), ContextBaseSize);
+
+    let base_size = ModifySection::base_size();
+    assert_eq!(base_size.depends(), ContextBaseSize);
+    base_size.apply(&ctx, &mut section).unwrap();
+    ModifySection::rel_size(1.5).apply(&ctx, &mut section).unwrap();
+    ModifySection::color(0xff00ff).apply(&ctx, &mut section).unwrap();
+    ModifySection::color_name().apply(&ctx, &mut section).unwrap();
+
+    // `write_neq` doesn't change anything if the value is already set
+    assert_eq!(ModifySection::color_neq_changes(), ItemStyleColor);
+    let color_neq = ModifySection::color_neq(0xff00ff);
+    assert_eq!(color_neq.apply(&ctx, &mut section).unwrap(), Changed::No);
+    let color_neq = ModifySection::color_neq(0x00ff00);
+    assert_eq!(color_neq.apply(&ctx, &mut section).unwrap(), Changed::Yes);
+    assert_eq!(section.style.color, 0x00ff00);
+    ModifySection::color(0xff00ff).apply(&ctx, &mut section).unwrap();
+
+    let style = Style { color: 0xff00ff, font_size: 15.0 };
+    let content = "#ff00ff".to_owned();
+    assert_eq!(section, Section { content, style, offset: (0, 0) });
+
+    let offset = ModifySection::offset(3);
+    assert_eq!(offset.apply(&ctx, &mut section).unwrap(), Changed::Yes);
+    assert_eq!(offset.apply(&ctx, &mut section).unwrap(), Changed::No);
+    assert_eq!(section.offset, (3, 0));
+
+    ModifySection::double_base_size().apply(&ctx, &mut section).unwrap();
+    assert_eq!(section.style.font_size, 20.0);
+
+    let changes = ItemStyleColor | ItemStyleFontSize;
+    let dynamic = ModifySection::Dynamic { add: 1, depends: ContextBaseSize.into(), changes };
+    assert_eq!(dynamic.depends(), ContextBaseSize);
+    assert_eq!(dynamic.changes(), changes);
+    dynamic.apply(&ctx, &mut section).unwrap();
+    assert_eq!(section.style, Style { color: 0xff0100, font_size: 10.0 });
+}
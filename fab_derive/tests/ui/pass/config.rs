@@ -0,0 +1,84 @@
+//! `impl_modify` configuration options.
+extern crate cuicui_fab as fab;
+extern crate enumset as renamed_enumset;
+
+use std::fmt;
+
+use fab::{impl_modify, modify::Modify};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    content: String,
+    size: f32,
+}
+
+mod private {
+    use super::*;
+
+    /// `pub(crate)` enums, `Debug` is implemented by hand.
+    #[impl_modify(
+        cuicui_fab_path = fab,
+        enumset_crate = renamed_enumset,
+        visibility = pub(crate),
+        no_derive(Debug),
+    )]
+    impl Modify for ModifySection {
+        type Context<'a> = ();
+        type Item<'a> = &'a mut Section;
+        type MakeItem = Section;
+        type Items<'a, 'b, 'c> = Vec<Section>;
+
+        #[modify(write(.size))]
+        pub fn size(set_size: f32) -> f32 {
+            set_size
+        }
+    }
+    impl fmt::Debug for ModifySection {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("ModifySection")
+        }
+    }
+
+    /// Neither `Debug` nor `Clone` are derived.
+    #[impl_modify(cuicui_fab_path = fab, no_derive(Debug, Clone))]
+    impl Modify for NoDerive {
+        type Context<'a> = ();
+        type Item<'a> = &'a mut Section;
+        type MakeItem = Section;
+        type Items<'a, 'b, 'c> = Vec<Section>;
+
+        #[modify(write_mut(.content))]
+        pub fn content(set_content: &String, content: &mut String) {
+            content.clone_from(set_content);
+        }
+    }
+    impl fmt::Debug for NoDerive {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("NoDerive")
+        }
+    }
+    impl Clone for NoDerive {
+        fn clone(&self) -> Self {
+            match self {
+                NoDerive::Content { set_content } => NoDerive::content(set_content.clone()),
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut section = Section::default();
+
+    let size = private::ModifySection::size(12.0);
+    assert_eq!(format!("{size:?}"), "ModifySection");
+    size.clone().apply(&(), &mut section).unwrap();
+    assert_eq!(section.size, 12.0);
+
+    let field: renamed_enumset::EnumSet<private::ModifySectionField> = size.changes();
+    assert_eq!(field, private::ModifySectionField::ItemSize);
+
+    let content = private::NoDerive::content("Hello".to_owned());
+    assert_eq!(format!("{content:?}"), "NoDerive");
+    content.clone().apply(&(), &mut section).unwrap();
+    assert_eq!(section.content, "Hello");
+}
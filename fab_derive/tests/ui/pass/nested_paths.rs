@@ -0,0 +1,89 @@
+//! Field paths are split into their most precise accessors, so that a
+//! modifier writing to `.style` is known to change `.style.color`.
+use cuicui_fab::{impl_modify, modify::Modify};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    color: u32,
+    font_size: f32,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    content: String,
+    style: Style,
+    lines: Vec<String>,
+    tuple: (u32, (f32, f32)),
+}
+
+#[impl_modify]
+impl Modify for ModifySection {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    #[modify(write(.style))]
+    pub fn style(set_style: &Style) -> Style {
+        set_style.clone()
+    }
+
+    #[modify(read(.style.color), write(.content))]
+    pub fn describe(color: &u32) -> String {
+        format!("#{color:06x}")
+    }
+
+    #[modify(write(.style.font_size))]
+    pub fn font_size(size: f32) -> f32 {
+        size
+    }
+
+    #[modify(write(.lines))]
+    pub fn lines(set_lines: &Vec<String>) -> Vec<String> {
+        set_lines.clone()
+    }
+
+    #[modify(read(first = .lines[0]), write(.content))]
+    pub fn first_line(first: &String) -> String {
+        first.clone()
+    }
+
+    #[modify(write(.tuple))]
+    pub fn tuple(set_tuple: &(u32, (f32, f32))) -> (u32, (f32, f32)) {
+        *set_tuple
+    }
+
+    #[modify(read(x = .tuple.1.0), write(.style.font_size))]
+    pub fn tuple_size(x: &f32) -> f32 {
+        *x
+    }
+}
+
+fn main() {
+    use ModifySectionField as Field;
+
+    // `.style` is split into `.style.color` and `.style.font_size`
+    let style = Field::ItemStyleColor | Field::ItemStyleFontSize;
+    assert_eq!(ModifySection::style_changes(), style);
+    assert_eq!(ModifySection::describe_depends(), Field::ItemStyleColor);
+    assert!(ModifySection::style_changes().is_superset(ModifySection::describe_depends()));
+    assert!(ModifySection::font_size_changes().is_disjoint(ModifySection::describe_depends()));
+
+    // `.lines` is split into `.lines[0]`
+    assert_eq!(ModifySection::lines_changes(), Field::ItemLinesAt0);
+    assert_eq!(ModifySection::first_line_depends(), Field::ItemLinesAt0);
+
+    // `.tuple` is split into `.tuple.1.0`
+    assert_eq!(ModifySection::tuple_changes(), Field::ItemTuple10);
+    assert_eq!(ModifySection::tuple_size_depends(), Field::ItemTuple10);
+
+    let mut section = Section::default();
+    let set_style = Style { color: 0x00ff00, font_size: 12.0 };
+    ModifySection::style(set_style.clone()).apply(&(), &mut section).unwrap();
+    ModifySection::describe().apply(&(), &mut section).unwrap();
+    assert_eq!(section.style, set_style);
+    assert_eq!(section.content, "#00ff00");
+
+    ModifySection::tuple((1, (3.0, 4.0))).apply(&(), &mut section).unwrap();
+    ModifySection::tuple_size().apply(&(), &mut section).unwrap();
+    assert_eq!(section.style.font_size, 3.0);
+}
@@ -0,0 +1,69 @@
+//! Measure the time it takes to parse format strings.
+//!
+//! Run with `cargo bench -p cuicui_fab_parse`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use cuicui_fab_parse::format_string;
+
+/// Format strings taken from the `cuicui_richtext` examples.
+const REALISTIC: &[(&str, &str)] = &[
+    ("plain", "This is \n a test"),
+    (
+        "fps",
+        "FPS: {Font:fonts/FiraMono-Medium.ttf, Color:gold, Content:{Res(Fps).fps:.1}}",
+    ),
+    (
+        "fancy",
+        "{Color:{color}|{Rainbow:20.0|Bonjour} {greeted}!\n\
+        {Color:Yellow, Sine:80|We are having fun here, woopy!}}",
+    ),
+    (
+        "reactors",
+        "Top button: {Marked(TopButton).Interaction:show_top}\n\
+        {Color: {Marked(BottomButton).Interaction:show_bottom}|Bottom Button state}",
+    ),
+    (
+        "breakout_score",
+        "Score: {Font: fonts/FiraMono-Medium.ttf, Color: rgb(1.0, 0.5, 0.5), \
+        RelSize: 1.5, Content: {Res(Score).score:}}\n\
+        {Color: rgb(1.0, 0.2, 0.2), Content: {Res(Deaths):?}}\n\
+        Paddle hits: {Color: pink, Content: {Marked(Paddle).Collider.collision_count:03}}\n\
+        Ball position: {Font: fonts/FiraMono-Medium.ttf, Color: pink|\
+        \\{x: {ball_x}, y: {ball_y}\\}}",
+    ),
+];
+
+fn realistic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format_string");
+    for &(name, input) in REALISTIC {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("realistic", name), input, |b, input| {
+            b.iter(|| format_string(black_box(input)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// A format string with `count` sibling sections, each `depth` levels deep.
+fn nested_sections(count: usize, depth: usize) -> String {
+    let open = "{Color:red, RelSize: 1.1|text ".repeat(depth);
+    let close = "}".repeat(depth);
+    let section = format!("{open}{{binding}}{close} and ");
+    section.repeat(count)
+}
+
+fn scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format_string");
+    for (count, depth) in [(1, 1), (10, 1), (100, 1), (10, 4), (10, 16)] {
+        let input = nested_sections(count, depth);
+        let id = BenchmarkId::new("sections", format!("{count}x{depth}"));
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(id, input.as_str(), |b, input| {
+            b.iter(|| format_string(black_box(input)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, realistic, scaling);
+criterion_main!(benches);
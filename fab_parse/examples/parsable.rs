@@ -0,0 +1,109 @@
+use std::{borrow::Cow, convert::Infallible, num::ParseIntError};
+
+use cuicui_fab_parse::{Deps, Parsable, ParsableError};
+use fab::{impl_modify, modify::Modify};
+use pretty_assertions::assert_eq;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    color: u32,
+    font_size: f32,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    content: String,
+    style: Style,
+}
+
+fn parse_cow(input: &str) -> Result<Cow<'static, str>, Infallible> {
+    Ok(input.to_owned().into())
+}
+fn parse_hex(input: &str) -> Result<u32, ParseIntError> {
+    u32::from_str_radix(input.trim_start_matches('#'), 16)
+}
+fn parse_rgb(input: &str) -> Result<(u8, u8, u8), ParseIntError> {
+    let mut channels = input.split(',').map(|c| c.trim().parse());
+    let mut next = || channels.next().unwrap_or(Ok(0));
+    Ok((next()?, next()?, next()?))
+}
+
+/// Operations on [`Section`], parsed from format strings.
+#[impl_modify(cuicui_fab_path = fab, parsable)]
+#[derive(PartialEq)]
+impl Modify for ModifySection {
+    type Context<'a> = ();
+    type Item<'a> = &'a mut Section;
+    type MakeItem = Section;
+    type Items<'a, 'b, 'c> = Vec<Section>;
+
+    /// Named `FontSize`, parsed with `f32::from_str`.
+    #[modify(write(.style.font_size))]
+    pub fn font_size(size: f32) -> f32 {
+        size
+    }
+    #[modify(read_write(.style.font_size))]
+    #[parse(name = "Size^")]
+    pub fn rel_size(relative: f32, font_size: &mut f32) {
+        *font_size *= relative;
+    }
+    #[modify(write(.style.color))]
+    #[parse(with = parse_hex)]
+    pub fn color(set_color: u32) -> u32 {
+        set_color
+    }
+    /// Several arguments, parsed as a tuple.
+    #[modify(write(.style.color))]
+    #[parse(with = parse_rgb)]
+    pub fn rgb(r: u8, g: u8, b: u8) -> u32 {
+        u32::from_be_bytes([0, r, g, b])
+    }
+    /// Several arguments without `with`, only usable with bindings.
+    #[modify(write(.style.color))]
+    pub fn gray(level: u8, alpha: u8) -> u32 {
+        u32::from_be_bytes([alpha, level, level, level])
+    }
+    #[modify(write_mut(.content))]
+    #[parse(with = parse_cow)]
+    pub fn content(statik: &Cow<'static, str>, content: &mut String) {
+        content.clear();
+        content.push_str(statik);
+    }
+    #[modify(write_mut(.content))]
+    #[parse(skip)]
+    pub fn clear(content: &mut String) {
+        content.clear();
+    }
+}
+
+fn main() {
+    let mut section = Section::default();
+    section.style.font_size = 10.0;
+    let parsed = [
+        ("Size^", "1.5"),
+        ("Color", "#ff0000"),
+        ("Content", "Hello world"),
+    ];
+    for (name, value) in parsed {
+        let modify = ModifySection::parse(name, value).unwrap();
+        modify.apply(&(), &mut section).unwrap();
+    }
+    let style = Style { color: 0xff0000, font_size: 15.0 };
+    assert_eq!(section, Section { content: "Hello world".to_owned(), style });
+
+    let rgb = ModifySection::parse("Rgb", "0, 128, 255").unwrap();
+    assert_eq!(rgb, ModifySection::rgb(0, 128, 255));
+
+    let parse_err = |name, value| ModifySection::parse(name, value).unwrap_err();
+    let bad_value = parse_err("FontSize", "big");
+    assert!(matches!(bad_value, ParsableError::BadValue { name: "FontSize", .. }));
+    assert!(matches!(parse_err("Gray", "128"), ParsableError::NotParsable("Gray")));
+    assert!(matches!(parse_err("Clear", ""), ParsableError::Unknown(_)));
+    assert!(matches!(parse_err("RelSize", "1.5"), ParsableError::Unknown(_)));
+
+    let Deps::Some { changes, depends } = ModifySection::dependencies_of("Size^") else {
+        panic!("Size^ is a modifier");
+    };
+    assert_eq!(changes, ModifySection::rel_size_changes());
+    assert_eq!(depends, ModifySection::rel_size_depends());
+    assert!(matches!(ModifySection::dependencies_of("Clear"), Deps::NoneWithName));
+}
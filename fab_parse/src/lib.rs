@@ -12,10 +12,13 @@ mod tests;
 
 mod error;
 pub mod hook;
+pub mod markup;
 mod post_process;
 pub mod rt_fmt;
 pub mod tree;
 
+use std::borrow::Cow;
+
 use winnow::{
     ascii::{alpha1, alphanumeric1, digit1, escaped, multispace0},
     branch::alt,
@@ -34,7 +37,8 @@ use tree::{
 };
 
 pub use hook::Hook;
-pub use post_process::{Deps, Parsable, Split, StringPair, Styleable};
+pub use markup::{Syntax, Tags};
+pub use post_process::{Deps, Parsable, ParsableError, Split, StringPair, Styleable};
 pub use rt_fmt::RuntimeFormat;
 pub use tree::Tree;
 
@@ -180,6 +184,32 @@ fn sections(input: &str) -> IResult<Sections> {
         .map(Sections::full_subsection)
         .parse_next(input)
 }
+/// Parse a binding source such as `Res(Settings).volume` on its own, outside
+/// of a format string.
+pub fn source(input: &str) -> anyhow::Result<Source> {
+    match path.parse(input).map_err(|e| e.into_owned())? {
+        Path::Tracked(source) => Ok(source),
+        Path::Binding(name) => Err(anyhow::anyhow!("'{name}' is a binding name, not a source")),
+    }
+}
+/// Parse a list of modifiers such as `Font: bold.ttf, FontSize: 32` on its
+/// own, outside of a format string.
+///
+/// Values are trimmed and unescaped, they can't be bindings.
+pub fn modifiers(input: &str) -> anyhow::Result<Vec<(&str, Cow<str>)>> {
+    let list: Vec<Modifier> = ws(separated1(closed_element, ws(',')))
+        .parse(input)
+        .map_err(|e| e.into_owned())?;
+    let unescape = |Modifier { name, value, .. }| match value {
+        Dyn::Static(value) => {
+            let mut value = value.trim().into();
+            post_process::escape_backslashes(&mut value);
+            Ok((name, value))
+        }
+        Dyn::Dynamic(_) => Err(anyhow::anyhow!("'{name}' is bound, it needs a value")),
+    };
+    list.into_iter().map(unescape).collect()
+}
 pub fn format_string(input: &str) -> anyhow::Result<Tree> {
     let sections = sections.parse(input).map_err(|e| e.into_owned())?;
     Ok(Tree { sections: sections.0 })
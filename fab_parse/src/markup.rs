@@ -0,0 +1,363 @@
+//! Alternative front-ends to the format string syntax, for text written with
+//! a subset of markdown or BBCode.
+//!
+//! Both produce the same [`Tree`] as [`format_string`], so styles, bindings
+//! and the resolver work the same whatever the syntax. Markup tags become
+//! modifiers following a [`Tags`] mapping:
+//!
+//! - BBCode's `[color=red]text[/color]` becomes `{Color: red|text}` when the
+//!   `color` tag is mapped to the `Color` modifier.
+//! - Markdown's `**text**`, `*text*` and `~~text~~` use the `b`, `i` and `s`
+//!   tags, the same as BBCode's `[b]`, `[i]` and `[s]`.
+//!
+//! In both syntaxes, `{binding}` is a binding, like in format strings, and
+//! `\` escapes the next character. Values of BBCode tags can also be bindings,
+//! as in `[color={team_color}]`. A `{` that doesn't start a binding and tags
+//! that are not in the [`Tags`] are kept as text.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+use winnow::{combinator::delimited, Parser};
+
+use crate::tree::{Binding, Dyn, Modifier, Section, Tree};
+use crate::{binding, format_string};
+
+/// The markup language of a format string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Syntax {
+    /// The `{Modifier: value|text}` syntax of [`format_string`].
+    #[default]
+    FormatString,
+    /// `**bold**`, `*italic*` and `~~strike~~`, see [`markdown`].
+    Markdown,
+    /// `[tag=value]text[/tag]`, see [`bbcode`].
+    BBCode,
+}
+impl Syntax {
+    /// Parse `input` written in this syntax.
+    pub fn parse<'a>(self, input: &'a str, tags: &'a Tags) -> anyhow::Result<Tree<'a>> {
+        match self {
+            Syntax::FormatString => format_string(input),
+            Syntax::Markdown => Ok(markdown(input, tags)?),
+            Syntax::BBCode => Ok(bbcode(input, tags)?),
+        }
+    }
+}
+
+/// The modifier a markup tag stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// Name of the modifier, such as `Color`.
+    pub modifier: String,
+    /// Value of the modifier when the tag has none, such as `[b]`.
+    pub value: Option<String>,
+}
+
+/// Mapping from markup tags to modifiers.
+///
+/// ```
+/// # use cuicui_fab_parse::Tags;
+/// let mut tags = Tags::default();
+/// tags.insert("color", "Color")
+///     .insert_with_value("b", "Font", "fonts/FiraSans-Bold.ttf");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Tags(HashMap<String, Tag>);
+impl Tags {
+    /// `[tag=value]` becomes `{modifier: value}`.
+    pub fn insert(&mut self, tag: impl Into<String>, modifier: impl Into<String>) -> &mut Self {
+        let modifier = Tag { modifier: modifier.into(), value: None };
+        self.0.insert(tag.into(), modifier);
+        self
+    }
+    /// `[tag]` becomes `{modifier: value}`, `[tag=other]` still becomes
+    /// `{modifier: other}`.
+    pub fn insert_with_value(
+        &mut self,
+        tag: impl Into<String>,
+        modifier: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        let modifier = Tag {
+            modifier: modifier.into(),
+            value: Some(value.into()),
+        };
+        self.0.insert(tag.into(), modifier);
+        self
+    }
+    pub fn get(&self, tag: &str) -> Option<&Tag> {
+        self.0.get(tag)
+    }
+}
+
+/// Error returned by [`markdown`] and [`bbcode`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("The `{0}` tag needs a value, such as `[{0}=value]`")]
+    MissingValue(Box<str>),
+
+    #[error("`{0}` was closed, but was never opened")]
+    NotOpened(Box<str>),
+
+    #[error("`{0}` was closed while `{1}` is still open")]
+    Mismatched(Box<str>, Box<str>),
+
+    #[error("`{0}` is never closed")]
+    NotClosed(Box<str>),
+
+    #[error("A `[` at byte {0} has no matching `]`")]
+    UnterminatedTag(usize),
+}
+
+/// A tag waiting to be closed.
+struct Open<'a> {
+    tag: &'a str,
+    /// The tag as written in the input, such as `**` or `[b]`.
+    source: &'a str,
+    modifier: Modifier<'a>,
+    /// Index of the first section within the tag.
+    first_section: usize,
+}
+
+/// Accumulates the sections of a markup text, in the same order as
+/// [`format_string`] would.
+struct Builder<'a> {
+    input: &'a str,
+    sections: Vec<Section<'a>>,
+    open: Vec<Open<'a>>,
+}
+impl<'a> Builder<'a> {
+    fn new(input: &'a str) -> Self {
+        Builder { input, sections: Vec::new(), open: Vec::new() }
+    }
+    fn text(&mut self, text: &'a str) {
+        self.sections.extend(Section::free(text));
+    }
+    /// The binding at byte `at` and where it ends, `None` if `at` is not the
+    /// start of a binding.
+    fn binding(&self, at: usize) -> Option<(Binding<'a>, usize)> {
+        let binding = delimited('{', binding, '}').parse_next(&self.input[at..]);
+        let (rest, binding) = binding.ok()?;
+        Some((binding, self.input.len() - rest.len()))
+    }
+    /// Add the text from `text_start` to the binding at byte `at`, and the
+    /// binding, returns where it ends.
+    ///
+    /// Returns `None` and adds nothing if `at` is not the start of a binding.
+    fn text_and_binding(&mut self, text_start: usize, at: usize) -> Option<usize> {
+        let (binding, end) = self.binding(at)?;
+        self.text(&self.input[text_start..at]);
+        self.sections.extend(Section::format(binding));
+        Some(end)
+    }
+    /// Parse the value of a tag, a binding or plain text, without quotes.
+    fn value(&self, value: &'a str) -> Option<Dyn<'a>> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        let binding = delimited('{', binding, '}').parse(value);
+        if let Ok(binding) = binding {
+            return Some(Dyn::Dynamic(binding));
+        }
+        let unquote = |q: char| value.strip_prefix(q).and_then(|v| v.strip_suffix(q));
+        Some(Dyn::Static(
+            unquote('"').or_else(|| unquote('\'')).unwrap_or(value),
+        ))
+    }
+    /// Open `tag`, mapped to `mapped` and written `source` in the input.
+    fn open(
+        &mut self,
+        tag: &'a str,
+        mapped: &'a Tag,
+        source: &'a str,
+        value: Option<Dyn<'a>>,
+    ) -> Result<(), Error> {
+        let Tag { modifier, value: default } = mapped;
+        let default = default.as_deref().map(Dyn::Static);
+        let value = value
+            .or(default)
+            .ok_or_else(|| Error::MissingValue(tag.into()))?;
+        let modifier = Modifier::new((modifier.as_str(), value));
+        let first_section = self.sections.len();
+        let open = Open { tag, source, modifier, first_section };
+        self.open.push(open);
+        Ok(())
+    }
+    fn close(&mut self, tag: &'a str) -> Result<(), Error> {
+        let Some(open) = self.open.pop() else {
+            return Err(Error::NotOpened(tag.into()));
+        };
+        if open.tag != tag {
+            return Err(Error::Mismatched(tag.into(), open.tag.into()));
+        }
+        // Like `{Icon: fire}`, tags without text still apply to a section.
+        if self.sections.len() == open.first_section {
+            self.sections.push(Section { modifiers: Vec::new() });
+        }
+        let subsection_count = self.sections.len() - open.first_section;
+        let modifier = Modifier { subsection_count, ..open.modifier };
+        self.sections[open.first_section].modifiers.push(modifier);
+        Ok(())
+    }
+    /// Close `tag` if it is the last opened tag, open it otherwise.
+    fn toggle(&mut self, tag: &'a str, mapped: &'a Tag, source: &'a str) -> Result<(), Error> {
+        match self.open.last() {
+            Some(open) if open.tag == tag => self.close(tag),
+            _ => self.open(tag, mapped, source, None),
+        }
+    }
+    fn finish(self) -> Result<Tree<'a>, Error> {
+        if let Some(open) = self.open.last() {
+            return Err(Error::NotClosed(open.tag.into()));
+        }
+        Ok(Tree { sections: self.sections })
+    }
+    /// Like [`Builder::finish`], but tags that are still open are kept as text
+    /// rather than failing.
+    fn finish_unclosed_as_text(mut self) -> Tree<'a> {
+        // Tags still open were never closed, so no closed tag contains where
+        // they start, and inserting a section there keeps modifiers valid.
+        while let Some(open) = self.open.pop() {
+            if let Some(text) = Section::free(open.source) {
+                self.sections.insert(open.first_section, text);
+            }
+        }
+        Tree { sections: self.sections }
+    }
+}
+
+/// Parse a text using BBCode tags, such as `[color=red]text[/color]`.
+///
+/// Tags become the modifiers they are mapped to in `tags`. Tags are not
+/// self-closing: `[icon=fire][/icon]` displays the `fire` icon.
+///
+/// Tags not in `tags`, such as `[sic]` or `[1]`, are kept as text.
+pub fn bbcode<'a>(input: &'a str, tags: &'a Tags) -> Result<Tree<'a>, Error> {
+    let mut builder = Builder::new(input);
+    let mut text_start = 0;
+    let mut i = 0;
+    // All special characters are ASCII, so they are never part of a multi-byte
+    // character, and `i` is always a character boundary when slicing.
+    while let Some(&byte) = input.as_bytes().get(i) {
+        match byte {
+            b'\\' => i += 2,
+            b'[' => {
+                let end = input[i..].find(']').ok_or(Error::UnterminatedTag(i))?;
+                let source = &input[i..i + end + 1];
+                let tag = &source[1..end];
+                let closed = tag.strip_prefix('/');
+                let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+                let name = closed.unwrap_or(name).trim();
+                let Some(mapped) = tags.get(name) else {
+                    i += end + 1;
+                    continue;
+                };
+                builder.text(&input[text_start..i]);
+                if closed.is_some() {
+                    builder.close(name)?;
+                } else {
+                    let value = builder.value(value);
+                    builder.open(name, mapped, source, value)?;
+                }
+                i += end + 1;
+                text_start = i;
+            }
+            b'{' => match builder.text_and_binding(text_start, i) {
+                Some(end) => (i, text_start) = (end, end),
+                None => i += 1,
+            },
+            _ => i += 1,
+        }
+    }
+    builder.text(&input[text_start..]);
+    builder.finish()
+}
+
+/// Whether the delimiter of `len` bytes at `i` can respectively open and close
+/// emphasis.
+///
+/// Like CommonMark, a delimiter followed by whitespace can't open emphasis,
+/// and a delimiter following whitespace can't close it.
+fn flanking(input: &str, i: usize, len: usize) -> (bool, bool) {
+    let is_text = |c: Option<char>| c.is_some_and(|c| !c.is_whitespace());
+    let opens = is_text(input[i + len..].chars().next());
+    let closes = is_text(input[..i].chars().next_back());
+    (opens, closes)
+}
+/// Whether `input` contains `delimiter` in a position where it closes
+/// emphasis.
+fn has_closer(input: &str, delimiter: &str) -> bool {
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while let Some(&byte) = bytes.get(i) {
+        if byte == b'\\' {
+            i += 2;
+            continue;
+        }
+        // `*` must not match the first character of `**`.
+        let run = bytes[i..].iter().take_while(|&&b| b == byte).count();
+        let is_delimiter = bytes[i..].starts_with(delimiter.as_bytes());
+        if is_delimiter && run == delimiter.len() && flanking(input, i, run).1 {
+            return true;
+        }
+        i += run;
+    }
+    false
+}
+
+/// Parse a text using a subset of markdown: `**bold**`, `*italic*` and
+/// `~~strike~~`.
+///
+/// They use respectively the `b`, `i` and `s` tags of `tags`.
+///
+/// Delimiters that can't open or close emphasis are kept as text, such as in
+/// `2 * 3`, `* item` or a `*` that is never closed, also when it is never
+/// closed because of the nesting of other delimiters, as in `*a **b* c**`.
+pub fn markdown<'a>(input: &'a str, tags: &'a Tags) -> Result<Tree<'a>, Error> {
+    let mut builder = Builder::new(input);
+    let mut text_start = 0;
+    let mut i = 0;
+    while let Some(&byte) = input.as_bytes().get(i) {
+        let rest = &input.as_bytes()[i..];
+        let (tag, delimiter) = match byte {
+            b'\\' => {
+                i += 2;
+                continue;
+            }
+            b'{' => {
+                match builder.text_and_binding(text_start, i) {
+                    Some(end) => (i, text_start) = (end, end),
+                    None => i += 1,
+                }
+                continue;
+            }
+            b'*' if rest.starts_with(b"**") => ("b", "**"),
+            b'*' => ("i", "*"),
+            b'~' if rest.starts_with(b"~~") => ("s", "~~"),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let len = delimiter.len();
+        let (opens, closes) = flanking(input, i, len);
+        let is_open = builder.open.last().is_some_and(|open| open.tag == tag);
+        let toggles = if is_open {
+            closes
+        } else {
+            opens && has_closer(&input[i + len..], delimiter)
+        };
+        let Some(mapped) = tags.get(tag).filter(|_| toggles) else {
+            i += len;
+            continue;
+        };
+        builder.text(&input[text_start..i]);
+        builder.toggle(tag, mapped, &input[i..i + len])?;
+        i += len;
+        text_start = i;
+    }
+    builder.text(&input[text_start..]);
+    Ok(builder.finish_unclosed_as_text())
+}
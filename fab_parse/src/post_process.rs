@@ -6,6 +6,7 @@ use bevy_math::cubic_splines::CubicCurve;
 use enumset::{EnumSet, EnumSetType};
 use fab::{binding, modify::Modify, resolve::MakeModify, resolve::ModifyKind};
 use log::warn;
+use thiserror::Error;
 
 use crate::tree::{self, get_content, get_content_mut, is_content, Dyn};
 use crate::Hook;
@@ -51,12 +52,43 @@ pub enum Deps<F: EnumSetType> {
 }
 
 /// A [`fab::Modify`] that can be read from a format string.
+///
+/// `#[impl_modify(parsable)]` implements this trait, with [`ParsableError`]
+/// as error.
 pub trait Parsable: Modify {
     type Err: Into<anyhow::Error> + Send + Sync;
 
     fn dependencies_of(name: &str) -> Deps<Self::Field>;
     fn parse(name: &str, value: &str) -> Result<Self, Self::Err>;
 }
+
+/// Error returned by [`Parsable::parse`] when implemented by `impl_modify`.
+#[derive(Debug, Error)]
+pub enum ParsableError {
+    #[error("`{0}` is not a parseable modifier")]
+    Unknown(Box<str>),
+
+    #[error("`{0}` modifier can't be parsed from a string, use a binding instead")]
+    NotParsable(&'static str),
+
+    #[error("Failed to parse `{value}` as a `{name}` modifier: {source}")]
+    BadValue {
+        name: &'static str,
+        value: Box<str>,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+impl ParsableError {
+    /// The `value` of the `name` modifier is invalid, due to `source`.
+    pub fn bad_value(
+        name: &'static str,
+        value: &str,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        let value = value.into();
+        ParsableError::BadValue { name, value, source: source.into() }
+    }
+}
 /// Two strings, one on the left represents the `name` of a modifer,
 /// the one on the right represents its `value`.
 ///
@@ -165,7 +197,7 @@ impl Split {
     }
 }
 
-struct Splitter<'a, Mk: FnMut(&str, usize) -> M, M: Modify> {
+struct Splitter<'a, Mk: FnMut(&str, usize, usize) -> M, M: Modify> {
     split: Split,
     // When `None`, this applies to all sections
     alias: Option<&'a str>,
@@ -175,7 +207,7 @@ struct Splitter<'a, Mk: FnMut(&str, usize) -> M, M: Modify> {
 }
 impl<'a, Mk, M: Modify> Splitter<'a, Mk, M>
 where
-    Mk: FnMut(&str, usize) -> M,
+    Mk: FnMut(&str, usize, usize) -> M,
 {
     fn new(split: Split, alias: &'a str, chopper: Mk) -> Self {
         Self {
@@ -186,9 +218,11 @@ where
         }
     }
 
-    fn rmod(&mut self, input: &str, count: usize) -> Option<Modifier<M>> {
+    fn rmod(&mut self, input: &str, index: &mut usize, count: usize) -> Option<Modifier<M>> {
         let chopper = self.chopper.as_mut()?;
-        Some(Modifier { influence: 1, inner: chopper(input, count) })
+        let inner = chopper(input, *index, count);
+        *index += 1;
+        Some(Modifier { influence: 1, inner })
     }
     fn is(&self, modi: &tree::Modifier) -> bool {
         Some(modi.name) == self.alias
@@ -217,6 +251,7 @@ where
                 continue;
             };
             let content_count = self.split.count(&sections[i..i + sub_count]);
+            let mut index = 0;
 
             sections = extend_segment(sections, i..i + sub_count, |start, range| {
                 let mut replacements = Vec::with_capacity(content_count);
@@ -235,12 +270,12 @@ where
                     for (prev_i, section) in start.iter_mut().enumerate() {
                         section.increment_exceeding(i - prev_i, tail.len());
                     }
-                    if let Some(rmod) = self.rmod(repeat_value, content_count) {
+                    if let Some(rmod) = self.rmod(repeat_value, &mut index, content_count) {
                         current.1.push(rmod);
                     }
 
                     let tail = tail.into_iter().map(|content| {
-                        let rmod = self.rmod(repeat_value, content_count);
+                        let rmod = self.rmod(repeat_value, &mut index, content_count);
                         let rmod = rmod.into_iter().collect();
                         Section(tree::Section::free(content).unwrap(), rmod)
                     });
@@ -424,31 +459,54 @@ impl<'a, M: Modify> Styleable<'a, M> {
 /// Cut the tree in various ways
 impl<'a, M: Modify> Styleable<'a, M> {
     pub fn split(self, split: Split) -> Self {
-        let split: Splitter<fn(&str, usize) -> M, M> =
+        let split: Splitter<fn(&str, usize, usize) -> M, M> =
             Splitter { split, alias: None, chopper: None, _p: PhantomData };
         Styleable { sections: split.process(self.sections) }
     }
-    pub fn chop(self, split: Split, alias: &str, chopper: impl FnMut(&str, usize) -> M) -> Self {
+    pub fn chop(
+        self,
+        split: Split,
+        alias: &str,
+        mut chopper: impl FnMut(&str, usize) -> M,
+    ) -> Self {
+        self.indexed_chop(split, alias, move |input, _, count| chopper(input, count))
+    }
+    /// Like [`Styleable::chop`], but `chopper` also gets the index of the
+    /// section it is called for, within the `count` sections of the chopped
+    /// `alias` occurence: `chopper(input, index, count)`.
+    pub fn indexed_chop(
+        self,
+        split: Split,
+        alias: &str,
+        chopper: impl FnMut(&str, usize, usize) -> M,
+    ) -> Self {
         let split = Splitter::new(split, alias, chopper);
         Styleable { sections: split.process(self.sections) }
     }
-    pub fn acc_chop<Acc: FromStr>(
+    /// Like [`Styleable::indexed_chop`], with the value of `alias` parsed as
+    /// an `Acc` once per occurence of `alias`.
+    ///
+    /// When the value can't be parsed, a warning is logged and
+    /// `Acc::default()` is used instead.
+    pub fn acc_chop<Acc: FromStr + Default>(
         self,
         split: Split,
         alias: &str,
         mut chopper: impl FnMut(&mut Acc, usize, usize) -> M,
     ) -> Self {
-        let mut acc = None;
-        let mut i = 0;
-
-        self.chop(split, alias, move |input: &str, count| {
-            let acc = acc.get_or_insert_with(|| input.parse::<Acc>().ok().unwrap());
-            let result = chopper(acc, i, count);
-            i += 1;
-            result
+        let mut acc = Acc::default();
+
+        self.indexed_chop(split, alias, move |input: &str, i, count| {
+            if i == 0 {
+                acc = input.trim().parse().unwrap_or_else(|_| {
+                    warn!("Can't parse the `{alias}` value `{input}`, using the default");
+                    Acc::default()
+                });
+            }
+            chopper(&mut acc, i, count)
         })
     }
-    pub fn curve_chop<Acc: FromStr>(
+    pub fn curve_chop<Acc: FromStr + Default>(
         self,
         split: Split,
         alias: &str,
@@ -464,7 +522,7 @@ impl<'a, M: Modify> Styleable<'a, M> {
         })
     }
 }
-fn escape_backslashes(input: &mut Cow<str>) {
+pub(crate) fn escape_backslashes(input: &mut Cow<str>) {
     if !input.contains('\\') {
         return;
     }
@@ -4,7 +4,7 @@ use pretty_assertions::assert_eq;
 use winnow::error::ParseError;
 use winnow::Parser;
 
-use super::{balanced_text, bare_content, close_section, closed_element, sections, tree};
+use super::{balanced_text, bare_content, close_section, closed_element, markup, sections, tree};
 use tree::{Binding, Dyn, Modifier, Section};
 
 macro_rules! sections {
@@ -253,6 +253,26 @@ fn outer_color_mod() {
     assert_eq!(Ok(expected), parse(input));
 }
 #[test]
+fn no_content_mod() {
+    let input = "Deals 5 {Icon: fire} damage";
+    let expected = sections!["Deals 5 ", [(Icon 1 static "fire")], " damage"];
+    assert_eq!(Ok(expected), parse(input));
+}
+#[test]
+fn no_content_non_image_mod() {
+    let input = "Deals 5 {Color: red} damage";
+    let expected = sections!["Deals 5 ", " damage"];
+    assert_eq!(Ok(expected), parse(input));
+}
+#[test]
+fn format_string_mod() {
+    let input = "{Tooltip: {Color: red|Burning}: deals damage over time|burn}";
+    let expected = sections![
+        ["burn", (Tooltip 1 static "{Color: red|Burning}: deals damage over time")]
+    ];
+    assert_eq!(Ok(expected), parse(input));
+}
+#[test]
 fn nested_dynamic_shorthand() {
     let input = "{Color: Blue | {dynamic_blue_content}}";
     let expected = sections![[
@@ -380,3 +400,142 @@ fn real_world_usecase() {
     assert_eq!(Ok(expected), parse(input));
 }
 // ---------------------------------
+#[test]
+fn source_complete() {
+    let source = super::source("Res(Settings).volume").unwrap();
+    assert_eq!(source.query, tree::Query::Res("Settings"));
+    assert_eq!(source.reflect_path, ".volume");
+
+    let source = super::source("Marked(Player).Stats.health").unwrap();
+    let query = tree::Query::Marked { marker: "Player", access: "Stats" };
+    assert_eq!(source.query, query);
+    assert_eq!(source.reflect_path, ".health");
+
+    assert!(super::source("volume").is_err());
+    assert!(super::source("Res(Settings).volume}").is_err());
+}
+#[test]
+fn modifier_list() {
+    let modifiers = super::modifiers(r" Font: bold.ttf, Shadow: (1, 2) ,Content: a\, b").unwrap();
+    let modifiers: Vec<_> = modifiers.iter().map(|(n, v)| (*n, v.as_ref())).collect();
+    let expected = [
+        ("Font", "bold.ttf"),
+        ("Shadow", "(1, 2)"),
+        ("Content", "a, b"),
+    ];
+    assert_eq!(&modifiers, &expected);
+
+    assert!(super::modifiers("Color: {color}").is_err());
+    assert!(super::modifiers("Color: red|text").is_err());
+}
+
+// ---------------------------------
+//        test markup parsing
+// ---------------------------------
+fn markup_tags() -> markup::Tags {
+    let mut tags = markup::Tags::default();
+    tags.insert("color", "Color")
+        .insert("size", "RelSize")
+        .insert_with_value("b", "Font", "bold.ttf")
+        .insert_with_value("i", "Font", "italic.ttf")
+        .insert_with_value("s", "Strike", "1");
+    tags
+}
+#[test]
+fn bbcode_nested_tags() {
+    let tags = markup_tags();
+    let input = "[color=red]Hello [b]world[/b][/color]!";
+    let expected = parse("{Color: red|Hello {Font: bold.ttf|world}}!");
+    let bbcode = markup::bbcode(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(expected.unwrap()), bbcode);
+}
+#[test]
+fn bbcode_bindings() {
+    let tags = markup_tags();
+    let input = "[size=\"1.5\"]{score}[/size] [color={team_color}]team[/color] [s]x[/s]";
+    let expected = parse("{RelSize: 1.5|{score}} {Color: {team_color}|team} {Strike: 1|x}");
+    let bbcode = markup::bbcode(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(expected.unwrap()), bbcode);
+}
+#[test]
+fn markdown_emphasis() {
+    let tags = markup_tags();
+    let input = "**bold *italic* text** ~~gone~~";
+    let expected = parse("{Font: bold.ttf|bold {Font: italic.ttf|italic} text} {Strike: 1|gone}");
+    let markdown = markup::markdown(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(expected.unwrap()), markdown);
+}
+#[test]
+fn markdown_literal_asterisks() {
+    let tags = markup_tags();
+    let markdown = |input| markup::markdown(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(sections!["2 * 3 = 6"]), markdown("2 * 3 = 6"));
+    assert_eq!(Ok(sections!["a ** b"]), markdown("a ** b"));
+    assert_eq!(Ok(sections!["* item"]), markdown("* item"));
+    assert_eq!(Ok(sections!["*unclosed"]), markdown("*unclosed"));
+
+    let expected = parse("2 * 3 {Font: italic.ttf|and} 4").unwrap();
+    assert_eq!(Ok(expected), markdown("2 * 3 *and* 4"));
+    let expected = parse("{Font: italic.ttf|a * b}").unwrap();
+    assert_eq!(Ok(expected), markdown("*a * b*"));
+}
+#[test]
+fn markdown_escape() {
+    let tags = markup_tags();
+    let input = r#"\*not italic\* {name}"#;
+    let expected = sections![r#"\*not italic\* "#, { name }];
+    let markdown = markup::markdown(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(expected), markdown);
+}
+#[test]
+fn markup_errors() {
+    use markup::Error;
+
+    let tags = markup_tags();
+    let bbcode = |input: &'static str| markup::bbcode(input, &tags).map(|t| t.sections);
+
+    let missing = Error::MissingValue("color".into());
+    assert_eq!(bbcode("[color]red[/color]"), Err(missing));
+    assert_eq!(bbcode("text[/b]"), Err(Error::NotOpened("b".into())));
+    let mismatched = Error::Mismatched("b".into(), "i".into());
+    assert_eq!(bbcode("[b][i]text[/b][/i]"), Err(mismatched));
+    assert_eq!(bbcode("[b]text"), Err(Error::NotClosed("b".into())));
+    assert_eq!(bbcode("[b text"), Err(Error::UnterminatedTag(0)));
+}
+#[test]
+fn bbcode_unknown_tags() {
+    let tags = markup_tags();
+    let bbcode = |input| markup::bbcode(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(sections!["a footnote[1]"]), bbcode("a footnote[1]"));
+    assert_eq!(Ok(sections!["[x] done"]), bbcode("[x] done"));
+    assert_eq!(Ok(sections!["[url]link[/url]"]), bbcode("[url]link[/url]"));
+
+    let expected = parse("{Font: italic.ttf|teh [sic]} text").unwrap();
+    assert_eq!(Ok(expected), bbcode("[i]teh [sic][/i] text"));
+}
+#[test]
+fn markup_literal_braces() {
+    let tags = markup_tags();
+    let bbcode = |input| markup::bbcode(input, &tags).map(|t| t.sections);
+    let markdown = |input| markup::markdown(input, &tags).map(|t| t.sections);
+    assert_eq!(Ok(sections!["a { b"]), markdown("a { b"));
+    assert_eq!(Ok(sections!["{}"]), markdown("{}"));
+    assert_eq!(Ok(sections!["{ ", { name }]), markdown("{ {name}"));
+    assert_eq!(Ok(sections!["set {"]), bbcode("set {"));
+
+    let expected = sections![["{ ", (Font 2 static "bold.ttf")], { name }];
+    assert_eq!(Ok(expected), bbcode("[b]{ {name}[/b]"));
+}
+#[test]
+fn markdown_unclosed_nested() {
+    let tags = markup_tags();
+    let markdown = markup::markdown("*italic **bold* text**", &tags);
+    let mut expected = sections!["*"];
+    expected.extend(parse("italic {Font: bold.ttf|bold* text}").unwrap());
+    assert_eq!(Ok(expected), markdown.map(|t| t.sections));
+
+    let markdown = markup::markdown("**a *b** c*", &tags);
+    let mut expected = sections!["**"];
+    expected.extend(parse("a {Font: italic.ttf|b** c}").unwrap());
+    assert_eq!(Ok(expected), markdown.map(|t| t.sections));
+}
@@ -8,6 +8,11 @@ pub(crate) const CONTENT_NAME: &str = "Content";
 pub(crate) fn is_content(m: &Modifier) -> bool {
     m.name == CONTENT_NAME
 }
+/// Modifiers that display something in place of the section's text.
+pub(crate) const IMAGE_NAMES: [&str; 2] = ["Image", "Icon"];
+pub(crate) fn is_image(m: &Modifier) -> bool {
+    IMAGE_NAMES.contains(&m.name)
+}
 pub(crate) fn get_content<'a>(m: &Modifier<'a>) -> Option<&'a str> {
     match m.value {
         Dyn::Static(value) if is_content(m) => Some(value),
@@ -205,10 +210,11 @@ pub struct Tree<'a> {
 pub(crate) fn flatten_section<'a>(
     (mut modifiers, content): (Vec<Modifier<'a>>, Option<Sections<'a>>),
 ) -> Vec<Section<'a>> {
-    // Either we have a `content` metadata or we re-use section
+    // Either we have a `content` metadata or we re-use section.
+    // Image modifiers, such as `{Icon: fire}`, stand in for the content.
     let mut sections = if let Some(Sections(sections)) = content {
         sections
-    } else if modifiers.iter().any(is_content) {
+    } else if modifiers.iter().any(|m| is_content(m) || is_image(m)) {
         vec![Section { modifiers: Vec::with_capacity(modifiers.len()) }]
     } else {
         // TODO(err): should error here, we have metadata and no content,
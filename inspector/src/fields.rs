@@ -0,0 +1,151 @@
+//! Find the reflection paths of all the fields of a type from the type registry.
+
+use std::fmt;
+
+use bevy::reflect::{TypeInfo, TypeRegistryInternal as TypeRegistry};
+
+/// How deep to go into nested structs before displaying the whole value.
+const MAX_DEPTH: usize = 4;
+
+/// All the reflection paths to the "leaf" fields of `info`, for example
+/// `.translation.x` for `Transform`.
+///
+/// Lists, maps, enums and opaque values are not expanded, they are displayed
+/// as a single field.
+pub(crate) fn leaf_paths(info: &TypeInfo, registry: &TypeRegistry) -> Vec<String> {
+    let mut paths = Vec::new();
+    push_leaves(Some(info), registry, String::new(), 0, &mut paths);
+    paths
+}
+fn push_leaves(
+    info: Option<&TypeInfo>,
+    registry: &TypeRegistry,
+    prefix: String,
+    depth: usize,
+    paths: &mut Vec<String>,
+) {
+    let Some(info) = info.filter(|_| depth < MAX_DEPTH) else {
+        paths.push(prefix);
+        return;
+    };
+    let mut push_field = |id, name: &dyn fmt::Display| {
+        let path = format!("{prefix}.{name}");
+        push_leaves(registry.get_type_info(id), registry, path, depth + 1, paths);
+    };
+    match info {
+        TypeInfo::Struct(info) => {
+            for field in info.iter() {
+                push_field(field.type_id(), &field.name());
+            }
+        }
+        TypeInfo::TupleStruct(info) => {
+            for field in info.iter() {
+                push_field(field.type_id(), &field.index());
+            }
+        }
+        TypeInfo::Tuple(info) => {
+            for field in info.iter() {
+                push_field(field.type_id(), &field.index());
+            }
+        }
+        _ => paths.push(prefix),
+    }
+}
+
+/// Whether `name` can be used as a type name in a hook.
+pub(crate) fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    let is_start = |c: char| c.is_ascii_alphabetic() || c == '_';
+    chars.next().is_some_and(is_start) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::{FromReflect, Reflect, Typed};
+
+    use super::*;
+
+    #[derive(Reflect)]
+    struct Inner {
+        x: f32,
+        y: f32,
+    }
+    #[derive(Reflect, FromReflect)]
+    enum Mode {
+        On,
+        Off,
+    }
+    #[derive(Reflect)]
+    struct Wrapper(Inner, u32);
+
+    #[derive(Reflect)]
+    struct Outer {
+        inner: Inner,
+        mode: Mode,
+        wrapper: Wrapper,
+        pair: (u8, Inner),
+    }
+    // Nests 5 levels deep, one more than MAX_DEPTH.
+    #[derive(Reflect)]
+    struct Deep(Wrapper);
+    #[derive(Reflect)]
+    struct Deeper(Deep);
+    #[derive(Reflect)]
+    struct Deepest(Deeper);
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Inner>();
+        registry.register::<Mode>();
+        registry.register::<Wrapper>();
+        registry.register::<(u8, Inner)>();
+        registry.register::<Outer>();
+        registry.register::<Deep>();
+        registry.register::<Deeper>();
+        registry.register::<Deepest>();
+        registry
+    }
+
+    #[test]
+    fn nested_paths() {
+        let registry = registry();
+        let paths = leaf_paths(Outer::type_info(), &registry);
+        let expected = [
+            ".inner.x",
+            ".inner.y",
+            ".mode",
+            ".wrapper.0.x",
+            ".wrapper.0.y",
+            ".wrapper.1",
+            ".pair.0",
+            ".pair.1.x",
+            ".pair.1.y",
+        ];
+        assert_eq!(paths, expected);
+    }
+    #[test]
+    fn leaf_types() {
+        let registry = registry();
+        assert_eq!(leaf_paths(Mode::type_info(), &registry), [""]);
+        assert_eq!(leaf_paths(f32::type_info(), &registry), [""]);
+    }
+    #[test]
+    fn max_depth() {
+        let registry = registry();
+        let paths = leaf_paths(Deepest::type_info(), &registry);
+        assert_eq!(paths, [".0.0.0.0", ".0.0.0.1"]);
+    }
+    #[test]
+    fn identifiers() {
+        assert!(is_ident("Time"));
+        assert!(is_ident("_private"));
+        assert!(is_ident("Player2"));
+
+        assert!(!is_ident(""));
+        assert!(!is_ident("2d"));
+        assert!(!is_ident("Handle<Image>"));
+        assert!(!is_ident("(u8, u8)"));
+        assert!(!is_ident("[f32; 3]"));
+        assert!(!is_ident("has space"));
+    }
+}
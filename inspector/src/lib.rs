@@ -0,0 +1,279 @@
+//! An in-game inspector for bevy, built with `cuicui_richtext` and `cuicui_layout`.
+//!
+//! Spawn an [`Inspector`] and it will display all the reflected fields of an
+//! entity's components or of a resource. Each field is a binding updated
+//! by `cuicui_richtext`, generated from the type registry.
+//!
+//! # Usage
+//!
+//! - [ ] Add [`InspectorPlugin`] and `RichTextPlugin` to your app
+//! - [ ] Spawn an [`Inspector`], for example `Inspector::entity(player)`.
+//!
+//! Only the components that `#[reflect(Queryable)]` and resources that
+//! `#[reflect(Resource)]` are displayed, and their type must be registered.
+
+use std::any::{self, TypeId};
+
+use bevy::ecs::reflect::ReflectResource;
+use bevy::prelude::*;
+use bevy_fab::{Read, Write};
+use cuicui_layout as layout;
+use cuicui_richtext::{MakeRichText, Modifier, ReflectQueryable};
+use log::{error, warn};
+use thiserror::Error;
+
+mod fields;
+
+#[derive(Debug, Error)]
+enum Error {
+    #[error("The inspected entity {0:?} doesn't exist")]
+    NoEntity(Entity),
+    #[error("Can't read the `{0}` field of the inspected entity: {1}")]
+    BadField(String, String),
+    #[error("The inspected resource `{0}` is not in the type registry")]
+    NotRegistered(&'static str),
+    #[error("The inspected resource `{0}` doesn't reflect Resource")]
+    NotResource(&'static str),
+    #[error("The inspected resource `{0}` has a name that can't be used in a hook")]
+    BadTypeName(&'static str),
+}
+
+/// What an [`Inspector`] displays.
+#[derive(Clone, Copy, Debug)]
+pub enum Inspected {
+    /// All the reflected components of this entity.
+    Entity(Entity),
+    /// A reflected resource.
+    Resource(TypeId, &'static str),
+}
+
+/// A panel displaying the fields of an [`Inspected`] value.
+///
+/// The panel is built when the `Inspector` component is added, it doesn't
+/// track components added or removed after that.
+#[derive(Component, Clone, Debug)]
+pub struct Inspector {
+    pub inspected: Inspected,
+    /// Width of the panel, in pixels.
+    pub width: f32,
+    pub text_style: TextStyle,
+}
+impl Inspector {
+    pub fn new(inspected: Inspected) -> Self {
+        let text_style = TextStyle { font_size: 16.0, ..default() };
+        Inspector { inspected, width: 300.0, text_style }
+    }
+    /// Inspect the components of `entity`.
+    pub fn entity(entity: Entity) -> Self {
+        Inspector::new(Inspected::Entity(entity))
+    }
+    /// Inspect the `R` resource.
+    pub fn resource<R: Resource>() -> Self {
+        let inspected = Inspected::Resource(TypeId::of::<R>(), any::type_name::<R>());
+        Inspector::new(inspected)
+    }
+    /// Returns this [`Inspector`] with a new [`TextStyle`] for all its fields.
+    pub fn with_text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_style = text_style;
+        self
+    }
+    /// Returns this [`Inspector`] with a new panel width.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+    fn line_height(&self) -> f32 {
+        self.text_style.font_size * 1.2
+    }
+}
+
+/// Marks an [`Inspector`] which UI was already spawned.
+#[derive(Component)]
+struct Built;
+
+/// A node of the inspector UI, positioned by `cuicui_layout`.
+#[derive(Component)]
+struct InspectorNode;
+
+/// A header and the rich text format strings of each field of a
+/// component or resource.
+struct Section {
+    header: String,
+    fields: Vec<String>,
+}
+impl Section {
+    /// `hook` returns the hook or binding displaying the field at a reflection
+    /// path, such as `{Res(Time).elapsed}`, or `None` to skip the field.
+    fn new(header: &str, paths: Vec<String>, mut hook: impl FnMut(&str) -> Option<String>) -> Self {
+        let field = |path: String| match path.strip_prefix('.') {
+            Some(label) => Some(format!("{label}: {}", hook(&path)?)),
+            None => hook(&path),
+        };
+        let fields = paths.into_iter().filter_map(field).collect();
+        Section { header: header.to_owned(), fields }
+    }
+    fn lines(&self) -> usize {
+        self.fields.len() + 1
+    }
+}
+
+/// Bind each field of the components of `entity` to a binding of
+/// [`bevy_fab::WorldBindings`], owned by the `inspector` entity.
+///
+/// Fields that can't be read are logged and left out of the panel.
+fn entity_sections(
+    world: &mut World,
+    inspector: Entity,
+    entity: Entity,
+) -> Result<Vec<Section>, Error> {
+    world.get_entity(entity).ok_or(Error::NoEntity(entity))?;
+    let components = world.inspect_entity(entity).into_iter();
+    let components: Vec<_> = components.filter_map(|info| info.type_id()).collect();
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut bindings = world.resource_mut::<bevy_fab::WorldBindings<Modifier>>();
+    let prefix = format!("inspector_{}v{}", inspector.index(), inspector.generation());
+    let mut bound = 0;
+    let mut sections = Vec::new();
+    for id in components {
+        let Some(registration) = registry.get(id) else { continue; };
+        let Some(queryable) = registration.data::<ReflectQueryable>() else { continue; };
+        let short_name = registration.short_name();
+        let mut hook = |path: &str| {
+            let read = match Read::component_field(entity, queryable, path) {
+                Ok(read) => read,
+                Err(err) => {
+                    warn!("{}", Error::BadField(path.to_owned(), err.to_string()));
+                    return None;
+                }
+            };
+            let name = format!("{prefix}_{bound}");
+            bound += 1;
+            bindings.bind_owned(inspector, &name, read, Write::Debug);
+            Some(format!("{{{name}}}"))
+        };
+        let paths = fields::leaf_paths(registration.type_info(), &registry);
+        sections.push(Section::new(short_name, paths, &mut hook));
+    }
+    Ok(sections)
+}
+fn resource_sections(world: &World, id: TypeId, name: &'static str) -> Result<Section, Error> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registration = registry.get(id).ok_or(Error::NotRegistered(name))?;
+    let short_name = registration.short_name();
+    if registration.data::<ReflectResource>().is_none() {
+        return Err(Error::NotResource(name));
+    }
+    if !fields::is_ident(short_name) {
+        return Err(Error::BadTypeName(name));
+    }
+    let paths = fields::leaf_paths(registration.type_info(), &registry);
+    let hook = |path: &str| Some(format!("{{Res({short_name}){path}}}"));
+    Ok(Section::new(short_name, paths, hook))
+}
+
+fn ui_node() -> impl Bundle {
+    let style = Style { position_type: PositionType::Absolute, ..default() };
+    let ui_node = NodeBundle { style, ..default() };
+    (ui_node, layout::PosRect::default(), InspectorNode)
+}
+fn node(node: layout::Node) -> impl Bundle {
+    (node, ui_node())
+}
+fn spawn_line(parent: &mut WorldChildBuilder, inspector: &Inspector, text: &str) {
+    let (width, height) = (inspector.width, inspector.line_height());
+    let line = node(layout::Node::Known(layout::Size { width, height }));
+    let text = MakeRichText::new(text).with_text_style(inspector.text_style.clone());
+    parent.spawn(line).with_children(|line| {
+        line.spawn(text);
+    });
+}
+
+/// Spawn the UI of newly added [`Inspector`]s.
+fn build_inspectors(world: &mut World) {
+    let mut new_inspectors = world.query_filtered::<(Entity, &Inspector), Without<Built>>();
+    let new_inspectors: Vec<_> = new_inspectors
+        .iter(world)
+        .map(|(entity, inspector)| (entity, inspector.clone()))
+        .collect();
+
+    for (entity, inspector) in new_inspectors {
+        let sections = match inspector.inspected {
+            Inspected::Entity(inspected) => entity_sections(world, entity, inspected),
+            Inspected::Resource(id, name) => resource_sections(world, id, name).map(|s| vec![s]),
+        };
+        let sections = match sections {
+            Ok(sections) => sections,
+            Err(err) => {
+                error!("Can't build inspector: {err}");
+                Vec::new()
+            }
+        };
+        let lines: usize = sections.iter().map(Section::lines).sum();
+        let bounds = layout::Size {
+            width: inspector.width,
+            height: lines as f32 * inspector.line_height(),
+        };
+        let (direction, space_use) = (layout::Direction::Vertical, layout::SpaceUse::Compact);
+        let root = layout::Root::new(bounds, direction, space_use);
+        let container = layout::Container::new(direction, space_use);
+
+        let mut entity = world.entity_mut(entity);
+        entity.insert((Built, root, ui_node()));
+        entity.with_children(|parent| {
+            for section in &sections {
+                let container = node(layout::Node::Container(container));
+                parent.spawn(container).with_children(|parent| {
+                    spawn_line(parent, &inspector, &section.header);
+                    for field in &section.fields {
+                        spawn_line(parent, &inspector, field);
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Remove the bindings of [`Inspector`] components removed from their entity.
+///
+/// Despawned inspectors' bindings are removed by [`bevy_fab::update_hooked`].
+fn remove_inspector_hooks(
+    mut removed: RemovedComponents<Inspector>,
+    mut bindings: ResMut<bevy_fab::WorldBindings<Modifier>>,
+) {
+    for inspector in removed.iter() {
+        bindings.remove_hooks_of(inspector);
+    }
+}
+
+type UpdatedNode = (With<InspectorNode>, Changed<layout::PosRect>);
+
+/// Position the inspector's `bevy_ui` nodes according to the layout
+/// computed by `cuicui_layout`.
+fn update_styles(mut nodes: Query<(&layout::PosRect, &mut Style), UpdatedNode>) {
+    for (rect, mut style) in &mut nodes {
+        let (pos, size) = (rect.pos(), rect.size());
+        style.position.left = Val::Px(pos.left);
+        style.position.top = Val::Px(pos.top);
+        style.size = Size::new(Val::Px(size.width), Val::Px(size.height));
+    }
+}
+
+/// Spawns the UI of [`Inspector`]s and keeps it positioned.
+///
+/// This adds `cuicui_layout`'s plugin if it wasn't added yet, but you still
+/// need to add `RichTextPlugin`.
+pub struct InspectorPlugin;
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        use layout::Systems::ComputeLayout;
+
+        if !app.is_plugin_added::<layout::Plug>() {
+            app.add_plugin(layout::Plug);
+        }
+        app.add_system(build_inspectors.before(ComputeLayout))
+            .add_system(update_styles.after(ComputeLayout))
+            .add_system(remove_inspector_hooks);
+    }
+}
@@ -8,8 +8,12 @@ macro_rules! register_reflect_query {
     (@just_type $registry:expr, $( $to_register:ty ),* $(,)?) => {
         $( $registry.register_type::<$to_register>() );*
     };
+    // Registering a type already registered does nothing, this lets apps
+    // without the plugins of those types, such as headless apps, add `ReflectQueryable`.
     ($registry:expr, $( $to_register:ty ),* $(,)?) => {
-        $( $registry.register_type_data::<$to_register, ReflectQueryable>() );*
+        $( $registry
+            .register_type::<$to_register>()
+            .register_type_data::<$to_register, ReflectQueryable>() );*
     };
 }
 
@@ -0,0 +1,86 @@
+//! Measure the time it takes to update many rich text entities at once.
+//!
+//! The update systems iterate [`LocalBindings`] in parallel on the
+//! [`ComputeTaskPool`]. Since the task pool is global to the process, the
+//! thread count is read from the `CUICUI_BENCH_THREADS` environment variable.
+//! To see the speedup, compare a single-threaded run with the default:
+//!
+//! ```sh
+//! CUICUI_BENCH_THREADS=1 cargo bench -p cuicui_richtext
+//! cargo bench -p cuicui_richtext
+//! ```
+//!
+//! criterion reports the second run relative to the first one.
+//!
+//! [`LocalBindings`]: bevy_fab::LocalBindings
+//! [`ComputeTaskPool`]: bevy::tasks::ComputeTaskPool
+use std::env;
+
+use bevy::{
+    app::ScheduleRunnerPlugin, core::TaskPoolOptions, prelude::*, text::Font, time::TimePlugin,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use cuicui_richtext::{MakeRichText, Modifier, RichTextPlugin};
+
+const FORMAT_STRING: &str = "Health: {health}/100 {Color:red|-{damage}}\n\
+    {Color:blue|Team} score: {score}";
+
+fn thread_count() -> Option<usize> {
+    env::var("CUICUI_BENCH_THREADS").ok()?.parse().ok()
+}
+
+fn setup_app(text_count: usize) -> App {
+    let task_pool_options = match thread_count() {
+        Some(threads) => TaskPoolOptions::with_num_threads(threads),
+        None => TaskPoolOptions::default(),
+    };
+    let mut app = App::new();
+    app.add_plugin(TaskPoolPlugin { task_pool_options })
+        .add_plugin(TypeRegistrationPlugin)
+        .add_plugin(FrameCountPlugin)
+        .add_plugin(ScheduleRunnerPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(TimePlugin)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Font>()
+        .add_asset::<Image>()
+        .add_plugin(RichTextPlugin::no_default_styles());
+
+    for _ in 0..text_count {
+        app.world.spawn(MakeRichText::new(FORMAT_STRING));
+    }
+    // Parse the format strings and spawn the text sections.
+    app.update();
+    app
+}
+
+fn set_bindings(app: &mut App, frame: usize) {
+    let mut world_bindings = app
+        .world
+        .resource_mut::<bevy_fab::WorldBindings<Modifier>>();
+    let bindings = &mut world_bindings.bindings;
+    let content = |value: usize| Modifier::content(value.to_string().into());
+    bindings.set("health", content(frame % 100)).unwrap();
+    bindings.set("damage", content(frame % 7)).unwrap();
+    bindings.set("score", content(frame)).unwrap();
+}
+
+fn update_rich_text(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_rich_text");
+    for text_count in [10, 100, 1000] {
+        let mut app = setup_app(text_count);
+        let mut frame = 0;
+        let id = BenchmarkId::new("world_bindings", text_count);
+        group.bench_function(id, |b| {
+            b.iter(|| {
+                frame += 1;
+                set_bindings(&mut app, frame);
+                app.update();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, update_rich_text);
+criterion_main!(benches);
@@ -11,7 +11,8 @@ use bevy::{
     sprite::MaterialMesh2dBundle,
     utils::HashMap,
 };
-use cuicui_richtext::{MakeRichText, ReflectQueryable, RichTextPlugin, WorldBindingsMut};
+use cuicui_richtext::trait_extensions::AppTextBindExtension;
+use cuicui_richtext::{MakeRichText, Modifier, ReflectQueryable, RichTextPlugin, WorldBindingsMut};
 
 const TIME_STEP: f32 = 1.0 / 60.0;
 
@@ -79,6 +80,9 @@ fn main() {
         .register_type::<Collider>()
         .init_resource::<Score>()
         .init_resource::<Deaths>()
+        .bind_resource("deaths", |deaths: &Deaths, entry| {
+            entry.insert(Modifier::content(format!("Deaths: {}", deaths.0).into()));
+        })
         .insert_resource(ClearColor(BACKGROUND_COLOR))
         .add_startup_system(setup)
         .add_event::<CollisionEvent>()
@@ -254,7 +258,7 @@ fn setup(
         MakeRichText::new(
             "Score: {Font: fonts/FiraMono-Medium.ttf, Color: rgb(1.0, 0.5, 0.5), \
             RelSize: 1.5, Content: {Res(Score).score:}}\n\
-            {Color: rgb(1.0, 0.2, 0.2), Content: {Res(Deaths):?}}\n\
+            {Color: rgb(1.0, 0.2, 0.2), Content: {deaths}}\n\
             Paddle hits: {Color: pink, Content: {Marked(Paddle).Collider.collision_count:03}}\n\
             Ball position: {Font: fonts/FiraMono-Medium.ttf, Color: pink|\
             \\{x: {ball_x}, y: {ball_y}\\}}",
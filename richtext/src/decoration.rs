@@ -0,0 +1,403 @@
+//! Underline, strike through, outline and shadow sections, with the
+//! `Underline`, `Strike`, `Outline` and `Shadow` modifiers.
+//!
+//! bevy's [`TextStyle`] has no decorations, so they are drawn by siblings of
+//! the section: plain nodes for lines and copies of the section's text for
+//! outlines and shadows.
+//!
+//! A text node with children ignores the size of its text, so sections with
+//! decorations are wrapped in an overlay node, see [`overlay`]. Decorations are
+//! absolutely positioned children of the overlay, they don't affect the layout.
+//! Outlines and shadows are before the section in the overlay's children, so
+//! they are drawn under it.
+
+use bevy::{ecs::query::WorldQuery, prelude::*};
+
+use crate::{color, image::DESCENT, modifiers::parameters};
+
+/// Height of the strike through line above the baseline, relative to the
+/// font size.
+const STRIKE_HEIGHT: f32 = 0.3;
+/// Color of the `Outline` modifier when not specified.
+const OUTLINE_COLOR: Color = Color::BLACK;
+/// Color of the `Shadow` modifier when not specified.
+const SHADOW_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.5);
+/// Directions in which the copies of an outline are moved.
+const OUTLINE_DIRECTIONS: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (0.707, 0.707),
+    (0.0, 1.0),
+    (-0.707, 0.707),
+    (-1.0, 0.0),
+    (-0.707, -0.707),
+    (0.0, -1.0),
+    (0.707, -0.707),
+];
+
+/// A line drawn over or under a section.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Line {
+    /// Thickness of the line in pixels.
+    pub thickness: f32,
+    /// Color of the line, the color of the text if `None`.
+    pub color: Option<Color>,
+}
+/// An outline around the characters of a section.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outline {
+    /// Thickness of the outline in pixels.
+    pub thickness: f32,
+    pub color: Color,
+}
+/// A copy of the text of a section, drawn behind it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow {
+    /// How many pixels right and down the shadow is.
+    pub offset: Vec2,
+    pub color: Color,
+}
+
+/// The decorations of a section, set by the `Underline`, `Strike`, `Outline`
+/// and `Shadow` modifiers.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct Decorations {
+    pub underline: Option<Line>,
+    pub strike: Option<Line>,
+    pub outline: Option<Outline>,
+    pub shadow: Option<Shadow>,
+}
+
+/// The entities drawing the [`Decorations`] of a section.
+#[derive(Component)]
+pub struct DecorationNodes(Vec<Entity>);
+
+/// The parent of a section holding the entities drawn over it, see [`overlay`].
+#[derive(Component)]
+pub struct Overlay(Entity);
+
+/// The [`Overlay`] of `section`, spawned in place of `section` in its parent
+/// if it doesn't have one yet.
+///
+/// Only sections with decorations or a hovered link need an overlay, so that
+/// rich text with many sections doesn't spawn twice as many nodes.
+pub(crate) fn overlay(section: Entity, overlay: Option<&Overlay>, cmds: &mut Commands) -> Entity {
+    if let Some(&Overlay(overlay)) = overlay {
+        return overlay;
+    }
+    let overlay = cmds.spawn(NodeBundle::default()).id();
+    cmds.entity(section).insert(Overlay(overlay));
+    cmds.add(move |world: &mut World| {
+        let Some(line) = world.get::<Parent>(section).map(Parent::get) else { return; };
+        let children = world.get::<Children>(line).map_or(&[][..], |c| &c[..]);
+        let index = children.iter().position(|&c| c == section);
+        let index = index.unwrap_or(children.len());
+        world.entity_mut(line).insert_children(index, &[overlay]);
+        world.entity_mut(overlay).add_child(section);
+    });
+    overlay
+}
+
+/// An entity drawing a decoration.
+enum DecorationNode {
+    /// A copy of the section's text, with `color` instead of the text's color.
+    Copy { offset: Vec2, color: Color },
+    /// A line, `bottom` is its distance to the bottom of the section,
+    /// relative to the font size.
+    Line { line: Line, bottom: f32 },
+}
+impl Decorations {
+    /// The nodes drawing those decorations, in drawing order, copies first.
+    fn nodes(&self) -> Vec<DecorationNode> {
+        let mut nodes = Vec::new();
+        if let Some(Shadow { offset, color }) = self.shadow {
+            nodes.push(DecorationNode::Copy { offset, color });
+        }
+        if let Some(Outline { thickness, color }) = self.outline {
+            let copy = |(x, y)| {
+                let offset = Vec2::new(x, y) * thickness;
+                DecorationNode::Copy { offset, color }
+            };
+            nodes.extend(OUTLINE_DIRECTIONS.into_iter().map(copy));
+        }
+        if let Some(line) = self.underline {
+            nodes.push(DecorationNode::Line { line, bottom: DESCENT / 2.0 });
+        }
+        if let Some(line) = self.strike {
+            nodes.push(DecorationNode::Line { line, bottom: DESCENT + STRIKE_HEIGHT });
+        }
+        nodes
+    }
+}
+impl DecorationNode {
+    fn style(&self, text: &Text) -> Style {
+        let font_size = text.sections.first().map_or(0.0, |s| s.style.font_size);
+        let (position, size) = match self {
+            DecorationNode::Copy { offset, .. } => (
+                UiRect {
+                    left: Val::Px(offset.x),
+                    top: Val::Px(offset.y),
+                    ..default()
+                },
+                Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+            ),
+            DecorationNode::Line { line, bottom } => (
+                UiRect {
+                    left: Val::Px(0.0),
+                    bottom: Val::Px(font_size * bottom),
+                    ..default()
+                },
+                Size::new(Val::Percent(100.0), Val::Px(line.thickness)),
+            ),
+        };
+        Style {
+            position_type: PositionType::Absolute,
+            position,
+            size,
+            ..default()
+        }
+    }
+    fn is_copy(&self) -> bool {
+        matches!(self, DecorationNode::Copy { .. })
+    }
+    fn text(color: Color, text: &Text) -> Text {
+        let mut text = text.clone();
+        text.sections.iter_mut().for_each(|s| s.style.color = color);
+        text
+    }
+    /// Update `copy` to be `text` with `color`, only if it is different.
+    ///
+    /// The text of a section changes often, for example with animated
+    /// colors, this avoids cloning it for each copy when it doesn't matter.
+    fn update_text(color: Color, text: &Text, copy: &mut Mut<Text>) {
+        let same = |(c, s): (&TextSection, &TextSection)| {
+            c.value == s.value
+                && c.style.font == s.style.font
+                && c.style.font_size == s.style.font_size
+                && c.style.color == color
+        };
+        let same_len = copy.sections.len() == text.sections.len();
+        if !same_len || !copy.sections.iter().zip(&text.sections).all(same) {
+            **copy = DecorationNode::text(color, text);
+        }
+    }
+    fn color(line: &Line, text: &Text) -> Color {
+        let text_color = text.sections.first().map(|s| s.style.color);
+        line.color.or(text_color).unwrap_or(Color::WHITE)
+    }
+    fn spawn(&self, text: &Text, visibility: Visibility, cmds: &mut Commands) -> Entity {
+        let style = self.style(text);
+        match self {
+            DecorationNode::Copy { color, .. } => {
+                let text = DecorationNode::text(*color, text);
+                let copy = TextBundle { text, style, visibility, ..default() };
+                cmds.spawn(copy).id()
+            }
+            DecorationNode::Line { line, .. } => {
+                let background_color = DecorationNode::color(line, text).into();
+                let line = NodeBundle { style, background_color, visibility, ..default() };
+                cmds.spawn(line).id()
+            }
+        }
+    }
+    /// Update `entity` to draw this node, returns `false` if `entity` is not
+    /// the same kind of node.
+    fn update(&self, entity: Entity, section: &DecorationSectionItem, nodes: &mut Nodes) -> bool {
+        let Ok((copy, mut style, mut background, mut visibility)) = nodes.get_mut(entity) else {
+            return false;
+        };
+        let text = section.text;
+        match (self, copy) {
+            (DecorationNode::Copy { color, .. }, Some(mut copy)) => {
+                DecorationNode::update_text(*color, text, &mut copy);
+            }
+            (DecorationNode::Line { line, .. }, None) => {
+                let color = DecorationNode::color(line, text);
+                if background.0 != color {
+                    background.0 = color;
+                }
+            }
+            _ => return false,
+        }
+        let new_style = self.style(text);
+        if *style != new_style {
+            *style = new_style;
+        }
+        if *visibility != *section.visibility {
+            *visibility = *section.visibility;
+        }
+        true
+    }
+}
+
+type Nodes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static mut Text>,
+        &'static mut Style,
+        &'static mut BackgroundColor,
+        &'static mut Visibility,
+    ),
+    Without<Decorations>,
+>;
+#[derive(WorldQuery)]
+pub struct DecorationSection {
+    entity: Entity,
+    decorations: &'static Decorations,
+    text: &'static Text,
+    visibility: &'static Visibility,
+    overlay: Option<&'static Overlay>,
+    nodes: Option<&'static DecorationNodes>,
+}
+type ChangedSection = Or<(Changed<Decorations>, Changed<Text>, Changed<Visibility>)>;
+
+/// Keep the entities drawing [`Decorations`] in sync with their section's
+/// text.
+pub fn update_decorations(
+    mut cmds: Commands,
+    sections: Query<DecorationSection, ChangedSection>,
+    mut nodes: Nodes,
+) {
+    for section in &sections {
+        let new_nodes = section.decorations.nodes();
+        let spawned = section.nodes.map_or(&[][..], |s| &s.0);
+
+        let mut update = |(node, &e): (&DecorationNode, _)| node.update(e, &section, &mut nodes);
+        if spawned.len() == new_nodes.len() && new_nodes.iter().zip(spawned).all(&mut update) {
+            continue;
+        }
+        // The kind of decorations changed, spawn them again.
+        for &decoration in spawned {
+            cmds.entity(decoration).despawn_recursive();
+        }
+        let (text, visibility) = (section.text, *section.visibility);
+        let spawn = |node: &DecorationNode| node.spawn(text, visibility, &mut cmds);
+        let spawned: Vec<_> = new_nodes.iter().map(spawn).collect();
+
+        let (copies, lines) = spawned.split_at(new_nodes.iter().filter(|n| n.is_copy()).count());
+        let overlay = overlay(section.entity, section.overlay, &mut cmds);
+        cmds.entity(overlay)
+            .insert_children(0, copies)
+            .push_children(lines);
+        cmds.entity(section.entity).insert(DecorationNodes(spawned));
+    }
+}
+
+/// Parse the value of the `Underline` and `Strike` modifiers:
+/// `thickness` or `(thickness, color)`.
+pub(crate) fn parse_line(input: &str) -> Result<(f32, Option<Color>), color::Error> {
+    let input = parameters(input);
+    let (thickness, color) = match input.split_once(',') {
+        Some((thickness, line_color)) => (thickness, Some(color::parse(line_color)?)),
+        None => (input, None),
+    };
+    Ok((thickness.trim().parse()?, color))
+}
+/// Parse the value of the `Outline` modifier: `thickness` or `(thickness, color)`.
+pub(crate) fn parse_outline(input: &str) -> Result<(f32, Color), color::Error> {
+    let (thickness, color) = parse_line(input)?;
+    Ok((thickness, color.unwrap_or(OUTLINE_COLOR)))
+}
+/// Parse the value of the `Shadow` modifier: `(x, y)` or `(x, y, color)`.
+pub(crate) fn parse_shadow(input: &str) -> Result<(f32, f32, Color), color::Error> {
+    let input = parameters(input);
+    let (x, rest) = input.split_once(',').unwrap_or((input, "0"));
+    let (y, color) = parse_line(rest)?;
+    Ok((x.trim().parse()?, y, color.unwrap_or(SHADOW_COLOR)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADOW: Shadow = Shadow { offset: Vec2::ONE, color: Color::BLACK };
+    const UNDERLINE: Line = Line { thickness: 2.0, color: None };
+
+    fn shadow() -> Decorations {
+        Decorations { shadow: Some(SHADOW), ..default() }
+    }
+    /// A world with a line of two sections, after a first update of their
+    /// decorations.
+    fn decorations_world(decorations: [Decorations; 2]) -> (World, Schedule, Entity, [Entity; 2]) {
+        let mut world = World::new();
+        let mut spawn = |(value, decorations)| {
+            let text = Text::from_section(value, default());
+            let section = TextBundle { text, ..default() };
+            world.spawn((section, decorations)).id()
+        };
+        let [first, second] = decorations;
+        let sections = [spawn(("first", first)), spawn(("second", second))];
+        let mut line = world.spawn(NodeBundle::default());
+        let line = line.push_children(&sections).id();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(update_decorations);
+        schedule.run(&mut world);
+        world.clear_trackers();
+        (world, schedule, line, sections)
+    }
+    fn children(world: &World, entity: Entity) -> Vec<Entity> {
+        let children = world.get::<Children>(entity);
+        children.map_or(Vec::new(), |c| c.to_vec())
+    }
+    fn nodes(world: &World, section: Entity) -> Vec<Entity> {
+        let nodes = world.get::<DecorationNodes>(section);
+        nodes.map_or(Vec::new(), |n| n.0.clone())
+    }
+    fn text(world: &World, entity: Entity) -> (&str, Color) {
+        let section = &world.get::<Text>(entity).unwrap().sections[0];
+        (&section.value, section.style.color)
+    }
+
+    #[test]
+    fn spawn_decorations() {
+        let underlined = Decorations { underline: Some(UNDERLINE), ..shadow() };
+        let (world, _, line, [first, second]) = decorations_world([underlined, default()]);
+
+        let Some(&Overlay(overlay)) = world.get::<Overlay>(first) else {
+            panic!("decorated sections are wrapped in an overlay");
+        };
+        assert_eq!(children(&world, line), [overlay, second]);
+        let [copy, underline] = nodes(&world, first)[..] else {
+            panic!("a section with a shadow and an underline has two decorations");
+        };
+        assert_eq!(children(&world, overlay), [copy, first, underline]);
+        assert_eq!(text(&world, copy), ("first", Color::BLACK));
+        assert!(world.get::<Text>(underline).is_none());
+
+        assert!(world.get::<Overlay>(second).is_none());
+        assert!(nodes(&world, second).is_empty());
+    }
+    #[test]
+    fn update_changed_copy() {
+        let (mut world, mut schedule, _, [first, second]) = decorations_world([shadow(), shadow()]);
+        let copies = [nodes(&world, first), nodes(&world, second)];
+        let [[first_copy], [second_copy]] = copies.map(|nodes| <[_; 1]>::try_from(nodes).unwrap());
+        world.get_mut::<Text>(first).unwrap().sections[0].value = "changed".to_owned();
+        schedule.run(&mut world);
+
+        assert_eq!(nodes(&world, first), [first_copy]);
+        assert_eq!(text(&world, first_copy), ("changed", Color::BLACK));
+
+        let (last_tick, tick) = (world.last_change_tick(), world.read_change_tick());
+        let second_copy = world.entity(second_copy);
+        let ticks = second_copy.get_change_ticks::<Text>().unwrap();
+        assert!(!ticks.is_changed(last_tick, tick));
+    }
+    #[test]
+    fn remove_decorations() {
+        let decorations = [shadow(), default()];
+        let (mut world, mut schedule, line, [first, second]) = decorations_world(decorations);
+        let [copy] = nodes(&world, first)[..] else {
+            panic!("a section with a shadow has a single decoration");
+        };
+        *world.get_mut::<Decorations>(first).unwrap() = default();
+        schedule.run(&mut world);
+
+        assert!(world.get_entity(copy).is_none());
+        assert!(nodes(&world, first).is_empty());
+        let &Overlay(overlay) = world.get::<Overlay>(first).unwrap();
+        assert_eq!(children(&world, overlay), [first]);
+        assert_eq!(children(&world, line), [overlay, second]);
+    }
+}
@@ -0,0 +1,238 @@
+//! Font families, with the `Family`, `Bold` and `Italic` modifiers.
+//!
+//! A font family groups the regular, bold, italic and bold-italic fonts of a
+//! typeface under a name. The `Bold` and `Italic` modifiers find the family
+//! of the section's current font and pick the matching face, so they compose:
+//! `Bold` inside `Italic` is bold-italic.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+/// A style of a [`FontFamily`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontFace {
+    #[default]
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+}
+impl FontFace {
+    pub const fn new(bold: bool, italic: bool) -> Self {
+        match (bold, italic) {
+            (false, false) => FontFace::Regular,
+            (true, false) => FontFace::Bold,
+            (false, true) => FontFace::Italic,
+            (true, true) => FontFace::BoldItalic,
+        }
+    }
+    pub const fn is_bold(self) -> bool {
+        matches!(self, FontFace::Bold | FontFace::BoldItalic)
+    }
+    pub const fn is_italic(self) -> bool {
+        matches!(self, FontFace::Italic | FontFace::BoldItalic)
+    }
+    /// This face, bold if `bold` is `true`, not bold otherwise.
+    pub const fn with_bold(self, bold: bool) -> Self {
+        FontFace::new(bold, self.is_italic())
+    }
+    /// This face, italic if `italic` is `true`, not italic otherwise.
+    pub const fn with_italic(self, italic: bool) -> Self {
+        FontFace::new(self.is_bold(), italic)
+    }
+    /// The faces to try in order when looking for this face in a family.
+    const fn fallbacks(self) -> &'static [FontFace] {
+        use FontFace::{Bold, BoldItalic, Italic, Regular};
+        match self {
+            Regular => &[Regular],
+            Bold => &[Bold, Regular],
+            Italic => &[Italic, Regular],
+            BoldItalic => &[BoldItalic, Bold, Italic, Regular],
+        }
+    }
+}
+
+/// The fonts of a typeface, by [`FontFace`].
+///
+/// ```rust,ignore
+/// let fira = FontFamily::new(assets.load("fonts/FiraSans-Regular.ttf"))
+///     .with_bold(assets.load("fonts/FiraSans-Bold.ttf"))
+///     .with_italic(assets.load("fonts/FiraSans-Italic.ttf"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FontFamily {
+    pub regular: Option<Handle<Font>>,
+    pub bold: Option<Handle<Font>>,
+    pub italic: Option<Handle<Font>>,
+    pub bold_italic: Option<Handle<Font>>,
+}
+impl FontFamily {
+    pub fn new(regular: Handle<Font>) -> Self {
+        FontFamily { regular: Some(regular), ..default() }
+    }
+    pub fn with_bold(mut self, bold: Handle<Font>) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+    pub fn with_italic(mut self, italic: Handle<Font>) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+    pub fn with_bold_italic(mut self, bold_italic: Handle<Font>) -> Self {
+        self.bold_italic = Some(bold_italic);
+        self
+    }
+    /// The font of `face`, `None` if this family doesn't have it.
+    pub fn get(&self, face: FontFace) -> Option<&Handle<Font>> {
+        match face {
+            FontFace::Regular => self.regular.as_ref(),
+            FontFace::Bold => self.bold.as_ref(),
+            FontFace::Italic => self.italic.as_ref(),
+            FontFace::BoldItalic => self.bold_italic.as_ref(),
+        }
+    }
+    /// The font of `face`, or of the closest face this family has.
+    ///
+    /// A missing bold-italic font falls back to bold, then italic, then
+    /// regular. Bold and italic fall back to regular.
+    pub fn closest(&self, face: FontFace) -> Option<&Handle<Font>> {
+        face.fallbacks().iter().find_map(|&face| self.get(face))
+    }
+    fn faces(&self) -> impl Iterator<Item = (FontFace, &Handle<Font>)> {
+        use FontFace::{Bold, BoldItalic, Italic, Regular};
+        let face = move |face| Some((face, self.get(face)?));
+        let faces = [Regular, Bold, Italic, BoldItalic];
+        faces.into_iter().filter_map(face)
+    }
+}
+
+/// Error when selecting a font with the `Family`, `Bold` and `Italic` modifiers.
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("No font family named `{0}` in `FontFamilies`")]
+    Unknown(String),
+    #[error("The `{0}` font family has no font, not even a regular one")]
+    Empty(String),
+    #[error(
+        "Can't make the current font {0:?}, it is not part of a font family. \
+        Use the `Family` modifier before `Bold` or `Italic`."
+    )]
+    NotInFamily(FontFace),
+}
+
+/// The font families used by the `Family`, `Bold` and `Italic` modifiers,
+/// by name.
+///
+/// ```rust,ignore
+/// fn setup(mut families: ResMut<FontFamilies>, assets: Res<AssetServer>) {
+///     let fira = FontFamily::new(assets.load("fonts/FiraSans-Regular.ttf"))
+///         .with_bold(assets.load("fonts/FiraSans-Bold.ttf"));
+///     families.insert("fira", fira);
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct FontFamilies {
+    families: HashMap<String, FontFamily>,
+    /// The font of each family and face in `families`.
+    ///
+    /// The same font may be part of several families, or be several faces
+    /// of a family.
+    faces: BTreeMap<(String, FontFace), Handle<Font>>,
+}
+impl FontFamilies {
+    /// Use `family` when `{Family: name}` is in a format string.
+    pub fn insert(&mut self, name: impl Into<String>, family: FontFamily) {
+        let name = name.into();
+        self.faces.retain(|(family, _), _| *family != name);
+        for (face, font) in family.faces() {
+            self.faces.insert((name.clone(), face), font.clone());
+        }
+        self.families.insert(name, family);
+    }
+    pub fn get(&self, name: &str) -> Option<&FontFamily> {
+        self.families.get(name)
+    }
+    /// The family name and face of `font`, if it is part of a family.
+    ///
+    /// When `font` is part of several families, the first family by name is
+    /// used, when it is several faces, the first face in [`FontFace`] order.
+    pub fn face_of(&self, font: &Handle<Font>) -> Option<(&str, FontFace)> {
+        let ((family, face), _) = self.faces.iter().find(|(_, f)| *f == font)?;
+        Some((family, *face))
+    }
+    /// The font of `family` closest to `face`.
+    pub fn font(&self, family: &str, face: FontFace) -> Result<Handle<Font>, Error> {
+        let fonts = self.get(family);
+        let fonts = fonts.ok_or_else(|| Error::Unknown(family.to_owned()))?;
+        let font = fonts.closest(face).cloned();
+        font.ok_or_else(|| Error::Empty(family.to_owned()))
+    }
+    /// The font of the family of `font` closest to `change` of its face.
+    pub fn with_face(
+        &self,
+        font: &Handle<Font>,
+        change: impl FnOnce(FontFace) -> FontFace,
+    ) -> Result<Handle<Font>, Error> {
+        match self.face_of(font) {
+            Some((family, face)) => self.font(family, change(face)),
+            None => Err(Error::NotInFamily(change(FontFace::Regular))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::HandleId;
+
+    use super::*;
+
+    fn font() -> Handle<Font> {
+        Handle::weak(HandleId::random::<Font>())
+    }
+
+    #[test]
+    fn compose_faces() {
+        let face = FontFace::Italic.with_bold(true);
+        assert_eq!(face, FontFace::BoldItalic);
+        assert_eq!(face.with_italic(false), FontFace::Bold);
+        assert_eq!(face.with_bold(false).with_italic(false), FontFace::Regular);
+    }
+    #[test]
+    fn fallback_faces() {
+        let (regular, italic) = (font(), font());
+        let mut families = FontFamilies::default();
+        let serif = FontFamily::new(regular.clone()).with_italic(italic.clone());
+        families.insert("serif", serif);
+
+        let bold_italic = families.with_face(&italic, |f| f.with_bold(true));
+        assert_eq!(bold_italic, Ok(italic.clone()));
+        let bold = families.with_face(&regular, |f| f.with_bold(true));
+        assert_eq!(bold, Ok(regular.clone()));
+        let not_italic = families.with_face(&italic, |f| f.with_italic(false));
+        assert_eq!(not_italic, Ok(regular));
+    }
+    #[test]
+    fn shared_fonts() {
+        let (regular, bold) = (font(), font());
+        let mut families = FontFamilies::default();
+        let sans = FontFamily::new(regular.clone()).with_bold(bold.clone());
+        families.insert("sans", sans.clone());
+        families.insert("ui", FontFamily::new(regular.clone()));
+
+        families.insert("sans", sans.with_italic(font()));
+        assert_eq!(families.face_of(&bold), Some(("sans", FontFace::Bold)));
+        families.insert("sans", FontFamily::new(font()));
+        assert_eq!(families.face_of(&regular), Some(("ui", FontFace::Regular)));
+        assert_eq!(families.face_of(&bold), None);
+    }
+    #[test]
+    fn missing_family() {
+        let families = FontFamilies::default();
+        let error = families.font("mono", FontFace::Bold);
+        assert_eq!(error, Err(Error::Unknown("mono".to_owned())));
+        let error = families.with_face(&font(), |f| f.with_bold(true));
+        assert_eq!(error, Err(Error::NotInFamily(FontFace::Bold)));
+    }
+}
@@ -0,0 +1,161 @@
+//! Display images inline with text, with the `Image` and `Icon` modifiers.
+//!
+//! Each section of a rich text is its own entity with `cresustext`, so a
+//! section can be drawn as an image instead of text.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::modifiers::{get_asset, warn_once};
+
+/// Images used with the `Icon` modifier, by name.
+///
+/// `bevy_ui` can't draw a region of a texture atlas, so each icon is a
+/// separate image.
+///
+/// ```rust,ignore
+/// fn setup(mut icons: ResMut<Icons>, assets: Res<AssetServer>) {
+///     icons.insert("fire", assets.load("icons/fire.png"));
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct Icons(HashMap<String, Handle<Image>>);
+impl Icons {
+    /// Use `image` when `{Icon: name}` is in a format string.
+    pub fn insert(&mut self, name: impl Into<String>, image: Handle<Image>) {
+        self.0.insert(name.into(), image);
+    }
+    pub fn get(&self, name: &str) -> Option<&Handle<Image>> {
+        self.0.get(name)
+    }
+}
+
+/// Get the images displayed by the `Image` and `Icon` modifiers.
+#[derive(Default, Clone, Copy)]
+pub struct GetImage<'a> {
+    images: Option<&'a Assets<Image>>,
+    server: Option<&'a AssetServer>,
+    icons: Option<&'a Icons>,
+}
+impl<'a> GetImage<'a> {
+    pub fn new(images: &'a Assets<Image>, icons: &'a Icons) -> Self {
+        let (images, icons) = (Some(images), Some(icons));
+        GetImage { images, icons, server: None }
+    }
+    /// Check with `server` that images exist in [`GetImage::get`].
+    pub fn with_server(self, server: &'a AssetServer) -> Self {
+        GetImage { server: Some(server), ..self }
+    }
+    /// The image at `path`, it must be loaded first through `AssetServer`.
+    ///
+    /// Returns `None` and logs a warning once per `path` if the image doesn't
+    /// exist, for example when `path` has a typo.
+    pub fn get(&self, path: &str) -> Option<Handle<Image>> {
+        let image = get_asset(self.images?, self.server, path);
+        if image.is_none() {
+            warn_once(format!("The image `{path}` doesn't exist or wasn't loaded"));
+        }
+        image
+    }
+    /// The image registered as `name` in [`Icons`].
+    pub fn icon(&self, name: &str) -> Option<Handle<Image>> {
+        self.icons?.get(name).cloned()
+    }
+}
+
+/// The image displayed in place of a section's text, set by the `Image`
+/// and `Icon` modifiers.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SectionImage {
+    pub image: Option<Handle<Image>>,
+    /// Height of the image relative to the section's font size.
+    pub size: f32,
+}
+impl Default for SectionImage {
+    fn default() -> Self {
+        SectionImage { image: None, size: 1.0 }
+    }
+}
+
+/// How far the baseline is from the bottom of a line, relative to the
+/// font size.
+///
+/// This is an approximation, the actual value depends on the font.
+pub(crate) const DESCENT: f32 = 0.2;
+
+/// The text of a section while it is displayed as an image.
+#[derive(Component)]
+pub(crate) struct HiddenText(Vec<String>);
+
+type ImageSection = Or<(Changed<SectionImage>, With<UiImage>)>;
+type ImageQuery<'a> = (
+    Entity,
+    &'a SectionImage,
+    Option<&'a UiImage>,
+    Option<&'a mut HiddenText>,
+    &'a mut Text,
+    &'a mut Style,
+    &'a mut BackgroundColor,
+);
+
+/// Display the [`SectionImage`] of sections, sized relative to their font
+/// size and aligned with the baseline of the text next to them.
+///
+/// The section's text is hidden while it displays an image, and restored
+/// when the image is removed.
+pub fn update_section_images(
+    mut cmds: Commands,
+    images: Res<Assets<Image>>,
+    mut sections: Query<ImageQuery, ImageSection>,
+) {
+    for (entity, section_image, ui_image, hidden, mut text, mut style, mut background) in
+        &mut sections
+    {
+        let Some(image) = &section_image.image else {
+            if ui_image.is_some() {
+                cmds.entity(entity).remove::<(UiImage, HiddenText)>();
+                background.0 = Color::NONE;
+                style.size = Size::AUTO;
+                style.margin = UiRect::default();
+            }
+            if let Some(hidden) = hidden {
+                for (section, value) in text.sections.iter_mut().zip(&hidden.0) {
+                    section.value = value.clone();
+                }
+            }
+            continue;
+        };
+        // Modifiers may write to the text after it was hidden, so we check
+        // every frame.
+        if text.sections.iter().any(|s| !s.value.is_empty()) {
+            let take = |s: &mut TextSection| std::mem::take(&mut s.value);
+            let values = text.sections.iter_mut().map(take).collect();
+            if let Some(mut hidden) = hidden {
+                hidden.0 = values;
+            } else {
+                cmds.entity(entity).insert(HiddenText(values));
+            }
+        }
+        let font_size = text.sections.first().map_or(0.0, |s| s.style.font_size);
+        let image_size = images.get(image).map_or(Vec2::ONE, Image::size);
+        let height = font_size * section_image.size;
+        let width = height * image_size.x / image_size.y.max(1.0);
+
+        let new_style = Style {
+            size: Size::new(Val::Px(width), Val::Px(height)),
+            align_self: AlignSelf::FlexEnd,
+            margin: UiRect::bottom(Val::Px(font_size * DESCENT)),
+            ..style.clone()
+        };
+        if *style != new_style {
+            *style = new_style;
+        }
+        if background.0 != Color::WHITE {
+            background.0 = Color::WHITE;
+        }
+        if ui_image.map(|i| &i.texture) != Some(image) {
+            cmds.entity(entity).insert(UiImage::new(image.clone()));
+        }
+    }
+}
@@ -11,12 +11,17 @@ use bevy::{
     text::{BreakLineOn, Font, TextAlignment},
 };
 use bevy_fab::trait_extensions::AppStylesExtension;
-use bevy_fab::{BevyModify, FabPlugin, LocalBindings, ParseFormatString};
-use fab_parse::{Split, Styleable};
+use bevy_fab::{BevyModify, FabPlugin, LocalBindings, ParseFormatString, Scopes};
+use enumset::EnumSet;
+use fab::{binding::BindingKey, modify::FieldsOf};
+use fab_parse::{Split, Styleable, Syntax, Tags};
 
+use crate::font::FontFamilies;
+#[cfg(feature = "cresustext")]
+use crate::image::{GetImage, Icons};
 #[cfg(feature = "cresustext")]
 use crate::modifiers::ModifierQuery;
-use crate::modifiers::{GetFont, Modifier};
+use crate::modifiers::{parse_pair, GetFont, Modifier, ModifierField, TextContext};
 
 #[derive(Clone, Copy)]
 pub struct TextGlobalStyle {
@@ -36,30 +41,117 @@ impl Default for TextGlobalStyle {
 #[derive(SystemParam)]
 pub struct WorldBindings<'w, 's> {
     bindings: Res<'w, bevy_fab::WorldBindings<Modifier>>,
+    scopes: Scopes<'w, 's, Modifier>,
     context: Res<'w, Assets<Font>>,
+    server: Res<'w, AssetServer>,
+    time: Option<Res<'w, Time>>,
+    families: Res<'w, FontFamilies>,
     _p: PhantomData<&'s ()>,
 }
 #[derive(SystemParam)]
 pub struct WorldBindingsMut<'w, 's> {
     bindings: ResMut<'w, bevy_fab::WorldBindings<Modifier>>,
     #[cfg(feature = "cresustext")]
+    scopes: Scopes<'w, 's, Modifier>,
+    #[cfg(feature = "cresustext")]
     items: Query<'w, 's, ModifierQuery>,
     #[cfg(feature = "cresustext")]
     context: Res<'w, Assets<Font>>,
+    #[cfg(feature = "cresustext")]
+    server: Res<'w, AssetServer>,
+    #[cfg(feature = "cresustext")]
+    time: Option<Res<'w, Time>>,
+    #[cfg(feature = "cresustext")]
+    families: Res<'w, FontFamilies>,
+    #[cfg(feature = "cresustext")]
+    images: Res<'w, Assets<Image>>,
+    #[cfg(feature = "cresustext")]
+    icons: Res<'w, Icons>,
     _p: PhantomData<&'s ()>,
 }
 impl<'w, 's> WorldBindingsMut<'w, 's> {
+    /// The [`BindingKey`] of `name`, store it to set bindings without looking
+    /// up their name.
+    pub fn key(&mut self, name: &str) -> BindingKey<Modifier> {
+        self.bindings.bindings.key(name)
+    }
+    /// Set a named content binding. This will mark it as changed.
     pub fn set_content(&mut self, key: &str, value: &impl fmt::Display) {
-        let Some(modifier) = self.bindings.bindings.get_mut(key) else {
-            self.bindings.bindings.set(key, value.to_string().into());
+        let key = self.key(key);
+        self.set_content_key(key, value);
+    }
+    /// Like [`WorldBindingsMut::set_content`], but with a [`BindingKey`].
+    pub fn set_content_key(&mut self, key: BindingKey<Modifier>, value: &impl fmt::Display) {
+        let bindings = &mut self.bindings.bindings;
+        let Some(modifier) = bindings.get_key_mut(key) else {
+            bindings.set_key(key, value.to_string().into());
             return;
         };
         modifier.set_content(format_args!("{value}"));
     }
+    /// Serialize all world bindings, keyed by binding name.
+    ///
+    /// This fails if a binding is a [`Modifier::Dynamic`].
+    #[cfg(feature = "serde")]
+    pub fn save<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.bindings.bindings, serializer)
+    }
+    /// Set the bindings serialized with [`WorldBindingsMut::save`].
+    ///
+    /// Loaded bindings are marked as changed.
+    #[cfg(feature = "serde")]
+    pub fn load<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        self.bindings.bindings.load(deserializer)
+    }
+}
+/// Despawn the children of `entity` holding its [`Sections`].
+///
+/// [`Sections`]: crate::modifiers::Sections
+#[cfg(feature = "cresustext")]
+fn despawn_sections(entity: Entity, world: &mut World) {
+    use crate::modifiers::Sections;
+
+    let Some(mut text) = world.get_entity_mut(entity) else { return; };
+    let Some(sections) = text.take::<Sections>() else { return; };
+    let child_of_text = |section| {
+        let mut child = section;
+        loop {
+            let parent = world.get::<Parent>(child)?.get();
+            if parent == entity {
+                return Some(child);
+            }
+            child = parent;
+        }
+    };
+    let mut lines: Vec<_> = sections.iter().filter_map(|&s| child_of_text(s)).collect();
+    lines.dedup();
+    for line in lines {
+        world.entity_mut(line).despawn_recursive();
+    }
+}
+pub(crate) fn text_context<'a>(
+    fonts: &'a Assets<Font>,
+    server: &'a AssetServer,
+    families: &'a FontFamilies,
+    time: Option<&Time>,
+) -> TextContext<'a> {
+    let time = time.map_or(0.0, Time::elapsed_seconds_wrapped);
+    let fonts = GetFont::new(fonts).with_families(families);
+    let fonts = fonts.with_server(server);
+    TextContext {
+        fonts,
+        time,
+        #[cfg(feature = "cresustext")]
+        images: default(),
+    }
 }
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct RichText {
+    entity: Entity,
     inner: &'static mut LocalBindings<Modifier>,
     #[cfg(feature = "richtext")]
     text: &'static mut Text,
@@ -67,28 +159,47 @@ pub struct RichText {
     children: Option<&'static crate::modifiers::Sections>,
 }
 impl RichTextItem<'_> {
-    /// Update `to_update` with updated values from `world` and `self`-local bindings.
+    /// Update `to_update` with updated values from `world`, the
+    /// [`ScopedBindings`] of this entity's ancestors and `self`-local bindings.
     ///
     /// Only the relevant sections of `to_update` are updated. The change trackers
     /// are then reset.
+    ///
+    /// [`ScopedBindings`]: bevy_fab::ScopedBindings
     #[cfg(feature = "richtext")]
     pub fn update(&mut self, world: &WorldBindings) {
-        let fonts = GetFont::new(&world.context);
-        self.inner.update(&mut self.text, &world.bindings, &fonts);
+        let (fonts, server, time) = (&world.context, &world.server, world.time.as_deref());
+        let context = text_context(fonts, server, &world.families, time);
+        let bindings = &world.bindings;
+        let scopes = world.scopes.ancestors_of(self.entity);
+        self.inner.update(&mut self.text, bindings, &scopes, &context);
     }
-    /// Update `to_update` with updated values from `world` and `self`-local bindings.
+    /// Update `to_update` with updated values from `world`, the
+    /// [`ScopedBindings`] of this entity's ancestors and `self`-local bindings.
     ///
     /// Only the relevant sections of `to_update` are updated. The change trackers
     /// are then reset.
+    ///
+    /// [`ScopedBindings`]: bevy_fab::ScopedBindings
     #[cfg(feature = "cresustext")]
-    pub fn update(&mut self, world: WorldBindingsMut) {
-        let fonts = GetFont::new(&world.context);
-        let mut items = bevy_fab::Items::new(self.children, world.items);
-        self.inner.update(&mut items, &world.bindings, &fonts);
+    pub fn update(&mut self, mut world: WorldBindingsMut) {
+        let (fonts, server, time) = (&world.context, &world.server, world.time.as_deref());
+        let context = text_context(fonts, server, &world.families, time);
+        let images = GetImage::new(&world.images, &world.icons).with_server(server);
+        let context = TextContext { images, ..context };
+        let mut items = bevy_fab::Items::new(self.children, &mut world.items);
+        let bindings = &world.bindings;
+        let scopes = world.scopes.ancestors_of(self.entity);
+        self.inner.update(&mut items, bindings, &scopes, &context);
     }
     pub fn set(&mut self, key: &str, value: Modifier) {
         self.inner.bindings.set(key, value);
     }
+    /// Like [`RichTextItem::set`], but with a [`BindingKey`], see
+    /// [`fab::binding::Local::get_key`].
+    pub fn set_key(&mut self, key: BindingKey<Modifier>, value: Modifier) {
+        self.inner.bindings.set_key(key, value);
+    }
     /// Set a named content binding. This will mark it as changed.
     pub fn set_content(&mut self, key: &str, value: &impl fmt::Display) {
         let Some(modifier) = self.inner.bindings.get_mut(key) else {
@@ -97,6 +208,15 @@ impl RichTextItem<'_> {
         };
         modifier.set_content(format_args!("{value}"));
     }
+    /// Like [`RichTextItem::set_content`], but with a [`BindingKey`].
+    pub fn set_content_key(&mut self, key: BindingKey<Modifier>, value: &impl fmt::Display) {
+        let bindings = &mut self.inner.bindings;
+        let Some(modifier) = bindings.get_key_mut(key) else {
+            bindings.set_key(key, value.to_string().into());
+            return;
+        };
+        modifier.set_content(format_args!("{value}"));
+    }
 }
 #[derive(Bundle)]
 pub struct MakeRichText {
@@ -109,10 +229,9 @@ impl MakeRichText {
         let default_text = default();
         #[cfg(feature = "cresustext")]
         let default_text = {
-            let default_section = TextSection::default();
-            let mut default_text = Text::default();
-            default_text.sections.push(default_section);
-            (default(), default_text)
+            let mut default_item: <Modifier as fab::Modify>::MakeItem = default();
+            default_item.1.sections.push(TextSection::default());
+            default_item
         };
         let inner = ParseFormatString::new(format_string.into(), default_text, default());
         MakeRichText { inner, text_bundle: default() }
@@ -126,6 +245,14 @@ impl MakeRichText {
         *style_field = style;
         self
     }
+    /// Returns this [`MakeRichText`] with its format string written in `syntax`,
+    /// such as markdown or BBCode.
+    ///
+    /// Markup tags become the modifiers they are mapped to in [`crate::MarkupTags`].
+    pub fn with_syntax(mut self, syntax: Syntax) -> Self {
+        self.inner.syntax = syntax;
+        self
+    }
     /// Returns this [`MakeRichText`] with a new [`TextAlignment`] on [`Text`].
     pub fn with_text_alignment(mut self, alignment: TextAlignment) -> Self {
         let extras = self.inner.items_extra.as_mut().unwrap();
@@ -147,7 +274,22 @@ impl MakeRichText {
 }
 
 impl BevyModify for Modifier {
-    type Param = SRes<Assets<Font>>;
+    #[cfg(feature = "richtext")]
+    type Param = (
+        SRes<Assets<Font>>,
+        Option<SRes<Time>>,
+        SRes<FontFamilies>,
+        SRes<AssetServer>,
+    );
+    #[cfg(feature = "cresustext")]
+    type Param = (
+        SRes<Assets<Font>>,
+        Option<SRes<Time>>,
+        SRes<FontFamilies>,
+        SRes<AssetServer>,
+        SRes<Assets<Image>>,
+        SRes<Icons>,
+    );
     type ItemsCtorData = TextGlobalStyle;
 
     fn set_content(&mut self, s: fmt::Arguments) {
@@ -163,8 +305,39 @@ impl BevyModify for Modifier {
         Modifier::content(s.to_string().into())
     }
 
+    #[cfg(feature = "richtext")]
     fn context<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Context<'a> {
-        GetFont::new(param)
+        let (fonts, time, families, server) = param;
+        text_context(fonts, server, families, time.as_deref())
+    }
+    #[cfg(feature = "cresustext")]
+    fn context<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Context<'a> {
+        let (fonts, time, families, server, images, icons) = param;
+        let images = GetImage::new(images, icons).with_server(server);
+        let context = text_context(fonts, server, families, time.as_deref());
+        TextContext { images, ..context }
+    }
+    fn context_changes(param: &SystemParamItem<Self::Param>) -> FieldsOf<Self> {
+        let changed = |is_changed: bool, field: ModifierField| match is_changed {
+            true => EnumSet::only(field),
+            false => EnumSet::empty(),
+        };
+        let time = param.1.as_ref().is_some_and(|time| time.is_changed());
+        // Font families registered after the text is spawned should apply.
+        let families = param.2.is_changed();
+        // Icons added after the text is spawned should show up.
+        #[cfg(feature = "cresustext")]
+        let icons = changed(param.5.is_changed(), ModifierField::ContextImages);
+        #[cfg(feature = "richtext")]
+        let icons = EnumSet::empty();
+
+        changed(time, ModifierField::ContextTime)
+            | changed(families, ModifierField::ContextFonts)
+            | icons
+    }
+    #[cfg(feature = "cresustext")]
+    fn changed_components(fields: FieldsOf<Self>) -> u32 {
+        crate::modifiers::item_components(fields)
     }
 
     #[cfg(feature = "richtext")]
@@ -178,7 +351,7 @@ impl BevyModify for Modifier {
     #[cfg(feature = "cresustext")]
     fn spawn_items(
         extra: &TextGlobalStyle,
-        sections: Vec<(bevy_layout_offset::UiOffset, Text)>,
+        sections: Vec<Self::MakeItem>,
         cmds: &mut EntityCommands,
     ) {
         use crate::modifiers::Sections;
@@ -207,22 +380,39 @@ impl BevyModify for Modifier {
 
         let mut entities = Vec::with_capacity(sections.len());
 
+        // Lines are never empty, so that `despawn_items` finds all of them
+        // from `Sections`.
+        let mut lines = vec![Vec::new()];
+        for section in sections {
+            let ends_line = line_ending(&section.1);
+            lines.last_mut().unwrap().push(section);
+            if ends_line {
+                lines.push(Vec::new());
+            }
+        }
+        lines.retain(|line| !line.is_empty());
+
         cmds.insert(vertical());
         cmds.with_children(|cmds| {
-            let mut line_cmds = cmds.spawn(horizontal());
-
-            for (offset, text) in sections {
-                let ends_line = line_ending(&text);
-
-                line_cmds.with_children(|line_cmds| {
-                    entities.push(line_cmds.spawn(text_bundle(text)).insert(offset).id());
+            for line in lines {
+                cmds.spawn(horizontal()).with_children(|line_cmds| {
+                    for (offset, text, timing, image, decorations, link, tooltip) in line {
+                        let text = text_bundle(text);
+                        let section = (text, offset, timing, image, decorations, link, tooltip);
+                        entities.push(line_cmds.spawn(section).id());
+                    }
                 });
-                if ends_line {
-                    line_cmds = cmds.spawn(horizontal());
-                }
             }
         });
-        cmds.insert(Sections(entities.into_boxed_slice()));
+        cmds.insert(Sections::new(entities.clone()));
+        cmds.add(crate::typewriter::hide_unrevealed(entities));
+    }
+    /// Despawn the lines holding the [`Sections`], keeping other children.
+    ///
+    /// [`Sections`]: crate::modifiers::Sections
+    #[cfg(feature = "cresustext")]
+    fn despawn_items(cmds: &mut EntityCommands) {
+        cmds.add(despawn_sections);
     }
     fn add_update_system(app: &mut App) {
         use bevy::prelude::CoreSet::PostUpdate;
@@ -236,6 +426,35 @@ impl BevyModify for Modifier {
     }
 }
 
+/// Parse the `value` or `(value, phase)` of the `name` style, `phase` offsets
+/// the phase or seed each character gets from the style.
+///
+/// Bad values are replaced by `(0, 0)` with a warning, rather than failing
+/// the whole format string.
+fn chop_pair(name: &str, input: &str) -> (f32, f32) {
+    parse_pair(input).unwrap_or_else(|err| {
+        warn!("The `{name}` style expects `value` or `(value, phase)`, got `{input}`: {err}");
+        (0.0, 0.0)
+    })
+}
+
+/// Phase difference between two consecutive characters of the `Wave` style.
+#[cfg(feature = "cresustext")]
+const WAVE_CHAR_PHASE: f32 = 0.1;
+
+/// The chopper of the `Type` styles, the value multiplies the typewriter speed.
+#[cfg(feature = "cresustext")]
+fn type_speed(speed: &str, _: usize) -> Modifier {
+    Modifier::reveal_speed(speed.parse().unwrap_or(1.0))
+}
+/// The chopper of the `Pause` style, it pauses the typewriter before the
+/// first word of each `Pause` section.
+#[cfg(feature = "cresustext")]
+fn pause_first_word(seconds: &str, i: usize, _: usize) -> Modifier {
+    let seconds = if i == 0 { seconds.parse().unwrap_or(0.0) } else { 0.0 };
+    Modifier::reveal_pause(seconds)
+}
+
 fn default_styles(tree: Styleable<Modifier>) -> Styleable<Modifier> {
     use Split::{ByChar, ByWord};
 
@@ -248,13 +467,52 @@ fn default_styles(tree: Styleable<Modifier>) -> Styleable<Modifier> {
         .curve_chop(ByWord, "Sine", sin_curve.to_curve(), |ampl: &mut f32, t| {
             let size_change = (20.0 + t * *ampl).floor();
             Modifier::font_size(size_change)
+        })
+        .indexed_chop(ByChar, "RainbowCycle", |input, i, count| {
+            let (speed, phase) = chop_pair("RainbowCycle", input);
+            Modifier::rainbow_cycle(speed, phase + i as f32 * 360.0 / count as f32)
         });
     #[cfg(feature = "cresustext")]
-    let tree = tree.split(Split::ByLine);
+    let tree = tree
+        .indexed_chop(ByChar, "Wave", |input, i, _| {
+            let (amplitude, phase) = chop_pair("Wave", input);
+            Modifier::wave(amplitude, phase + i as f32 * WAVE_CHAR_PHASE)
+        })
+        .indexed_chop(ByChar, "Shake", |input, i, _| {
+            let (intensity, seed) = chop_pair("Shake", input);
+            Modifier::shake(intensity, seed + i as f32)
+        })
+        .indexed_chop(ByChar, "Jitter", |input, i, _| {
+            let (intensity, seed) = chop_pair("Jitter", input);
+            Modifier::jitter(intensity, seed + i as f32)
+        })
+        .chop(ByChar, "Type", type_speed)
+        .chop(ByWord, "TypeWords", type_speed)
+        .indexed_chop(ByWord, "Pause", pause_first_word)
+        .split(Split::ByLine);
 
     tree
 }
 
+/// Markup tags of the BBCode and markdown syntaxes.
+///
+/// The `b` and `i` tags only work with fonts of a [`crate::FontFamilies`].
+fn default_tags(tags: &mut Tags) {
+    tags.insert("color", "Color")
+        .insert("size", "RelSize")
+        .insert("font", "Font")
+        .insert("family", "Family")
+        .insert_with_value("b", "Bold", "true")
+        .insert_with_value("i", "Italic", "true");
+    #[cfg(feature = "cresustext")]
+    tags.insert("url", "Link")
+        .insert("tooltip", "Tooltip")
+        .insert("icon", "Icon")
+        .insert("img", "Image")
+        .insert_with_value("u", "Underline", "1")
+        .insert_with_value("s", "Strike", "1");
+}
+
 /// Plugin to add to get `RichText` stuff working, it wouldn't otherwise, you silly goose.
 pub struct RichTextPlugin {
     fab: FabPlugin<Modifier>,
@@ -280,10 +538,254 @@ impl Default for RichTextPlugin {
 impl Plugin for RichTextPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         self.fab.build(app);
+        app.init_resource::<FontFamilies>();
         if self.default_styles {
             app.add_style(default_styles);
+            default_tags(&mut app.world.resource_mut::<crate::MarkupTags>());
             #[cfg(feature = "cresustext")]
             app.add_plugin(bevy_layout_offset::OffsetPlugin);
         }
+        #[cfg(feature = "cresustext")]
+        app.add_event::<crate::TypewriterRevealed>()
+            .add_event::<crate::TypewriterFinished>()
+            .add_event::<crate::RichTextLinkClicked>()
+            .init_resource::<Icons>()
+            .init_resource::<crate::TooltipStyle>()
+            .add_system(crate::typewriter::reveal_system)
+            .add_systems((
+                crate::link::section_interaction,
+                crate::link::link_system,
+                crate::tooltip::tooltip_system,
+            ))
+            .add_systems(
+                (
+                    crate::image::update_section_images,
+                    crate::decoration::update_decorations,
+                )
+                    .in_base_set(CoreSet::PostUpdate)
+                    .before(bevy::ui::UiSystem::Flex),
+            );
+    }
+}
+
+#[cfg(all(test, feature = "cresustext"))]
+mod tests {
+    use bevy::ecs::{event::ManualEventReader, system::CommandQueue};
+    use bevy::{core::TaskPoolOptions, time::TimePlugin};
+    use bevy_fab::BindingChanged;
+    use bevy_layout_offset::UiOffset;
+    use fab::binding::Scope;
+
+    use super::*;
+    use crate::modifiers::Sections;
+    use crate::trait_extensions::EntityCommandsTextBindExtension;
+    use crate::{image::SectionImage, MakeRichText};
+
+    type Bindings = bevy_fab::WorldBindings<Modifier>;
+    type Reader = ManualEventReader<BindingChanged<Modifier>>;
+    type ScopedBindings = bevy_fab::ScopedBindings<Modifier>;
+
+    fn rich_text_app() -> App {
+        let task_pool_options = TaskPoolOptions::with_num_threads(4);
+        let mut app = App::new();
+        app.add_plugin(TaskPoolPlugin { task_pool_options })
+            .add_plugin(TypeRegistrationPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(TimePlugin)
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Font>()
+            .add_asset::<Image>()
+            .add_plugin(RichTextPlugin::no_default_styles());
+        app
+    }
+    fn contents(world: &mut World, text: Entity) -> Vec<String> {
+        let sections = world.get::<Sections>(text).unwrap().to_vec();
+        let text = |&section: &Entity| world.get::<Text>(section).unwrap();
+        let content = |s| text(s).sections[0].value.clone();
+        sections.iter().map(content).collect()
+    }
+
+    #[test]
+    fn parallel_update() {
+        let mut app = rich_text_app();
+        let texts: Vec<_> = (0..64)
+            .map(|_| app.world.spawn(MakeRichText::new("Health: {health}")).id())
+            .collect();
+        app.update();
+
+        let mut bindings = app.world.resource_mut::<Bindings>();
+        let health = Modifier::content("42".into());
+        bindings.bindings.set("health", health).unwrap();
+        app.update();
+
+        for text in texts {
+            assert_eq!(contents(&mut app.world, text), ["Health: ", "42"]);
+        }
+    }
+
+    #[test]
+    fn update_only_changes_touched_components() {
+        type Refs<'w, 's> = Query<'w, 's, (Ref<'static, Text>, Ref<'static, UiOffset>)>;
+        fn changed(In(sections): In<Vec<Entity>>, query: Refs) -> Vec<(bool, bool)> {
+            let changed = |(text, offset): (Ref<Text>, Ref<UiOffset>)| {
+                (text.is_changed(), offset.is_changed())
+            };
+            let changed_at = |&section: &Entity| changed(query.get(section).unwrap());
+            sections.iter().map(changed_at).collect()
+        }
+        let mut app = rich_text_app();
+        let make_text = MakeRichText::new("Health: {Color:{color}|high}");
+        let text = app.world.spawn(make_text).id();
+        app.update();
+        let mut changed = IntoSystem::into_system(changed);
+        changed.initialize(&mut app.world);
+        let mut set_color = |color: Color| {
+            let mut bindings = app.world.resource_mut::<Bindings>();
+            let color = Modifier::color(color);
+            bindings.bindings.set("color", color).unwrap();
+            app.update();
+            let sections = app.world.get::<Sections>(text).unwrap().to_vec();
+            changed.run(sections, &mut app.world)
+        };
+        set_color(Color::RED);
+
+        assert_eq!(set_color(Color::RED), [(false, false), (false, false)]);
+        assert_eq!(set_color(Color::BLUE), [(false, false), (true, false)]);
+    }
+
+    fn changed_bindings(app: &mut App, reader: &mut Reader) -> Vec<(&'static str, Option<Entity>)> {
+        app.update();
+        let world = &app.world;
+        let bindings = &world.resource::<Bindings>().bindings;
+        let name = |event: &BindingChanged<Modifier>| {
+            let names = ["health", "mood"];
+            let is_event = |name: &&str| bindings.get_key(name).ok() == Some(event.binding);
+            names.into_iter().find(is_event).unwrap()
+        };
+        let events = reader.iter(world.resource::<Events<BindingChanged<Modifier>>>());
+        events.map(|event| (name(event), event.entity)).collect()
+    }
+
+    #[test]
+    fn binding_changed_events() {
+        let content = |value: &str| Modifier::content(value.to_owned().into());
+        let mut app = rich_text_app();
+        let mut reader = Reader::default();
+        let text = app.world.spawn(MakeRichText::new("{health}{mood}")).id();
+        let scope = app.world.spawn(ScopedBindings::default()).id();
+        app.update();
+        let bindings = &mut app.world.resource_mut::<Bindings>().into_inner().bindings;
+        let (health, mood) = (bindings.key("health"), bindings.key("mood"));
+
+        let set_health = |app: &mut App| {
+            let bindings = &mut app.world.resource_mut::<Bindings>().into_inner().bindings;
+            bindings.set_key(health, content("42"));
+        };
+        set_health(&mut app);
+        assert_eq!(changed_bindings(&mut app, &mut reader), [("health", None)]);
+        set_health(&mut app);
+        assert_eq!(changed_bindings(&mut app, &mut reader), []);
+
+        let local = app.world.get_mut::<LocalBindings<Modifier>>(text).unwrap();
+        local.into_inner().bindings.set_key(mood, content("happy"));
+        let local_event = ("mood", Some(text));
+        assert_eq!(changed_bindings(&mut app, &mut reader), [local_event]);
+
+        fn scoped(app: &mut App, scope: Entity) -> &mut Scope<Modifier> {
+            let scoped = app.world.get_mut::<ScopedBindings>(scope).unwrap();
+            &mut scoped.into_inner().bindings
+        }
+        scoped(&mut app, scope).set_key(health, content("7"));
+        let scoped_event = ("health", Some(scope));
+        assert_eq!(changed_bindings(&mut app, &mut reader), [scoped_event]);
+
+        // The sent value is forgotten with the binding, setting it again
+        // sends an event.
+        scoped(&mut app, scope).remove(health);
+        assert_eq!(changed_bindings(&mut app, &mut reader), []);
+        scoped(&mut app, scope).set_key(health, content("7"));
+        assert_eq!(changed_bindings(&mut app, &mut reader), [scoped_event]);
+    }
+
+    #[derive(Component, Reflect)]
+    struct Health(u32);
+    impl fmt::Display for Health {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    #[test]
+    fn bound_components_are_scoped() {
+        let mut app = rich_text_app();
+        let mut queue = CommandQueue::default();
+        let mut cmds = Commands::new(&mut queue, &app.world);
+        let mut spawn_enemy = |health| {
+            let mut enemy = cmds.spawn(Health(health));
+            enemy.bind_component::<Health>("hp");
+            let mut text = None;
+            let make_text = MakeRichText::new("HP: {hp}");
+            enemy.with_children(|enemy| text = Some(enemy.spawn(make_text).id()));
+            (enemy.id(), text.unwrap())
+        };
+        let (goblin, goblin_text) = spawn_enemy(10);
+        let (_, troll_text) = spawn_enemy(50);
+        queue.apply(&mut app.world);
+        // Hooks and the text update system are not ordered, a hooked value
+        // may only show up in the following update.
+        app.update();
+        app.update();
+        assert_eq!(contents(&mut app.world, goblin_text), ["HP: ", "10"]);
+        assert_eq!(contents(&mut app.world, troll_text), ["HP: ", "50"]);
+
+        app.world.get_mut::<Health>(goblin).unwrap().0 = 3;
+        app.update();
+        app.update();
+        assert_eq!(contents(&mut app.world, goblin_text), ["HP: ", "3"]);
+        assert_eq!(contents(&mut app.world, troll_text), ["HP: ", "50"]);
+    }
+
+    #[test]
+    fn despawn_sections_keeps_other_children() {
+        let mut world = World::new();
+        let section = world.spawn_empty().id();
+        let overlay = world.spawn_empty().push_children(&[section]).id();
+        let line = world.spawn_empty().push_children(&[overlay]).id();
+        let user_child = world.spawn_empty().id();
+        let sections = Sections::new(vec![section]);
+        let mut text = world.spawn(sections);
+        let text = text.push_children(&[line, user_child]).id();
+
+        despawn_sections(text, &mut world);
+
+        assert!(world.get_entity(line).is_none());
+        assert!(world.get_entity(section).is_none());
+        assert!(world.get::<Sections>(text).is_none());
+        let children = world.get::<Children>(text).unwrap();
+        assert_eq!(&children[..], &[user_child]);
+    }
+    #[test]
+    fn missing_assets() {
+        let mut app = rich_text_app();
+        let present = bevy::asset::HandleId::from("present.png");
+        let mut images = app.world.resource_mut::<Assets<Image>>();
+        images.set_untracked(present, Image::default());
+
+        let text = "{Font: typo.ttf|font} {Image: typo.png|missing} {Image: present.png|image}";
+        let text = app.world.spawn(MakeRichText::new(text)).id();
+        app.update();
+
+        let sections = app.world.get::<Sections>(text).unwrap().to_vec();
+        let font = |&section: &Entity| {
+            let text = app.world.get::<Text>(section).unwrap();
+            text.sections[0].style.font.clone()
+        };
+        let image = |section| {
+            let image = app.world.get::<SectionImage>(section).unwrap();
+            image.image.clone()
+        };
+        assert_eq!(font(&sections[0]), Handle::default());
+        assert_eq!(image(sections[2]), None);
+        assert_eq!(image(sections[4]).map(|image| image.id()), Some(present));
     }
 }
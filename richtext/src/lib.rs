@@ -113,27 +113,65 @@ compile_error!(
 );
 
 mod color;
+#[cfg(feature = "cresustext")]
+mod decoration;
+mod font;
+#[cfg(feature = "cresustext")]
+mod image;
 mod integration;
+#[cfg(feature = "cresustext")]
+mod link;
 pub mod modifiers;
+#[cfg(feature = "cresustext")]
+mod tooltip;
+#[cfg(feature = "cresustext")]
+mod typewriter;
 
 /// See [`bevy_fab::UserFmt`] docs.
 pub type UserFmt = bevy_fab::UserFmt<Modifier>;
 /// See [`bevy_fab::Styles`] docs.
 pub type Styles = bevy_fab::Styles<Modifier>;
+/// See [`bevy_fab::BindingChanged`] docs.
+pub type BindingChanged = bevy_fab::BindingChanged<Modifier>;
+/// See [`bevy_fab::ScopedBindings`] docs.
+pub type ScopedBindings = bevy_fab::ScopedBindings<Modifier>;
+/// See [`bevy_fab::MarkupTags`] docs.
+pub type MarkupTags = bevy_fab::MarkupTags<Modifier>;
 
-pub use bevy_fab::{FmtSystem, IntoFmtSystem, ReflectQueryable};
+#[cfg(feature = "style_sheet")]
+pub use bevy_fab::StyleSheet;
+pub use bevy_fab::{FmtSystem, IntoFmtSystem, ReflectQueryable, WriteBack, WriteBackError};
 pub use fab::binding::{Entry, Id};
+pub use fab_parse::Syntax;
+
+/// See [`fab::binding::BindingKey`] docs.
+pub type BindingKey = fab::binding::BindingKey<Modifier>;
+#[cfg(feature = "cresustext")]
+pub use decoration::{Decorations, Line, Outline, Shadow};
+pub use font::{FontFace, FontFamilies, FontFamily};
+#[cfg(feature = "cresustext")]
+pub use image::{GetImage, Icons, SectionImage};
 #[cfg(not(feature = "cresustext"))]
 pub use integration::WorldBindings;
 pub use integration::{
     MakeRichText, RichText, RichTextFetch, RichTextItem, RichTextPlugin, WorldBindingsMut,
 };
-pub use modifiers::{GetFont, Modifier};
+#[cfg(feature = "cresustext")]
+pub use link::{RichTextLinkClicked, SectionLink};
+pub use modifiers::{GetFont, Modifier, TextContext};
+#[cfg(feature = "cresustext")]
+pub use tooltip::{SectionTooltip, TooltipStyle};
+#[cfg(feature = "cresustext")]
+pub use typewriter::{RevealTiming, Typewriter, TypewriterFinished, TypewriterRevealed};
 
 pub mod trait_extensions {
+    use std::fmt;
+
+    use bevy::prelude::{Component, FromWorld, Resource};
     use bevy::reflect::Reflect;
     use bevy_fab::{
-        trait_extensions::{AppFormattersExtension, AppStylesExtension},
+        trait_extensions::EntityCommandsBindExtension,
+        trait_extensions::{AppBindExtension, AppFormattersExtension, AppStylesExtension},
         FmtSystem, IntoFmtSystem,
     };
     use fab::binding::Entry;
@@ -157,6 +195,12 @@ pub mod trait_extensions {
         {
             AppStylesExtension::add_style(self, style)
         }
+        /// Load the [`StyleSheet`](crate::StyleSheet) at `path`, and apply it
+        /// before the styles added with `add_style`.
+        #[cfg(feature = "style_sheet")]
+        fn add_style_sheet(&mut self, path: &str) -> &mut Self {
+            AppStylesExtension::add_style_sheet(self, path)
+        }
     }
     impl<T: AppStylesExtension<Modifier>> AppTextStylesExtension for T {}
 
@@ -195,4 +239,38 @@ pub mod trait_extensions {
         }
     }
     impl<T: AppFormattersExtension<Modifier>> AppTextFormattersExtension for T {}
+
+    /// Explicit [`AppBindExtension`] for this crate's [`Modifier`]
+    pub trait AppTextBindExtension: AppBindExtension<Modifier> {
+        /// Update the `name` binding with `fmt` each time the `R` resource changes.
+        fn bind_resource<R: Resource + Reflect + FromWorld>(
+            &mut self,
+            name: impl AsRef<str>,
+            fmt: impl Fn(&R, Entry<Modifier>) + Send + Sync + 'static,
+        ) -> &mut Self {
+            AppBindExtension::bind_resource(self, name, fmt)
+        }
+    }
+    impl<T: AppBindExtension<Modifier>> AppTextBindExtension for T {}
+
+    /// Explicit [`EntityCommandsBindExtension`] for this crate's [`Modifier`]
+    pub trait EntityCommandsTextBindExtension: EntityCommandsBindExtension<Modifier> {
+        /// Update the `name` binding with the `C` component of this entity.
+        fn bind_component<C>(&mut self, name: impl Into<String>) -> &mut Self
+        where
+            C: Component + Reflect + fmt::Display,
+        {
+            EntityCommandsBindExtension::<Modifier>::bind_component::<C>(self, name)
+        }
+        /// Update the `name` binding with `fmt` each time the `C` component
+        /// of this entity changes.
+        fn bind_component_with<C: Component + Reflect>(
+            &mut self,
+            name: impl Into<String>,
+            fmt: impl Fn(&C, Entry<Modifier>) + Send + Sync + 'static,
+        ) -> &mut Self {
+            EntityCommandsBindExtension::bind_component_with(self, name, fmt)
+        }
+    }
+    impl<T: EntityCommandsBindExtension<Modifier>> EntityCommandsTextBindExtension for T {}
 }
@@ -0,0 +1,357 @@
+//! Clickable links in rich text, with the `Link` modifier.
+//!
+//! Each section of a rich text is its own UI node with `cresustext`, so
+//! sections with a link get an [`Interaction`]. Consecutive sections with the
+//! same link, such as the characters of a link chopped by `Split::ByChar`,
+//! are a single link: they are highlighted and clicked together.
+
+use std::borrow::Cow;
+
+use bevy::{ecs::query::WorldQuery, prelude::*};
+use bevy_layout_offset::UiOffset;
+use fab::modify::{MakeItem, Modify};
+
+use crate::decoration::{self, Decorations, Overlay};
+use crate::font::FontFamilies;
+use crate::image::{GetImage, Icons, SectionImage};
+use crate::integration::text_context;
+use crate::modifiers::{Modifier, Sections, TextContext};
+use crate::tooltip::SectionTooltip;
+use crate::typewriter::RevealTiming;
+use crate::Styles;
+
+/// The link of a section, set by the `Link` modifier.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct SectionLink(pub Option<Cow<'static, str>>);
+
+/// A link of a rich text was clicked.
+#[derive(Debug)]
+pub struct RichTextLinkClicked {
+    /// The entity of the rich text.
+    pub entity: Entity,
+    /// The value of the `Link` modifier, `quest_42` in `{Link: quest_42|the old tower}`.
+    pub link: String,
+}
+
+/// The modifiers applied to a link while it is hovered: the `LinkHover` alias.
+///
+/// They are applied to a copy of the link's sections drawn over them, so
+/// only their effect on the text and decorations of sections is visible.
+const LINK_HOVER: &str = "{LinkHover: _|_}";
+
+/// The modifiers of the `LinkHover` alias, hovered links are yellow if it is
+/// not defined.
+fn hover_modifiers(styles: &mut Styles) -> Vec<Modifier> {
+    let Ok(modifiers) = styles.static_modifiers(LINK_HOVER) else {
+        return vec![Modifier::color(Color::YELLOW)];
+    };
+    let not_content = |m: &Modifier| !matches!(m, Modifier::Content { .. });
+    modifiers.into_iter().filter(not_content).collect()
+}
+
+/// The entity drawing the `LinkHover` style of a section, a sibling drawn
+/// over it.
+#[derive(Component)]
+pub struct LinkHighlight(Entity);
+
+type InteractionQuery<'a> = (
+    Entity,
+    &'a SectionLink,
+    &'a SectionTooltip,
+    Option<&'a Interaction>,
+);
+type ChangedInteractive = Or<(Changed<SectionLink>, Changed<SectionTooltip>)>;
+
+/// Add an [`Interaction`] to sections with a link or a tooltip, so that they
+/// can be hovered and clicked.
+pub fn section_interaction(
+    mut cmds: Commands,
+    sections: Query<InteractionQuery, ChangedInteractive>,
+) {
+    for (entity, link, tooltip, interaction) in &sections {
+        let interactive = link.0.is_some() || tooltip.0.is_some();
+        match (interactive, interaction) {
+            (true, None) => {
+                cmds.entity(entity).insert(Interaction::None);
+            }
+            (false, Some(_)) => {
+                cmds.entity(entity).remove::<Interaction>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(WorldQuery)]
+pub struct LinkSection {
+    entity: Entity,
+    offset: &'static UiOffset,
+    text: Ref<'static, Text>,
+    timing: &'static RevealTiming,
+    image: &'static SectionImage,
+    decorations: Ref<'static, Decorations>,
+    link: &'static SectionLink,
+    tooltip: &'static SectionTooltip,
+    interaction: Option<Ref<'static, Interaction>>,
+    highlight: Option<&'static LinkHighlight>,
+    overlay: Option<&'static Overlay>,
+}
+impl LinkSectionItem<'_> {
+    fn is_hovered(&self) -> bool {
+        let hovered = |i: &Ref<Interaction>| **i != Interaction::None;
+        self.interaction.as_ref().is_some_and(hovered)
+    }
+    fn is_clicked(&self) -> bool {
+        let clicked = |i: &Ref<Interaction>| i.is_changed() && **i == Interaction::Clicked;
+        self.interaction.as_ref().is_some_and(clicked)
+    }
+    /// The text and decorations of this section with `hover` applied.
+    fn highlighted(&self, hover: &[Modifier], ctx: &TextContext) -> (Text, Decorations) {
+        let mut highlighted = (
+            *self.offset,
+            self.text.clone(),
+            *self.timing,
+            self.image.clone(),
+            self.decorations.clone(),
+            self.link.clone(),
+            self.tooltip.clone(),
+        );
+        for modifier in hover {
+            if let Err(error) = modifier.apply(ctx, highlighted.as_item()) {
+                warn!("Error when applying link hover modifier {modifier:?}: {error}");
+            }
+        }
+        let (_, text, _, _, mut decorations, ..) = highlighted;
+        // Avoid drawing the section's decorations twice.
+        if decorations == *self.decorations {
+            decorations = default();
+        }
+        (text, decorations)
+    }
+}
+
+/// Highlight hovered links with the `LinkHover` alias, and send
+/// [`RichTextLinkClicked`] when they are clicked.
+///
+/// Links are only checked when the [`Interaction`] of a section changes.
+pub fn link_system(
+    mut cmds: Commands,
+    texts: Query<(Entity, &Sections)>,
+    interactions: Query<(), (Changed<Interaction>, With<SectionLink>)>,
+    sections: Query<LinkSection>,
+    highlighted: Query<LinkSection, With<LinkHighlight>>,
+    mut highlights: Query<(&mut Text, &mut Decorations), Without<SectionLink>>,
+    mut styles: ResMut<Styles>,
+    mut hover: Local<Option<Vec<Modifier>>>,
+    fonts: Res<Assets<Font>>,
+    server: Res<AssetServer>,
+    time: Option<Res<Time>>,
+    families: Res<FontFamilies>,
+    images: Res<Assets<Image>>,
+    icons: Res<Icons>,
+    mut clicked: EventWriter<RichTextLinkClicked>,
+) {
+    let hover_changed = hover.is_none() || styles.is_changed();
+    if hover_changed {
+        *hover = Some(hover_modifiers(styles.bypass_change_detection()));
+    }
+    let hover = hover.as_deref().unwrap_or_default();
+
+    let images = GetImage::new(&images, &icons).with_server(&server);
+    let ctx = text_context(&fonts, &server, &families, time.as_deref());
+    let ctx = TextContext { images, ..ctx };
+
+    // Keep the highlights of hovered links in sync with their section.
+    for section in &highlighted {
+        let changed = section.text.is_changed() || section.decorations.is_changed();
+        if !changed && !hover_changed {
+            continue;
+        }
+        let Some(&LinkHighlight(highlight)) = section.highlight else { continue; };
+        let Ok((mut text, mut decorations)) = highlights.get_mut(highlight) else { continue; };
+        (*text, *decorations) = section.highlighted(hover, &ctx);
+    }
+    if interactions.is_empty() {
+        return;
+    }
+    for (entity, text_sections) in &texts {
+        let link = |&section: &Entity| sections.get(section).ok().and_then(|s| s.link.0.as_ref());
+        let mut i = 0;
+        while let Some(first) = text_sections.get(i) {
+            let Some(name) = link(first) else {
+                i += 1;
+                continue;
+            };
+            let same_link = |s: &&Entity| link(s) == Some(name);
+            let len = text_sections[i..].iter().take_while(same_link).count();
+            let link_sections = &text_sections[i..i + len];
+            i += len;
+
+            let link_sections = sections.iter_many(link_sections).collect::<Vec<_>>();
+            if link_sections.iter().any(LinkSectionItem::is_clicked) {
+                let link = name.to_string();
+                clicked.send(RichTextLinkClicked { entity, link });
+            }
+            let hovered = link_sections.iter().any(LinkSectionItem::is_hovered);
+
+            for section in &link_sections {
+                match (hovered, section.highlight) {
+                    (false, Some(&LinkHighlight(highlight))) => {
+                        cmds.entity(highlight).despawn_recursive();
+                        cmds.entity(section.entity).remove::<LinkHighlight>();
+                    }
+                    (true, None) => {
+                        let (text, decorations) = section.highlighted(hover, &ctx);
+                        let highlight = cmds.spawn((highlight_bundle(text), decorations)).id();
+                        cmds.entity(section.entity).insert(LinkHighlight(highlight));
+                        let overlay =
+                            decoration::overlay(section.entity, section.overlay, &mut cmds);
+                        cmds.entity(overlay).add_child(highlight);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+fn highlight_bundle(text: Text) -> TextBundle {
+    let style = Style {
+        position_type: PositionType::Absolute,
+        position: UiRect { left: Val::Px(0.0), top: Val::Px(0.0), ..default() },
+        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+        ..default()
+    };
+    TextBundle { text, style, ..default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::core::TaskPoolPlugin;
+
+    use super::*;
+
+    fn link_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(TaskPoolPlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Font>()
+            .add_asset::<Image>()
+            .init_resource::<Styles>()
+            .init_resource::<FontFamilies>()
+            .init_resource::<Icons>()
+            .add_event::<RichTextLinkClicked>()
+            .add_systems((section_interaction, link_system));
+        app
+    }
+    /// Spawn a rich text with a section per link in `links`, on a single line.
+    fn spawn_text(app: &mut App, links: &[Option<&'static str>]) -> (Entity, Vec<Entity>) {
+        let world = &mut app.world;
+        let mut spawn = |link: &Option<&'static str>| {
+            let text = Text::from_section("link", default());
+            let link = SectionLink(link.map(Cow::Borrowed));
+            let section = (
+                TextBundle { text, ..default() },
+                UiOffset::default(),
+                RevealTiming::default(),
+                SectionImage::default(),
+                Decorations::default(),
+                link,
+                SectionTooltip::default(),
+            );
+            world.spawn(section).id()
+        };
+        let sections: Vec<_> = links.iter().map(&mut spawn).collect();
+        let mut line = world.spawn(NodeBundle::default());
+        let line = line.push_children(&sections).id();
+        let mut text = world.spawn(Sections::new(sections.clone()));
+        let text = text.add_child(line).id();
+        app.update();
+        (text, sections)
+    }
+    fn set_interaction(app: &mut App, section: Entity, interaction: Interaction) {
+        *app.world.get_mut::<Interaction>(section).unwrap() = interaction;
+        app.update();
+    }
+    fn highlight(app: &App, section: Entity) -> Option<Entity> {
+        app.world.get::<LinkHighlight>(section).map(|h| h.0)
+    }
+    fn clicked(app: &mut App) -> Vec<(Entity, String)> {
+        let mut events = app.world.resource_mut::<Events<RichTextLinkClicked>>();
+        events.drain().map(|e| (e.entity, e.link)).collect()
+    }
+
+    #[test]
+    fn interactive_sections() {
+        let mut app = link_app();
+        let (_, sections) = spawn_text(&mut app, &[Some("quest"), None]);
+        assert!(app.world.get::<Interaction>(sections[0]).is_some());
+        assert!(app.world.get::<Interaction>(sections[1]).is_none());
+    }
+    #[test]
+    fn hover_link_sections() {
+        let mut app = link_app();
+        let quest = Some("quest");
+        let (_, sections) = spawn_text(&mut app, &[quest, quest, None, quest, Some("map")]);
+
+        set_interaction(&mut app, sections[1], Interaction::Hovered);
+        let highlights: Vec<_> = sections.iter().map(|&s| highlight(&app, s)).collect();
+        let highlighted: Vec<_> = highlights.iter().map(Option::is_some).collect();
+        assert_eq!(highlighted, [true, true, false, false, false]);
+
+        // The highlight is drawn over the section, a sibling in its overlay.
+        let line = app.world.get::<Parent>(sections[0]).unwrap().get();
+        let line = app.world.get::<Parent>(line).unwrap().get();
+        for (i, &section) in sections[..2].iter().enumerate() {
+            let overlay = app.world.get::<Parent>(section).unwrap().get();
+            let overlay_children = app.world.get::<Children>(overlay).unwrap();
+            assert_eq!(overlay_children[..], [section, highlights[i].unwrap()]);
+
+            let line_children = app.world.get::<Children>(line).unwrap();
+            assert_eq!(line_children[i], overlay);
+        }
+        let highlight = app.world.get::<Text>(highlights[0].unwrap()).unwrap();
+        assert_eq!(highlight.sections[0].value, "link");
+        assert_eq!(highlight.sections[0].style.color, Color::YELLOW);
+
+        set_interaction(&mut app, sections[1], Interaction::None);
+        for (section, highlight) in sections[..2].iter().zip(highlights) {
+            assert_eq!(self::highlight(&app, *section), None);
+            assert!(app.world.get_entity(highlight.unwrap()).is_none());
+        }
+    }
+    #[test]
+    fn link_hover_style() {
+        let mut app = link_app();
+        let hover = [("Color", "cyan"), ("Underline", "1")];
+        let styles = Styles::new(move |tree| tree.alias("LinkHover", |_| hover));
+        app.insert_resource(styles);
+        let (_, sections) = spawn_text(&mut app, &[Some("quest")]);
+
+        set_interaction(&mut app, sections[0], Interaction::Hovered);
+        let highlight = highlight(&app, sections[0]).unwrap();
+        let text = app.world.get::<Text>(highlight).unwrap();
+        assert_eq!(text.sections[0].style.color, Color::CYAN);
+        let decorations = app.world.get::<Decorations>(highlight).unwrap();
+        assert_eq!(decorations.underline.map(|line| line.thickness), Some(1.0));
+    }
+    #[test]
+    fn click_links() {
+        let mut app = link_app();
+        let links = [Some("quest"), Some("quest"), Some("map")];
+        let (text, sections) = spawn_text(&mut app, &links);
+
+        set_interaction(&mut app, sections[0], Interaction::Hovered);
+        assert!(clicked(&mut app).is_empty());
+
+        set_interaction(&mut app, sections[1], Interaction::Clicked);
+        assert_eq!(clicked(&mut app), [(text, "quest".to_owned())]);
+
+        // Holding the click doesn't click again.
+        app.update();
+        assert!(clicked(&mut app).is_empty());
+
+        set_interaction(&mut app, sections[1], Interaction::Hovered);
+        set_interaction(&mut app, sections[2], Interaction::Clicked);
+        assert_eq!(clicked(&mut app), [(text, "map".to_owned())]);
+    }
+}
@@ -4,15 +4,25 @@
 mod cresus_impl;
 #[cfg(feature = "richtext")]
 mod rich_impl;
+#[cfg(feature = "serde")]
+mod serialize;
 
-use std::{any::Any, fmt};
+#[cfg(feature = "cresustext")]
+use std::f32::consts::TAU;
+use std::{any::Any, borrow::Cow, collections::BTreeSet, convert::Infallible, fmt};
+use std::{num::ParseFloatError, sync::Mutex};
 
-use bevy::asset::HandleId;
-use bevy::prelude::{Assets, Handle};
+use bevy::asset::{Asset, HandleId, LoadState};
+use bevy::log::warn;
+use bevy::prelude::{AssetServer, Assets, Handle};
 use bevy::text::Font;
 use enumset::EnumSet;
-use fab_parse::{Deps, Parsable};
 
+use crate::font::{FontFace, FontFamilies};
+#[cfg(feature = "cresustext")]
+use crate::image::GetImage;
+#[cfg(feature = "cresustext")]
+pub(crate) use cresus_impl::item_components;
 #[cfg(feature = "cresustext")]
 pub use cresus_impl::{Modifier, ModifierField, ModifierItem, ModifierQuery, Sections};
 #[cfg(feature = "richtext")]
@@ -22,21 +32,162 @@ pub use rich_impl::{Modifier, ModifierField};
 /// own modifiers.
 pub type ModifyBox = Box<dyn TextModify + Send + Sync + 'static>;
 
+/// The messages already logged by [`warn_once`].
+static WARNED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Log `message` as a warning, unless it was already logged.
+///
+/// Modifiers are applied again each time their text changes, this avoids
+/// repeating the same warning about a bad modifier value each time.
+pub(crate) fn warn_once(message: String) {
+    let warned = WARNED.lock();
+    let mut warned = warned.unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !warned.contains(&message) {
+        warn!("{message}");
+        warned.insert(message);
+    }
+}
+/// The handle of the `T` asset at `path`, if it exists in `assets` or
+/// `server` is loading it.
+///
+/// Without `server`, assets not in `assets` are assumed to be loading.
+pub(crate) fn get_asset<T: Asset>(
+    assets: &Assets<T>,
+    server: Option<&AssetServer>,
+    path: &str,
+) -> Option<Handle<T>> {
+    let id = HandleId::from(path);
+    let load_state = server.map_or(LoadState::Loading, |server| server.get_load_state(id));
+    let is_loading = matches!(load_state, LoadState::Loading | LoadState::Loaded);
+    let exists = assets.contains(&Handle::weak(id));
+    (exists || is_loading).then(|| assets.get_handle(id))
+}
+
+/// Get the fonts used by the `Font`, `Family`, `Bold` and `Italic` modifiers.
 #[derive(Default, Clone, Copy)]
-pub struct GetFont<'a>(Option<&'a Assets<Font>>);
+pub struct GetFont<'a> {
+    assets: Option<&'a Assets<Font>>,
+    server: Option<&'a AssetServer>,
+    families: Option<&'a FontFamilies>,
+}
 impl<'a> GetFont<'a> {
     pub fn new(assets: &'a Assets<Font>) -> Self {
-        GetFont(Some(assets))
+        GetFont { assets: Some(assets), ..Default::default() }
     }
-    pub fn get(&self, name: &str) -> Option<Handle<Font>> {
-        self.0.map(|a| a.get_handle(HandleId::from(name)))
+    /// Use `families` for the `Family`, `Bold` and `Italic` modifiers.
+    pub fn with_families(self, families: &'a FontFamilies) -> Self {
+        GetFont { families: Some(families), ..self }
+    }
+    /// Check with `server` that fonts exist in [`GetFont::get`].
+    pub fn with_server(self, server: &'a AssetServer) -> Self {
+        GetFont { server: Some(server), ..self }
+    }
+    /// The font at `path`, it must be loaded first through `AssetServer`.
+    ///
+    /// Returns `None` and logs a warning once per `path` if the font doesn't
+    /// exist, for example when `path` has a typo.
+    pub fn get(&self, path: &str) -> Option<Handle<Font>> {
+        let Some(assets) = self.assets else {
+            warn_once(format!("Can't load font `{path}`, no font assets"));
+            return None;
+        };
+        let font = get_asset(assets, self.server, path);
+        if font.is_none() {
+            warn_once(format!("The font `{path}` doesn't exist or wasn't loaded"));
+        }
+        font
+    }
+    /// Set `font` to the font of the `family` [`FontFamily`], with the same
+    /// face as `font`.
+    ///
+    /// `font` is unchanged if `family` doesn't exist.
+    ///
+    /// [`FontFamily`]: crate::FontFamily
+    pub fn set_family(&self, family: &str, font: &mut Handle<Font>) {
+        let Some(families) = self.families else { return; };
+        let face = families.face_of(font).map(|(_, face)| face);
+        let face = face.unwrap_or(FontFace::Regular);
+        match families.font(family, face) {
+            Ok(family_font) => *font = family_font,
+            Err(error) => warn!("{error}"),
+        }
+    }
+    /// Set `font` to the font of its family with the `change`d face.
+    ///
+    /// When the family doesn't have this face, use the closest one, see
+    /// [`FontFamily::closest`]. `font` is unchanged if it is not part of a
+    /// family.
+    ///
+    /// [`FontFamily::closest`]: crate::FontFamily::closest
+    pub fn set_face(&self, font: &mut Handle<Font>, change: impl FnOnce(FontFace) -> FontFace) {
+        let Some(families) = self.families else { return; };
+        match families.with_face(font, change) {
+            Ok(face_font) => *font = face_font,
+            Err(error) => warn!("{error}"),
+        }
     }
 }
 
+/// The [`fab::Modify::Context`] of [`Modifier`].
+#[derive(Default, Clone, Copy)]
+pub struct TextContext<'a> {
+    pub fonts: GetFont<'a>,
+    #[cfg(feature = "cresustext")]
+    pub images: GetImage<'a>,
+    /// Seconds since startup, wrapped, see [`bevy::time::Time::elapsed_seconds_wrapped`].
+    ///
+    /// Modifiers depending on it are applied again each frame.
+    pub time: f32,
+}
+
+/// How many times per second the `Shake` modifier moves sections.
+#[cfg(feature = "cresustext")]
+const SHAKE_RATE: f32 = 20.0;
+/// How much the `Pulse` modifier scales sections up and down.
+#[cfg(feature = "cresustext")]
+const PULSE_AMPLITUDE: f32 = 0.15;
+
+/// The parameters of a modifier value, without the surrounding parenthesis.
+///
+/// A `,` ends the modifier value in format strings, so modifiers with several
+/// parameters are written between parenthesis: `{Offset: (0, -4)|text}`.
+pub(crate) fn parameters(input: &str) -> &str {
+    let input = input.trim();
+    let inner = input.strip_prefix('(').and_then(|i| i.strip_suffix(')'));
+    inner.unwrap_or(input)
+}
+/// Parse the value of modifiers with two parameters: `first` or `(first, second)`.
+///
+/// When not specified, `second` is `0`.
+pub(crate) fn parse_pair(input: &str) -> Result<(f32, f32), ParseFloatError> {
+    let input = parameters(input);
+    let (first, second) = input.split_once(',').unwrap_or((input, "0"));
+    Ok((first.trim().parse()?, second.trim().parse()?))
+}
+/// `sin` of `time` in cycles per seconds, `phase` is in fraction of a cycle.
+#[cfg(feature = "cresustext")]
+fn oscillate(frequency: f32, phase: f32, time: f32) -> f32 {
+    (TAU * (frequency * time + phase)).sin()
+}
+/// A pseudo-random value in `[-1, 1]`, the same for the same `seed` and `step`.
+#[cfg(feature = "cresustext")]
+fn random_unit(seed: f32, step: u32) -> f32 {
+    let mut hash = seed.to_bits() ^ step.wrapping_mul(0x9e37_79b9);
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+    hash as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
+
 impl fmt::Debug for Modifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Modifier::Font { path } => f.debug_tuple("Font").field(path).finish(),
+            Modifier::Family { family } => f.debug_tuple("Family").field(family).finish(),
+            Modifier::Bold { bold } => f.debug_tuple("Bold").field(bold).finish(),
+            Modifier::Italic { italic } => f.debug_tuple("Italic").field(italic).finish(),
             Modifier::RelSize { relative_size } => {
                 f.debug_tuple("Size^").field(relative_size).finish()
             }
@@ -44,48 +195,61 @@ impl fmt::Debug for Modifier {
             Modifier::Color { statik } => f.debug_tuple("Color").field(statik).finish(),
             Modifier::HueOffset { offset } => f.debug_tuple("Hue>").field(offset).finish(),
             Modifier::Content { statik } => f.debug_tuple("Text").field(statik).finish(),
-            Modifier::Dynamic { boxed, .. } => write!(f, "{boxed:?}"),
-        }
-    }
-}
-impl Parsable for Modifier {
-    type Err = anyhow::Error;
-
-    /// Returns the (depends, changes) field set of modifier named `name`.
-    fn dependencies_of(name: &str) -> Deps<ModifierField> {
-        let mut depends = EnumSet::EMPTY;
-
-        let changes = match name {
-            "Font" => Modifier::font_changes(),
-            "Color" => Modifier::color_changes(),
-            "Content" => Modifier::content_changes(),
-            "FontSize" => Modifier::font_size_changes(),
-            "RelSize" => {
-                depends = Modifier::rel_size_depends();
-                Modifier::rel_size_changes()
+            #[cfg(feature = "cresustext")]
+            Modifier::Wave { amplitude, phase } => write!(f, "Wave({amplitude}, {phase})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::Shake { intensity, seed } => write!(f, "Shake({intensity}, {seed})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::Pulse { frequency, phase } => write!(f, "Pulse({frequency}, {phase})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::RevealSpeed { relative_speed } => {
+                f.debug_tuple("RevealSpeed").field(relative_speed).finish()
             }
-            "HueOffset" => {
-                depends = Modifier::hue_offset_depends();
-                Modifier::hue_offset_changes()
+            #[cfg(feature = "cresustext")]
+            Modifier::RevealPause { seconds } => {
+                f.debug_tuple("RevealPause").field(seconds).finish()
             }
-            _ => return Deps::NoneWithName,
-        };
-        Deps::Some { changes, depends }
-    }
-
-    fn parse(name: &str, input: &str) -> Result<Self, Self::Err> {
-        match name {
-            "Font" => Ok(Modifier::font(input.to_string().into())),
-            "FontSize" => Ok(Modifier::font_size(input.parse()?)),
-            "RelSize" => Ok(Modifier::rel_size(input.parse()?)),
-            "Color" => Ok(Modifier::color(crate::color::parse(input)?)),
-            "HueOffset" => Ok(Modifier::hue_offset(input.parse()?)),
-            "Content" => Ok(Modifier::content(input.to_string().into())),
-            // TODO(err): nice struct instead of anyhow
-            n => Err(anyhow::anyhow!(format!("{n} is not a parseable modifier"))),
+            #[cfg(feature = "cresustext")]
+            Modifier::Offset { x, y } => write!(f, "Offset({x}, {y})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::Rotate { degrees } => f.debug_tuple("Rotate").field(degrees).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::Scale { scale } => f.debug_tuple("Scale").field(scale).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::Jitter { intensity, seed } => write!(f, "Jitter({intensity}, {seed})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::Image { path } => f.debug_tuple("Image").field(path).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::Icon { name } => f.debug_tuple("Icon").field(name).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::ImageSize { size } => f.debug_tuple("ImageSize").field(size).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::Underline { thickness, color } => {
+                write!(f, "Underline({thickness}, {color:?})")
+            }
+            #[cfg(feature = "cresustext")]
+            Modifier::Strike { thickness, color } => {
+                write!(f, "Strike({thickness}, {color:?})")
+            }
+            #[cfg(feature = "cresustext")]
+            Modifier::Outline { thickness, color } => {
+                write!(f, "Outline({thickness}, {color:?})")
+            }
+            #[cfg(feature = "cresustext")]
+            Modifier::Shadow { x, y, color } => write!(f, "Shadow({x}, {y}, {color:?})"),
+            #[cfg(feature = "cresustext")]
+            Modifier::Link { link } => f.debug_tuple("Link").field(link).finish(),
+            #[cfg(feature = "cresustext")]
+            Modifier::Tooltip { tooltip } => f.debug_tuple("Tooltip").field(tooltip).finish(),
+            Modifier::RainbowCycle { speed, phase } => write!(f, "RainbowCycle({speed}, {phase})"),
+            Modifier::Dynamic { boxed, .. } => write!(f, "{boxed:?}"),
         }
     }
 }
+/// Parse a [`Cow`] modifier value, this never fails.
+fn parse_cow(input: &str) -> Result<Cow<'static, str>, Infallible> {
+    Ok(input.to_owned().into())
+}
 
 impl From<String> for Modifier {
     fn from(value: String) -> Self {
@@ -1,44 +1,140 @@
 use std::{borrow::Cow, ops::Deref};
 
 use bevy::prelude::*;
-use bevy_fab::Items;
+use bevy_fab::{Items, UniqueEntities};
 use bevy_layout_offset::UiOffset;
+use enumset::EnumSet;
 use fab::{impl_modify, Modify};
 
-use super::{GetFont, ModifyBox};
+use super::{oscillate, parse_cow, parse_pair, random_unit, GetFont, ModifyBox, TextContext};
+use super::{PULSE_AMPLITUDE, SHAKE_RATE};
+use crate::decoration::{self, Decorations, Line, Outline, Shadow};
+use crate::image::{GetImage, SectionImage};
+use crate::link::SectionLink;
+use crate::tooltip::SectionTooltip;
+use crate::typewriter::RevealTiming;
 
-pub type ModifierQuery = (&'static mut UiOffset, &'static mut Text);
-pub type ModifierItem<'a> = (&'a mut UiOffset, &'a mut Text);
+pub type ModifierQuery = (
+    &'static mut UiOffset,
+    &'static mut Text,
+    &'static mut RevealTiming,
+    &'static mut SectionImage,
+    &'static mut Decorations,
+    &'static mut SectionLink,
+    &'static mut SectionTooltip,
+);
+pub type ModifierItem<'a> = (
+    &'a mut UiOffset,
+    &'a mut Text,
+    &'a mut RevealTiming,
+    &'a mut SectionImage,
+    &'a mut Decorations,
+    &'a mut SectionLink,
+    &'a mut SectionTooltip,
+);
 
-#[derive(Component)]
-pub struct Sections(pub Box<[Entity]>);
+/// The components of [`ModifierItem`] holding `fields`, as a bit set: bit `i`
+/// stands for the `i`th component of the tuple.
+pub(crate) fn item_components(fields: EnumSet<ModifierField>) -> u32 {
+    use ModifierField as F;
+
+    fields.iter().fold(0, |components, field| {
+        components
+            | match field {
+                F::Item00Rotation | F::Item00Scale | F::Item00TranslationY => 1 << 0,
+                F::Item1SectionsAt0StyleColor | F::Item1SectionsAt0StyleFont => 1 << 1,
+                F::Item1SectionsAt0StyleFontSize | F::Item1SectionsAt0Value => 1 << 1,
+                F::Item2Pause | F::Item2Speed => 1 << 2,
+                F::Item3Image | F::Item3Size => 1 << 3,
+                F::Item4Outline | F::Item4Shadow | F::Item4Strike | F::Item4Underline => 1 << 4,
+                F::Item50 => 1 << 5,
+                F::Item60 => 1 << 6,
+                F::ContextFonts | F::ContextTime | F::ContextImages => 0,
+            }
+    })
+}
 
+/// The entities holding each section of a cresustext rich text, in order.
+#[derive(Component)]
+pub struct Sections(Box<[Entity]>);
+impl Sections {
+    /// Create `Sections` from a list of freshly spawned entities.
+    ///
+    /// # Panics
+    ///
+    /// If an entity appears more than once in `entities`.
+    pub(crate) fn new(entities: Vec<Entity>) -> Self {
+        let mut sorted = entities.clone();
+        sorted.sort_unstable();
+        let unique = sorted.windows(2).all(|pair| pair[0] != pair[1]);
+        assert!(unique, "Sections must not contain the same entity twice");
+        Sections(entities.into_boxed_slice())
+    }
+}
 impl Deref for Sections {
     type Target = [Entity];
     fn deref(&self) -> &Self::Target {
         &self.0[..]
     }
 }
+// SAFETY: `Sections` can only be created by `Sections::new`, which checks for
+// duplicates, with the entities spawned for a single rich text.
+unsafe impl UniqueEntities for Sections {}
 
 /// Operations on bevy [`TextSection`]s.
 ///
 /// You can create your own operations. At the cost of storing them as a [`ModifyBox`]
 /// and having to be careful about what you update. You create such a `Modifier`
 /// using [`Modifier::Dynamic`].
-#[impl_modify(cuicui_fab_path = fab, no_derive(Debug))]
+#[impl_modify(
+    cuicui_fab_path = fab,
+    cuicui_fab_parse_path = fab_parse,
+    parsable,
+    no_derive(Debug)
+)]
 #[derive(PartialEq)]
 impl Modify for Modifier {
-    type Context<'a> = GetFont<'a>;
-    type MakeItem = (UiOffset, Text);
+    type Context<'a> = TextContext<'a>;
+    type MakeItem = (
+        UiOffset,
+        Text,
+        RevealTiming,
+        SectionImage,
+        Decorations,
+        SectionLink,
+        SectionTooltip,
+    );
     type Item<'a> = ModifierItem<'a>;
     type Items<'a, 'b, 'c> = Items<'a, 'b, 'c, Sections, ModifierQuery>;
 
     /// Set the font to provided `path`.
-    #[modify(context(get_font), write(.1.sections[0].style.font))]
+    #[modify(context(get_font = .fonts), write_neq(.1.sections[0].style.font))]
+    #[parse(with = parse_cow)]
     pub fn font(path: &Cow<'static, str>, get_font: &GetFont) -> Handle<Font> {
         trace!("Apply =font=: {path:?}");
         get_font.get(path).unwrap_or_default()
     }
+    /// Use the font of the `family` [`FontFamily`], keeping the current face.
+    ///
+    /// [`FontFamily`]: crate::FontFamily
+    #[modify(context(get_font = .fonts), read_write(.1.sections[0].style.font))]
+    #[parse(with = parse_cow)]
+    pub fn family(family: &Cow<'static, str>, get_font: &GetFont, font: &mut Handle<Font>) {
+        trace!("Apply =family=: {family:?}");
+        get_font.set_family(family, font);
+    }
+    /// Use the bold face of the current font family if `bold`, the regular
+    /// face otherwise.
+    #[modify(context(get_font = .fonts), read_write(.1.sections[0].style.font))]
+    pub fn bold(bold: bool, get_font: &GetFont, font: &mut Handle<Font>) {
+        get_font.set_face(font, |face| face.with_bold(bold));
+    }
+    /// Use the italic face of the current font family if `italic`, the
+    /// upright face otherwise.
+    #[modify(context(get_font = .fonts), read_write(.1.sections[0].style.font))]
+    pub fn italic(italic: bool, get_font: &GetFont, font: &mut Handle<Font>) {
+        get_font.set_face(font, |face| face.with_italic(italic));
+    }
     /// Increase the font size relative to the current section.
     #[modify(read_write(.1.sections[0].style.font_size))]
     pub fn rel_size(relative_size: f32, font_size: &mut f32) {
@@ -46,12 +142,13 @@ impl Modify for Modifier {
         *font_size *= relative_size;
     }
     /// Set font size to `size`.
-    #[modify(write(.1.sections[0].style.font_size))]
+    #[modify(write_neq(.1.sections[0].style.font_size))]
     pub fn font_size(size: f32) -> f32 {
         size
     }
     /// Set the color of the [`TextSection`] to `statik`.
-    #[modify(write(.1.sections[0].style.color))]
+    #[modify(write_neq(.1.sections[0].style.color))]
+    #[parse(with = crate::color::parse)]
     pub fn color(statik: Color) -> Color {
         trace!("Apply ~COLOR~: {statik:?}");
         statik
@@ -66,14 +163,175 @@ impl Modify for Modifier {
     }
     /// Set the text content of the [`TextSection`] to `statik`.
     #[modify(write_mut(.1.sections[0].value))]
+    #[parse(with = parse_cow)]
     pub fn content(statik: &Cow<'static, str>, value: &mut String) {
         trace!("Apply $CONTENT$: {statik:?}");
         value.clear();
         value.push_str(statik);
     }
+    /// Move the section by `x` and `y` pixels.
+    ///
+    /// This sets the translation of the section's [`UiOffset`], so it
+    /// overrides other modifiers moving it, such as `Wave`.
+    #[modify(write_neq(.0.0.translation))]
+    #[parse(with = parse_pair)]
+    pub fn offset(x: f32, y: f32) -> Vec3 {
+        Vec3::new(x, y, 0.0)
+    }
+    /// Rotate the section by `degrees` clockwise.
+    #[modify(write_neq(.0.0.rotation))]
+    pub fn rotate(degrees: f32) -> Quat {
+        Quat::from_rotation_z(-degrees.to_radians())
+    }
+    /// Scale the section by `scale`.
+    #[modify(write_neq(.0.0.scale))]
+    pub fn scale(scale: f32) -> Vec3 {
+        Vec3::splat(scale)
+    }
+    /// Move the section in a random direction by up to `intensity` pixels.
+    ///
+    /// Unlike `Shake`, this doesn't change over time. Sections with a different
+    /// `seed` move in a different direction.
+    #[modify(write_neq(.0.0.translation))]
+    #[parse(with = parse_pair)]
+    pub fn jitter(intensity: f32, seed: f32) -> Vec3 {
+        let (x, y) = (random_unit(seed, 0), random_unit(seed + 0.5, 0));
+        Vec3::new(intensity * x, intensity * y, 0.0)
+    }
+    /// Move the section up and down by `amplitude` pixels, once per second.
+    ///
+    /// `phase` is the fraction of a cycle by which this section is ahead.
+    #[modify(context(time = .time), write_neq(.0.0.translation.y))]
+    #[parse(with = parse_pair)]
+    pub fn wave(amplitude: f32, phase: f32, time: &f32) -> f32 {
+        amplitude * oscillate(1.0, phase, *time)
+    }
+    /// Randomly move the section by up to `intensity` pixels.
+    ///
+    /// Sections with a different `seed` move differently.
+    #[modify(context(time = .time), write_mut(.0.0.translation))]
+    #[parse(with = parse_pair)]
+    pub fn shake(intensity: f32, seed: f32, time: &f32, translation: &mut Vec3) {
+        let step = (time * SHAKE_RATE) as u32;
+        translation.x = intensity * random_unit(seed, step);
+        translation.y = intensity * random_unit(seed + 0.5, step);
+    }
+    /// Scale the section up and down, `frequency` times per second.
+    ///
+    /// `phase` is the fraction of a cycle by which this section is ahead.
+    #[modify(context(time = .time), write_neq(.0.0.scale))]
+    #[parse(with = parse_pair)]
+    pub fn pulse(frequency: f32, phase: f32, time: &f32) -> Vec3 {
+        Vec3::splat(1.0 + PULSE_AMPLITUDE * oscillate(frequency, phase, *time))
+    }
+    /// Cycle the Hue of the section's color at `speed` degrees per second,
+    /// starting at `phase` degrees.
+    ///
+    /// Saturation and lightness are kept, so this has no effect on white text.
+    #[modify(context(time = .time), read_write(.1.sections[0].style.color))]
+    #[parse(with = parse_pair)]
+    pub fn rainbow_cycle(speed: f32, phase: f32, time: &f32, color: &mut Color) {
+        let mut hsl = color.as_hsla_f32();
+        hsl[0] = (time * speed + phase).rem_euclid(360.0);
+        *color = Color::hsla(hsl[0], hsl[1], hsl[2], hsl[3]);
+    }
+    /// Multiply the speed at which a [`Typewriter`] reveals this section.
+    ///
+    /// [`Typewriter`]: crate::Typewriter
+    #[modify(read_write(.2.speed))]
+    pub fn reveal_speed(relative_speed: f32, speed: &mut f32) {
+        *speed *= relative_speed;
+    }
+    /// Wait `seconds` more before a [`Typewriter`] reveals this section.
+    ///
+    /// [`Typewriter`]: crate::Typewriter
+    #[modify(read_write(.2.pause))]
+    pub fn reveal_pause(seconds: f32, pause: &mut f32) {
+        *pause += seconds;
+    }
+    /// Display the image at `path` instead of the section's text.
+    ///
+    /// The image must be loaded first, for example with [`AssetServer::load`].
+    #[modify(context(get_image = .images), write_neq(.3.image))]
+    #[parse(with = parse_cow)]
+    pub fn image(path: &Cow<'static, str>, get_image: &GetImage) -> Option<Handle<Image>> {
+        get_image.get(path)
+    }
+    /// Display the image named `name` in [`Icons`] instead of the section's text.
+    ///
+    /// [`Icons`]: crate::Icons
+    #[modify(context(get_image = .images), write_neq(.3.image))]
+    #[parse(with = parse_cow)]
+    pub fn icon(name: &Cow<'static, str>, get_image: &GetImage) -> Option<Handle<Image>> {
+        get_image.icon(name)
+    }
+    /// Set the height of images relative to the font size, `1.0` by default.
+    #[modify(write_neq(.3.size))]
+    pub fn image_size(size: f32) -> f32 {
+        size
+    }
+    /// Underline the section with a `thickness` pixels thick line.
+    ///
+    /// The line has the color of the text if `color` is not set.
+    #[modify(write_neq(.4.underline))]
+    #[parse(with = decoration::parse_line)]
+    pub fn underline(thickness: f32, color: Option<Color>) -> Option<Line> {
+        Some(Line { thickness, color })
+    }
+    /// Strike through the section with a `thickness` pixels thick line.
+    ///
+    /// The line has the color of the text if `color` is not set.
+    #[modify(write_neq(.4.strike))]
+    #[parse(with = decoration::parse_line)]
+    pub fn strike(thickness: f32, color: Option<Color>) -> Option<Line> {
+        Some(Line { thickness, color })
+    }
+    /// Draw a `thickness` pixels thick outline around the section's characters.
+    #[modify(write_neq(.4.outline))]
+    #[parse(with = decoration::parse_outline)]
+    pub fn outline(thickness: f32, color: Color) -> Option<Outline> {
+        Some(Outline { thickness, color })
+    }
+    /// Draw a shadow of the section's text, `x` pixels right and `y` pixels down.
+    #[modify(write_neq(.4.shadow))]
+    #[parse(with = decoration::parse_shadow)]
+    pub fn shadow(x: f32, y: f32, color: Color) -> Option<Shadow> {
+        Some(Shadow { offset: Vec2::new(x, y), color })
+    }
+    /// Make the section a link to `link`, clicking it sends a [`RichTextLinkClicked`].
+    ///
+    /// [`RichTextLinkClicked`]: crate::RichTextLinkClicked
+    #[modify(write_neq(link = .5.0))]
+    #[parse(with = parse_cow)]
+    pub fn link(link: &Cow<'static, str>) -> Option<Cow<'static, str>> {
+        Some(link.clone())
+    }
+    /// Show `tooltip` next to the cursor while the section is hovered.
+    ///
+    /// `tooltip` is a format string, it is parsed when first shown.
+    #[modify(write_neq(tooltip = .6.0))]
+    #[parse(with = parse_cow)]
+    pub fn tooltip(tooltip: &Cow<'static, str>) -> Option<Cow<'static, str>> {
+        Some(tooltip.clone())
+    }
     /// Use an arbitrary [`ModifyBox`] to modify this section.
-    #[modify(dynamic_read_write(depends, changes, item), context(ctx))]
+    #[modify(dynamic_read_write(depends, changes, item), context(ctx = .fonts))]
     pub fn dynamic(boxed: &ModifyBox, ctx: &GetFont, item: ModifierItem) {
         boxed.apply(ctx, item);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::World;
+
+    use super::Sections;
+
+    #[test]
+    #[should_panic(expected = "Sections must not contain the same entity twice")]
+    fn duplicate_sections() {
+        let mut world = World::new();
+        let (first, second) = (world.spawn_empty().id(), world.spawn_empty().id());
+        Sections::new(vec![first, second, first]);
+    }
+}
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use bevy::prelude::*;
 use fab::{impl_modify, modify::Indexed, Modify};
 
-use super::{GetFont, ModifyBox};
+use super::{parse_cow, parse_pair, GetFont, ModifyBox, TextContext};
 
 impl Indexed<Modifier> for Text {
     fn get_mut(&mut self, index: usize) -> Option<&mut TextSection> {
@@ -16,20 +16,47 @@ impl Indexed<Modifier> for Text {
 /// You can create your own operations. At the cost of storing them as a [`ModifyBox`]
 /// and having to be careful about what you update. You create such a `Modifier`
 /// using [`Modifier::Dynamic`].
-#[impl_modify(cuicui_fab_path = fab, no_derive(Debug))]
+#[impl_modify(
+    cuicui_fab_path = fab,
+    cuicui_fab_parse_path = fab_parse,
+    parsable,
+    no_derive(Debug)
+)]
 #[derive(PartialEq)]
 impl Modify for Modifier {
-    type Context<'a> = GetFont<'a>;
+    type Context<'a> = TextContext<'a>;
     type Item<'a> = &'a mut TextSection;
     type MakeItem = TextSection;
     type Items<'a, 'b, 'c> = Text;
 
     /// Set the font to provided `path`.
-    #[modify(context(get_font), write(.style.font))]
+    #[modify(context(get_font = .fonts), write_neq(.style.font))]
+    #[parse(with = parse_cow)]
     pub fn font(path: &Cow<'static, str>, get_font: &GetFont) -> Handle<Font> {
         trace!("Apply =font=: {path:?}");
         get_font.get(path).unwrap_or_default()
     }
+    /// Use the font of the `family` [`FontFamily`], keeping the current face.
+    ///
+    /// [`FontFamily`]: crate::FontFamily
+    #[modify(context(get_font = .fonts), read_write(.style.font))]
+    #[parse(with = parse_cow)]
+    pub fn family(family: &Cow<'static, str>, get_font: &GetFont, font: &mut Handle<Font>) {
+        trace!("Apply =family=: {family:?}");
+        get_font.set_family(family, font);
+    }
+    /// Use the bold face of the current font family if `bold`, the regular
+    /// face otherwise.
+    #[modify(context(get_font = .fonts), read_write(.style.font))]
+    pub fn bold(bold: bool, get_font: &GetFont, font: &mut Handle<Font>) {
+        get_font.set_face(font, |face| face.with_bold(bold));
+    }
+    /// Use the italic face of the current font family if `italic`, the
+    /// upright face otherwise.
+    #[modify(context(get_font = .fonts), read_write(.style.font))]
+    pub fn italic(italic: bool, get_font: &GetFont, font: &mut Handle<Font>) {
+        get_font.set_face(font, |face| face.with_italic(italic));
+    }
     /// Increase the font size relative to the current section.
     #[modify(read_write(.style.font_size))]
     pub fn rel_size(relative_size: f32, font_size: &mut f32) {
@@ -37,12 +64,13 @@ impl Modify for Modifier {
         *font_size *= relative_size;
     }
     /// Set font size to `size`.
-    #[modify(write(.style.font_size))]
+    #[modify(write_neq(.style.font_size))]
     pub fn font_size(size: f32) -> f32 {
         size
     }
     /// Set the color of the [`TextSection`] to `statik`.
-    #[modify(write(.style.color))]
+    #[modify(write_neq(.style.color))]
+    #[parse(with = crate::color::parse)]
     pub fn color(statik: Color) -> Color {
         trace!("Apply ~COLOR~: {statik:?}");
         statik
@@ -57,13 +85,25 @@ impl Modify for Modifier {
     }
     /// Set the text content of the [`TextSection`] to `statik`.
     #[modify(write_mut(.value))]
+    #[parse(with = parse_cow)]
     pub fn content(statik: &Cow<'static, str>, value: &mut String) {
         trace!("Apply $CONTENT$: {statik:?}");
         value.clear();
         value.push_str(statik);
     }
+    /// Cycle the Hue of the section's color at `speed` degrees per second,
+    /// starting at `phase` degrees.
+    ///
+    /// Saturation and lightness are kept, so this has no effect on white text.
+    #[modify(context(time = .time), read_write(.style.color))]
+    #[parse(with = parse_pair)]
+    pub fn rainbow_cycle(speed: f32, phase: f32, time: &f32, color: &mut Color) {
+        let mut hsl = color.as_hsla_f32();
+        hsl[0] = (time * speed + phase).rem_euclid(360.0);
+        *color = Color::hsla(hsl[0], hsl[1], hsl[2], hsl[3]);
+    }
     /// Use an arbitrary [`ModifyBox`] to modify this section.
-    #[modify(dynamic_read_write(depends, changes, item), context(ctx))]
+    #[modify(dynamic_read_write(depends, changes, item), context(ctx = .fonts))]
     pub fn dynamic(boxed: &ModifyBox, ctx: &GetFont, item: &mut TextSection) {
         boxed.apply(ctx, item);
     }
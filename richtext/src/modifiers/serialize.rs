@@ -0,0 +1,321 @@
+//! Serialize and deserialize [`Modifier`]s, to save and load bindings.
+//!
+//! [`Modifier::Dynamic`] can't be serialized, trying to serialize it returns
+//! an error.
+
+use std::borrow::Cow;
+
+use bevy::prelude::Color;
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Modifier;
+
+/// The serialized form of [`Modifier`], without the `Dynamic` variant.
+///
+/// Variant names are part of the save format, they are set explicitly rather
+/// than derived from the rust names. `Modifier`'s `statik` fields are saved
+/// under a name describing their content.
+#[derive(Serialize, Deserialize)]
+enum Serialized<'a> {
+    #[serde(rename = "Font")]
+    Font {
+        path: Cow<'a, str>,
+    },
+    #[serde(rename = "Family")]
+    Family {
+        family: Cow<'a, str>,
+    },
+    #[serde(rename = "Bold")]
+    Bold {
+        bold: bool,
+    },
+    #[serde(rename = "Italic")]
+    Italic {
+        italic: bool,
+    },
+    #[serde(rename = "RelSize")]
+    RelSize {
+        relative_size: f32,
+    },
+    #[serde(rename = "FontSize")]
+    FontSize {
+        size: f32,
+    },
+    #[serde(rename = "Color")]
+    Color {
+        #[serde(rename = "color")]
+        statik: Color,
+    },
+    #[serde(rename = "HueOffset")]
+    HueOffset {
+        offset: f32,
+    },
+    #[serde(rename = "Content")]
+    Content {
+        #[serde(rename = "content")]
+        statik: Cow<'a, str>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Wave")]
+    Wave {
+        amplitude: f32,
+        phase: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Shake")]
+    Shake {
+        intensity: f32,
+        seed: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Pulse")]
+    Pulse {
+        frequency: f32,
+        phase: f32,
+    },
+    #[serde(rename = "RainbowCycle")]
+    RainbowCycle {
+        speed: f32,
+        phase: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Offset")]
+    Offset {
+        x: f32,
+        y: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Rotate")]
+    Rotate {
+        degrees: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Scale")]
+    Scale {
+        scale: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Jitter")]
+    Jitter {
+        intensity: f32,
+        seed: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "RevealSpeed")]
+    RevealSpeed {
+        relative_speed: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "RevealPause")]
+    RevealPause {
+        seconds: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Image")]
+    Image {
+        path: Cow<'a, str>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Icon")]
+    Icon {
+        name: Cow<'a, str>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "ImageSize")]
+    ImageSize {
+        size: f32,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Underline")]
+    Underline {
+        thickness: f32,
+        color: Option<Color>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Strike")]
+    Strike {
+        thickness: f32,
+        color: Option<Color>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Outline")]
+    Outline {
+        thickness: f32,
+        color: Color,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Shadow")]
+    Shadow {
+        x: f32,
+        y: f32,
+        color: Color,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Link")]
+    Link {
+        link: Cow<'a, str>,
+    },
+    #[cfg(feature = "cresustext")]
+    #[serde(rename = "Tooltip")]
+    Tooltip {
+        tooltip: Cow<'a, str>,
+    },
+}
+
+impl Serialize for Modifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn borrowed(str: &str) -> Cow<'_, str> {
+            Cow::Borrowed(str)
+        }
+        let serialized = match self {
+            Modifier::Font { path } => Serialized::Font { path: borrowed(path) },
+            Modifier::Family { family } => Serialized::Family { family: borrowed(family) },
+            &Modifier::Bold { bold } => Serialized::Bold { bold },
+            &Modifier::Italic { italic } => Serialized::Italic { italic },
+            Modifier::RelSize { relative_size } => {
+                Serialized::RelSize { relative_size: *relative_size }
+            }
+            Modifier::FontSize { size } => Serialized::FontSize { size: *size },
+            Modifier::Color { statik } => Serialized::Color { statik: *statik },
+            Modifier::HueOffset { offset } => Serialized::HueOffset { offset: *offset },
+            Modifier::Content { statik } => Serialized::Content { statik: borrowed(statik) },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Wave { amplitude, phase } => Serialized::Wave { amplitude, phase },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Shake { intensity, seed } => Serialized::Shake { intensity, seed },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Pulse { frequency, phase } => Serialized::Pulse { frequency, phase },
+            &Modifier::RainbowCycle { speed, phase } => Serialized::RainbowCycle { speed, phase },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Offset { x, y } => Serialized::Offset { x, y },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Rotate { degrees } => Serialized::Rotate { degrees },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Scale { scale } => Serialized::Scale { scale },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Jitter { intensity, seed } => Serialized::Jitter { intensity, seed },
+            #[cfg(feature = "cresustext")]
+            &Modifier::RevealSpeed { relative_speed } => Serialized::RevealSpeed { relative_speed },
+            #[cfg(feature = "cresustext")]
+            &Modifier::RevealPause { seconds } => Serialized::RevealPause { seconds },
+            #[cfg(feature = "cresustext")]
+            Modifier::Image { path } => Serialized::Image { path: borrowed(path) },
+            #[cfg(feature = "cresustext")]
+            Modifier::Icon { name } => Serialized::Icon { name: borrowed(name) },
+            #[cfg(feature = "cresustext")]
+            &Modifier::ImageSize { size } => Serialized::ImageSize { size },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Underline { thickness, color } => Serialized::Underline { thickness, color },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Strike { thickness, color } => Serialized::Strike { thickness, color },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Outline { thickness, color } => Serialized::Outline { thickness, color },
+            #[cfg(feature = "cresustext")]
+            &Modifier::Shadow { x, y, color } => Serialized::Shadow { x, y, color },
+            #[cfg(feature = "cresustext")]
+            Modifier::Link { link } => Serialized::Link { link: borrowed(link) },
+            #[cfg(feature = "cresustext")]
+            Modifier::Tooltip { tooltip } => Serialized::Tooltip { tooltip: borrowed(tooltip) },
+            Modifier::Dynamic { boxed, .. } => {
+                let msg = format!("Can't serialize the dynamic modifier {boxed:?}");
+                return Err(ser::Error::custom(msg));
+            }
+        };
+        serialized.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Modifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = |cow: Cow<str>| Cow::Owned(cow.into_owned());
+        Ok(match Serialized::deserialize(deserializer)? {
+            Serialized::Font { path } => Modifier::Font { path: owned(path) },
+            Serialized::Family { family } => Modifier::Family { family: owned(family) },
+            Serialized::Bold { bold } => Modifier::Bold { bold },
+            Serialized::Italic { italic } => Modifier::Italic { italic },
+            Serialized::RelSize { relative_size } => Modifier::RelSize { relative_size },
+            Serialized::FontSize { size } => Modifier::FontSize { size },
+            Serialized::Color { statik } => Modifier::Color { statik },
+            Serialized::HueOffset { offset } => Modifier::HueOffset { offset },
+            Serialized::Content { statik } => Modifier::Content { statik: owned(statik) },
+            #[cfg(feature = "cresustext")]
+            Serialized::Wave { amplitude, phase } => Modifier::Wave { amplitude, phase },
+            #[cfg(feature = "cresustext")]
+            Serialized::Shake { intensity, seed } => Modifier::Shake { intensity, seed },
+            #[cfg(feature = "cresustext")]
+            Serialized::Pulse { frequency, phase } => Modifier::Pulse { frequency, phase },
+            Serialized::RainbowCycle { speed, phase } => Modifier::RainbowCycle { speed, phase },
+            #[cfg(feature = "cresustext")]
+            Serialized::Offset { x, y } => Modifier::Offset { x, y },
+            #[cfg(feature = "cresustext")]
+            Serialized::Rotate { degrees } => Modifier::Rotate { degrees },
+            #[cfg(feature = "cresustext")]
+            Serialized::Scale { scale } => Modifier::Scale { scale },
+            #[cfg(feature = "cresustext")]
+            Serialized::Jitter { intensity, seed } => Modifier::Jitter { intensity, seed },
+            #[cfg(feature = "cresustext")]
+            Serialized::RevealSpeed { relative_speed } => Modifier::RevealSpeed { relative_speed },
+            #[cfg(feature = "cresustext")]
+            Serialized::RevealPause { seconds } => Modifier::RevealPause { seconds },
+            #[cfg(feature = "cresustext")]
+            Serialized::Image { path } => Modifier::Image { path: owned(path) },
+            #[cfg(feature = "cresustext")]
+            Serialized::Icon { name } => Modifier::Icon { name: owned(name) },
+            #[cfg(feature = "cresustext")]
+            Serialized::ImageSize { size } => Modifier::ImageSize { size },
+            #[cfg(feature = "cresustext")]
+            Serialized::Underline { thickness, color } => Modifier::Underline { thickness, color },
+            #[cfg(feature = "cresustext")]
+            Serialized::Strike { thickness, color } => Modifier::Strike { thickness, color },
+            #[cfg(feature = "cresustext")]
+            Serialized::Outline { thickness, color } => Modifier::Outline { thickness, color },
+            #[cfg(feature = "cresustext")]
+            Serialized::Shadow { x, y, color } => Modifier::Shadow { x, y, color },
+            #[cfg(feature = "cresustext")]
+            Serialized::Link { link } => Modifier::Link { link: owned(link) },
+            #[cfg(feature = "cresustext")]
+            Serialized::Tooltip { tooltip } => Modifier::Tooltip { tooltip: owned(tooltip) },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn round_trip(modifier: Modifier) {
+        let saved = ron::to_string(&modifier).unwrap();
+        let loaded: Modifier = ron::from_str(&saved).unwrap();
+        assert_eq!(saved, ron::to_string(&loaded).unwrap());
+    }
+    #[test]
+    fn statik_is_not_saved() {
+        let content = Modifier::Content { statik: "Hello".into() };
+        assert_eq!(ron::to_string(&content).unwrap(), r#"Content(content:"Hello")"#);
+
+        let color = ron::to_string(&Modifier::Color { statik: Color::RED }).unwrap();
+        assert!(color.starts_with("Color(color:"), "{color}");
+    }
+    #[test]
+    fn modifiers_round_trip() {
+        round_trip(Modifier::Font { path: "fonts/bold.ttf".into() });
+        round_trip(Modifier::Family { family: "serif".into() });
+        round_trip(Modifier::Bold { bold: true });
+        round_trip(Modifier::RelSize { relative_size: 1.5 });
+        round_trip(Modifier::FontSize { size: 32.0 });
+        round_trip(Modifier::Color { statik: Color::rgba(1.0, 0.5, 0.25, 1.0) });
+        round_trip(Modifier::HueOffset { offset: 90.0 });
+        round_trip(Modifier::Content { statik: "Hello world".into() });
+        round_trip(Modifier::RainbowCycle { speed: 1.0, phase: 0.5 });
+    }
+    #[cfg(feature = "cresustext")]
+    #[test]
+    fn cresustext_modifiers_round_trip() {
+        round_trip(Modifier::Wave { amplitude: 2.0, phase: 0.5 });
+        round_trip(Modifier::Offset { x: 1.0, y: -1.0 });
+        round_trip(Modifier::Underline { thickness: 2.0, color: None });
+        round_trip(Modifier::Shadow { x: 1.0, y: 2.0, color: Color::BLACK });
+        round_trip(Modifier::Link { link: "next_page".into() });
+    }
+}
@@ -0,0 +1,119 @@
+//! Glossary tooltips, with the `Tooltip` modifier.
+//!
+//! The value of `Tooltip` is itself a format string. It is parsed only once
+//! the section is hovered, when the floating rich text showing it is spawned.
+//!
+//! Only one tooltip is shown at a time, so consecutive sections with the same
+//! tooltip, such as the characters of a word chopped by `Split::ByChar`, share
+//! the same panel.
+
+use std::borrow::Cow;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::MakeRichText;
+
+/// The global [`ZIndex`] of tooltip panels, so that they are drawn above the
+/// rest of the UI.
+const TOOLTIP_Z_INDEX: i32 = 1000;
+
+/// The tooltip of a section, set by the `Tooltip` modifier.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct SectionTooltip(pub Option<Cow<'static, str>>);
+
+/// How tooltip panels look.
+///
+/// The text of a tooltip has the font and font size of the hovered section.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct TooltipStyle {
+    /// Color of the panel behind the tooltip's text.
+    pub background: Color,
+    /// Color of the tooltip's text, unless set in its format string.
+    pub color: Color,
+    /// Space in pixels between the edges of the panel and its text.
+    pub padding: f32,
+    /// How many pixels right and down of the cursor the panel is.
+    pub offset: Vec2,
+}
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        TooltipStyle {
+            background: Color::rgba(0.1, 0.1, 0.1, 0.9),
+            color: Color::WHITE,
+            padding: 6.0,
+            offset: Vec2::new(16.0, 16.0),
+        }
+    }
+}
+
+/// Show the tooltip of the hovered section next to the cursor.
+pub fn tooltip_system(
+    mut cmds: Commands,
+    mut shown: Local<Option<(Cow<'static, str>, Entity)>>,
+    sections: Query<(&SectionTooltip, &Interaction, &Text)>,
+    mut panels: Query<&mut Style>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    style: Res<TooltipStyle>,
+) {
+    let mut hovered = sections.iter().filter(|(_, i, _)| **i != Interaction::None);
+    let hovered = hovered.find_map(|(tooltip, _, text)| Some((tooltip.0.as_ref()?, text)));
+    let window = windows.get_single().ok();
+    let position = window.and_then(|window| panel_position(window, style.offset));
+    match (hovered, &*shown) {
+        (Some((tooltip, _)), Some((shown_tooltip, panel))) if tooltip == shown_tooltip => {
+            let Ok(mut panel) = panels.get_mut(*panel) else { return; };
+            if let Some(position) = position.filter(|p| *p != panel.position) {
+                panel.position = position;
+            }
+        }
+        (None, None) => {}
+        (hovered, _) => {
+            if let Some((_, panel)) = shown.take() {
+                cmds.entity(panel).despawn_recursive();
+            }
+            let Some((tooltip, text)) = hovered else { return; };
+            let position = position.unwrap_or_default();
+            let panel = spawn_panel(&mut cmds, tooltip, text, &style, position);
+            *shown = Some((tooltip.clone(), panel));
+        }
+    }
+}
+/// Where the panel is, `offset` away from the cursor.
+fn panel_position(window: &Window, offset: Vec2) -> Option<UiRect> {
+    let cursor = window.cursor_position()?;
+    // The cursor position starts at the bottom of the window, not the top.
+    let top = window.height() - cursor.y + offset.y;
+    Some(UiRect {
+        left: Val::Px(cursor.x + offset.x),
+        top: Val::Px(top),
+        ..default()
+    })
+}
+fn spawn_panel(
+    cmds: &mut Commands,
+    tooltip: &str,
+    section: &Text,
+    style: &TooltipStyle,
+    position: UiRect,
+) -> Entity {
+    let font_style = section.sections.first().map(|s| &s.style);
+    let mut text_style = font_style.cloned().unwrap_or_default();
+    text_style.color = style.color;
+
+    let panel = NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position,
+            padding: UiRect::all(Val::Px(style.padding)),
+            ..default()
+        },
+        background_color: style.background.into(),
+        z_index: ZIndex::Global(TOOLTIP_Z_INDEX),
+        ..default()
+    };
+    let mut panel = cmds.spawn(panel);
+    panel.with_children(|cmds| {
+        cmds.spawn(MakeRichText::new(tooltip).with_text_style(text_style));
+    });
+    panel.id()
+}
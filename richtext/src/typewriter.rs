@@ -0,0 +1,272 @@
+//! Reveal rich text section by section, like a typewriter.
+//!
+//! Each section of a rich text is its own entity with `cresustext`, the
+//! [`Typewriter`] hides them and shows them one after another. Use the
+//! `Type` or `TypeWords` styles to split text in one section per character
+//! or per word.
+
+use bevy::prelude::*;
+
+use crate::modifiers::Sections;
+
+/// How a [`Typewriter`] reveals a single section, set by the `RevealSpeed`
+/// and `RevealPause` modifiers.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct RevealTiming {
+    /// Multiplier of the [`Typewriter::rate`] for this section.
+    pub speed: f32,
+    /// Seconds to wait before revealing this section.
+    pub pause: f32,
+}
+impl Default for RevealTiming {
+    fn default() -> Self {
+        RevealTiming { speed: 1.0, pause: 0.0 }
+    }
+}
+impl RevealTiming {
+    /// Seconds it takes to reveal this section at `rate` sections per second.
+    fn delay(&self, rate: f32) -> f32 {
+        self.pause + 1.0 / (rate * self.speed)
+    }
+}
+
+/// A section of a [`Typewriter`] was revealed.
+///
+/// Use it to play a sound for each character. Sections revealed with
+/// [`Typewriter::skip`] are all sent in the same frame.
+#[derive(Debug)]
+pub struct TypewriterRevealed {
+    /// The entity with the [`Typewriter`].
+    pub typewriter: Entity,
+    /// The entity of the revealed section.
+    pub section: Entity,
+    /// Index of the revealed section in the rich text.
+    pub index: usize,
+}
+
+/// All the sections of a [`Typewriter`] are revealed.
+#[derive(Debug)]
+pub struct TypewriterFinished {
+    pub typewriter: Entity,
+}
+
+/// Reveal the sections of the rich text on the same entity one after
+/// another, at `rate` sections per second.
+///
+/// Sections are revealed at a different speed or after a pause when the
+/// `RevealSpeed` and `RevealPause` modifiers are set on them.
+#[derive(Component, Clone, Debug)]
+pub struct Typewriter {
+    /// How many sections are revealed per second.
+    pub rate: f32,
+    /// Seconds elapsed since the last section was revealed.
+    elapsed: f32,
+    revealed: usize,
+    /// Whether to update the visibility of all sections.
+    sync: bool,
+    advance: bool,
+    skip: bool,
+    finished: bool,
+}
+impl Typewriter {
+    pub fn new(rate: f32) -> Self {
+        Typewriter {
+            rate,
+            elapsed: 0.0,
+            revealed: 0,
+            sync: true,
+            advance: false,
+            skip: false,
+            finished: false,
+        }
+    }
+    /// Reveal all the remaining sections on the next update.
+    pub fn skip(&mut self) {
+        self.skip = true;
+    }
+    /// Reveal the next section now, without waiting for its pause.
+    pub fn advance(&mut self) {
+        self.advance = true;
+    }
+    /// Hide all sections and start revealing them again.
+    pub fn restart(&mut self) {
+        *self = Typewriter::new(self.rate);
+    }
+    /// How many sections are revealed.
+    pub fn revealed(&self) -> usize {
+        self.revealed
+    }
+    /// Whether all sections are revealed.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Hide the `sections` of `entity` its [`Typewriter`] didn't reveal yet, if
+/// it has one.
+///
+/// This runs when the sections are spawned, so that they are not visible
+/// for a frame before [`reveal_system`] hides them.
+pub(crate) fn hide_unrevealed(sections: Vec<Entity>) -> impl FnOnce(Entity, &mut World) {
+    move |entity, world| {
+        let Some(typewriter) = world.get::<Typewriter>(entity) else { return; };
+        for &section in sections.iter().skip(typewriter.revealed) {
+            if let Some(mut visibility) = world.get_mut::<Visibility>(section) {
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+/// Reveal sections of [`Typewriter`]s as time passes.
+pub fn reveal_system(
+    time: Res<Time>,
+    mut typewriters: Query<(Entity, &mut Typewriter, &Sections)>,
+    mut sections: Query<(&mut Visibility, &RevealTiming)>,
+    mut revealed: EventWriter<TypewriterRevealed>,
+    mut finished: EventWriter<TypewriterFinished>,
+) {
+    use Visibility::{Hidden, Inherited};
+
+    for (typewriter, mut state, children) in &mut typewriters {
+        if state.finished && !state.sync {
+            continue;
+        }
+        let state = &mut *state;
+        state.revealed = state.revealed.min(children.len());
+
+        if state.sync {
+            state.sync = false;
+            for (i, &section) in children.iter().enumerate() {
+                let Ok((mut visibility, _)) = sections.get_mut(section) else { continue; };
+                let is_revealed = i < state.revealed;
+                *visibility = if is_revealed { Inherited } else { Hidden };
+            }
+        }
+        state.elapsed += time.delta_seconds();
+
+        while let Some(&section) = children.get(state.revealed) {
+            let Ok((mut visibility, timing)) = sections.get_mut(section) else { break; };
+            let delay = timing.delay(state.rate);
+            let now = state.advance || state.skip;
+            if !now && state.elapsed < delay {
+                break;
+            }
+            state.elapsed = if now { 0.0 } else { state.elapsed - delay };
+            state.advance = false;
+            *visibility = Inherited;
+            revealed.send(TypewriterRevealed { typewriter, section, index: state.revealed });
+            state.revealed += 1;
+        }
+        state.skip = false;
+        if state.revealed == children.len() && !state.finished {
+            state.finished = true;
+            finished.send(TypewriterFinished { typewriter });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::event::Event;
+
+    use super::*;
+
+    fn typewriter_world(sections: usize) -> (World, Schedule, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Events<TypewriterRevealed>>();
+        world.init_resource::<Events<TypewriterFinished>>();
+        let mut time = Time::default();
+        time.update();
+        world.insert_resource(time);
+
+        let timing = RevealTiming::default();
+        let spawn = |_| world.spawn((Visibility::Inherited, timing)).id();
+        let sections = (0..sections).map(spawn).collect();
+        let typewriter = Typewriter::new(1.0);
+        let entity = world.spawn((typewriter, Sections::new(sections))).id();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(reveal_system);
+        schedule.run(&mut world);
+        (world, schedule, entity)
+    }
+    fn step(world: &mut World, schedule: &mut Schedule, seconds: f32) {
+        let mut time = world.resource_mut::<Time>();
+        let last_update = time.last_update().unwrap();
+        time.update_with_instant(last_update + Duration::from_secs_f32(seconds));
+        schedule.run(world);
+    }
+    fn drain<E: Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+    fn revealed_indices(world: &mut World) -> Vec<usize> {
+        let events = drain::<TypewriterRevealed>(world);
+        events.into_iter().map(|event| event.index).collect()
+    }
+    fn visible(world: &World, entity: Entity) -> Vec<bool> {
+        let sections = world.get::<Sections>(entity).unwrap();
+        let is_visible = |&section| world.get(section) == Some(&Visibility::Inherited);
+        sections.iter().map(is_visible).collect()
+    }
+    fn typewriter(world: &mut World, entity: Entity) -> Mut<'_, Typewriter> {
+        world.get_mut::<Typewriter>(entity).unwrap()
+    }
+
+    #[test]
+    fn reveal_over_time() {
+        let (mut world, mut schedule, entity) = typewriter_world(3);
+        assert_eq!(visible(&world, entity), [false, false, false]);
+
+        step(&mut world, &mut schedule, 1.0);
+        assert_eq!(visible(&world, entity), [true, false, false]);
+        assert_eq!(revealed_indices(&mut world), [0]);
+        assert!(drain::<TypewriterFinished>(&mut world).is_empty());
+
+        step(&mut world, &mut schedule, 2.5);
+        assert_eq!(visible(&world, entity), [true, true, true]);
+        assert_eq!(revealed_indices(&mut world), [1, 2]);
+        let finished = drain::<TypewriterFinished>(&mut world);
+        let finished: Vec<_> = finished.into_iter().map(|event| event.typewriter).collect();
+        assert_eq!(finished, [entity]);
+
+        step(&mut world, &mut schedule, 1.0);
+        assert!(revealed_indices(&mut world).is_empty());
+        assert!(drain::<TypewriterFinished>(&mut world).is_empty());
+    }
+    #[test]
+    fn advance() {
+        let (mut world, mut schedule, entity) = typewriter_world(3);
+        step(&mut world, &mut schedule, 0.5);
+        assert!(revealed_indices(&mut world).is_empty());
+
+        typewriter(&mut world, entity).advance();
+        step(&mut world, &mut schedule, 0.0);
+        assert_eq!(visible(&world, entity), [true, false, false]);
+        assert_eq!(revealed_indices(&mut world), [0]);
+
+        // Advancing resets the time elapsed since the last revealed section.
+        step(&mut world, &mut schedule, 0.5);
+        assert!(revealed_indices(&mut world).is_empty());
+        step(&mut world, &mut schedule, 0.5);
+        assert_eq!(revealed_indices(&mut world), [1]);
+    }
+    #[test]
+    fn skip() {
+        let (mut world, mut schedule, entity) = typewriter_world(3);
+        step(&mut world, &mut schedule, 1.0);
+        assert_eq!(revealed_indices(&mut world), [0]);
+
+        typewriter(&mut world, entity).skip();
+        step(&mut world, &mut schedule, 0.0);
+        assert_eq!(visible(&world, entity), [true, true, true]);
+        assert_eq!(revealed_indices(&mut world), [1, 2]);
+        assert_eq!(drain::<TypewriterFinished>(&mut world).len(), 1);
+
+        let typewriter = typewriter(&mut world, entity);
+        assert_eq!(typewriter.revealed(), 3);
+        assert!(typewriter.is_finished());
+    }
+}